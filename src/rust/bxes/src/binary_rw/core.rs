@@ -10,12 +10,12 @@ use super::error::BinaryError;
 pub type Result<T> = std::result::Result<T, BinaryError>;
 
 macro_rules! encode {
-    ($endian:expr, $value:expr, $stream:expr) => {
+    ($endian:expr, $value:expr, $writer:expr) => {
         let data = match $endian {
             Endian::Little => $value.to_le_bytes(),
             Endian::Big => $value.to_be_bytes(),
         };
-        return Ok($stream.write(&data)?);
+        return Ok($writer.write_stream(&data)?);
     };
 }
 
@@ -54,9 +54,25 @@ pub trait SeekStream {
     fn len(&self) -> Result<usize>;
 }
 
+impl<S: SeekStream + ?Sized> SeekStream for Box<S> {
+    fn seek(&mut self, to: usize) -> Result<usize> {
+        (**self).seek(to)
+    }
+
+    fn tell(&mut self) -> Result<usize> {
+        (**self).tell()
+    }
+
+    fn len(&self) -> Result<usize> {
+        (**self).len()
+    }
+}
+
 /// Trait for a readable stream.
 pub trait ReadStream: Read + SeekStream {}
 
+impl<S: ReadStream + ?Sized> ReadStream for Box<S> {}
+
 /// Trait for a writable stream.
 pub trait WriteStream: Write + SeekStream {}
 
@@ -64,11 +80,12 @@ pub trait WriteStream: Write + SeekStream {}
 pub struct BinaryReader<'a> {
     stream: &'a mut dyn ReadStream,
     endian: Endian,
+    checksum: Option<u32>,
 }
 
 impl<'a> Read for BinaryReader<'a> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.stream.read(buf)
+        self.read_stream(buf)
     }
 }
 
@@ -89,7 +106,31 @@ impl<'a> SeekStream for BinaryReader<'a> {
 impl<'a> BinaryReader<'a> {
     /// Create a binary reader with the given endianness.
     pub fn new(stream: &'a mut impl ReadStream, endian: Endian) -> Self {
-        Self { stream, endian }
+        Self {
+            stream,
+            endian,
+            checksum: None,
+        }
+    }
+
+    /// Starts updating the CRC32C checksum with the read bytes, the checksum
+    /// continues the given one, `0` starts a new checksum.
+    pub fn start_checksum(&mut self, checksum: u32) {
+        self.checksum = Some(checksum);
+    }
+
+    /// Stops updating the checksum and returns it, `None` if the checksum was not started.
+    pub fn stop_checksum(&mut self) -> Option<u32> {
+        self.checksum.take()
+    }
+
+    fn read_stream(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.stream.read(buf)?;
+        if let Some(checksum) = self.checksum.as_mut() {
+            *checksum = crc32c::crc32c_append(*checksum, &buf[..read]);
+        }
+
+        Ok(read)
     }
 
     /// Read a length-prefixed `String` from the stream.
@@ -97,12 +138,12 @@ impl<'a> BinaryReader<'a> {
         let chars = if cfg!(feature = "wasm32") {
             let str_len = self.read_u32()?;
             let mut chars: Vec<u8> = vec![0; str_len as usize];
-            self.stream.read(&mut chars)?;
+            self.read_stream(&mut chars)?;
             chars
         } else {
             let str_len = self.read_usize()?;
             let mut chars: Vec<u8> = vec![0; str_len];
-            self.stream.read(&mut chars)?;
+            self.read_stream(&mut chars)?;
             chars
         };
         Ok(String::from_utf8(chars)?)
@@ -131,14 +172,14 @@ impl<'a> BinaryReader<'a> {
     /// Read a `f32` from the stream.
     pub fn read_f32(&mut self) -> Result<f32> {
         let mut buffer: [u8; 4] = [0; 4];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, f32);
     }
 
     /// Read a `f64` from the stream.
     pub fn read_f64(&mut self) -> Result<f64> {
         let mut buffer: [u8; 8] = [0; 8];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, f64);
     }
 
@@ -146,7 +187,7 @@ impl<'a> BinaryReader<'a> {
     #[cfg(target_arch = "wasm32")]
     pub fn read_isize(&mut self) -> Result<isize> {
         let mut buffer: [u8; 4] = [0; 4];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, isize);
     }
 
@@ -154,7 +195,7 @@ impl<'a> BinaryReader<'a> {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn read_isize(&mut self) -> Result<isize> {
         let mut buffer: [u8; 8] = [0; 8];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, isize);
     }
 
@@ -162,7 +203,7 @@ impl<'a> BinaryReader<'a> {
     #[cfg(target_arch = "wasm32")]
     pub fn read_usize(&mut self) -> Result<usize> {
         let mut buffer: [u8; 4] = [0; 4];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, usize);
     }
 
@@ -170,63 +211,63 @@ impl<'a> BinaryReader<'a> {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn read_usize(&mut self) -> Result<usize> {
         let mut buffer: [u8; 8] = [0; 8];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, usize);
     }
 
     /// Read a `u64` from the stream.
     pub fn read_u64(&mut self) -> Result<u64> {
         let mut buffer: [u8; 8] = [0; 8];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, u64);
     }
 
     /// Read an `i64` from the stream.
     pub fn read_i64(&mut self) -> Result<i64> {
         let mut buffer: [u8; 8] = [0; 8];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, i64);
     }
 
     /// Read a `u32` from the stream.
     pub fn read_u32(&mut self) -> Result<u32> {
         let mut buffer: [u8; 4] = [0; 4];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, u32);
     }
 
     /// Read an `i32` from the stream.
     pub fn read_i32(&mut self) -> Result<i32> {
         let mut buffer: [u8; 4] = [0; 4];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, i32);
     }
 
     /// Read a `u16` from the stream.
     pub fn read_u16(&mut self) -> Result<u16> {
         let mut buffer: [u8; 2] = [0; 2];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, u16);
     }
 
     /// Read an `i16` from the stream.
     pub fn read_i16(&mut self) -> Result<i16> {
         let mut buffer: [u8; 2] = [0; 2];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, i16);
     }
 
     /// Read a `u8` from the stream.
     pub fn read_u8(&mut self) -> Result<u8> {
         let mut buffer: [u8; 1] = [0; 1];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, u8);
     }
 
     /// Read an `i8` from the stream.
     pub fn read_i8(&mut self) -> Result<i8> {
         let mut buffer: [u8; 1] = [0; 1];
-        self.stream.read(&mut buffer)?;
+        self.read_stream(&mut buffer)?;
         decode!(self.endian, buffer, i8);
     }
 }
@@ -235,6 +276,7 @@ impl<'a> BinaryReader<'a> {
 pub struct BinaryWriter<'a> {
     stream: &'a mut dyn WriteStream,
     endian: Endian,
+    checksum: Option<u32>,
 }
 
 impl<'a> SeekStream for BinaryWriter<'a> {
@@ -254,7 +296,31 @@ impl<'a> SeekStream for BinaryWriter<'a> {
 impl<'a> BinaryWriter<'a> {
     /// Create a binary writer with the given endianness.
     pub fn new(stream: &'a mut impl WriteStream, endian: Endian) -> Self {
-        Self { stream, endian }
+        Self {
+            stream,
+            endian,
+            checksum: None,
+        }
+    }
+
+    /// Starts updating the CRC32C checksum with the written bytes, the checksum
+    /// continues the given one, `0` starts a new checksum.
+    pub fn start_checksum(&mut self, checksum: u32) {
+        self.checksum = Some(checksum);
+    }
+
+    /// Stops updating the checksum and returns it, `None` if the checksum was not started.
+    pub fn stop_checksum(&mut self) -> Option<u32> {
+        self.checksum.take()
+    }
+
+    fn write_stream(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        let written = self.stream.write(data)?;
+        if let Some(checksum) = self.checksum.as_mut() {
+            *checksum = crc32c::crc32c_append(*checksum, &data[..written]);
+        }
+
+        Ok(written)
     }
 
     /// Write a length-prefixed `String` to the stream.
@@ -269,7 +335,7 @@ impl<'a> BinaryWriter<'a> {
         } else {
             self.write_usize(bytes.len())?;
         }
-        Ok(self.stream.write(&bytes.to_vec())?)
+        Ok(self.write_stream(bytes)?)
     }
 
     /// Write a character to the stream.
@@ -285,67 +351,67 @@ impl<'a> BinaryWriter<'a> {
 
     /// Write a `f32` to the stream.
     pub fn write_f32<V: Borrow<f32>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write a `f64` to the stream.
     pub fn write_f64<V: Borrow<f64>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write an `isize` to the stream.
     pub fn write_isize<V: Borrow<isize>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write a `usize` to the stream.
     pub fn write_usize<V: Borrow<usize>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write a `u64` to the stream.
     pub fn write_u64<V: Borrow<u64>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write an `i64` to the stream.
     pub fn write_i64<V: Borrow<i64>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write a `u32` to the stream.
     pub fn write_u32<V: Borrow<u32>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write an `i32` to the stream.
     pub fn write_i32<V: Borrow<i32>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write a `u16` to the stream.
     pub fn write_u16<V: Borrow<u16>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write an `i16` to the stream.
     pub fn write_i16<V: Borrow<i16>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write a `u8` to the stream.
     pub fn write_u8<V: Borrow<u8>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write an `i8` to the stream.
     pub fn write_i8<V: Borrow<i8>>(&mut self, value: V) -> Result<usize> {
-        encode!(self.endian, value.borrow(), self.stream);
+        encode!(self.endian, value.borrow(), self);
     }
 
     /// Write a byte buffer to the stream.
     pub fn write_bytes<B: AsRef<[u8]>>(&mut self, data: B) -> Result<usize> {
-        Ok(self.stream.write(data.as_ref())?)
+        Ok(self.write_stream(data.as_ref())?)
     }
 
     /// Write a byte buffer to the stream.
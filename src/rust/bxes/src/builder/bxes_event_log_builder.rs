@@ -0,0 +1,107 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::{
+    constants::BXES_VERSION,
+    models::{BxesEvent, BxesEventLog, BxesEventLogMetadata, BxesTraceVariant, BxesValue},
+};
+
+use super::event_equivalence::{BxesEventEquality, BxesEventEquivalence};
+
+/// Builds a log from individual traces, equal traces are grouped into one trace variant.
+///
+/// Traces are equal if their metadata is equal and their events are pairwise equivalent
+/// according to the equivalence of the builder, a trace variant keeps events of its first trace.
+pub struct BxesEventLogBuilder<E: BxesEventEquivalence = BxesEventEquality> {
+    pub version: u32,
+    pub metadata: BxesEventLogMetadata,
+    equivalence: E,
+    variants: Vec<BxesTraceVariant>,
+    variants_by_hash: HashMap<u64, Vec<usize>>,
+}
+
+impl BxesEventLogBuilder {
+    pub fn new() -> Self {
+        Self::with_equivalence(BxesEventEquality)
+    }
+}
+
+impl Default for BxesEventLogBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: BxesEventEquivalence> BxesEventLogBuilder<E> {
+    pub fn with_equivalence(equivalence: E) -> Self {
+        Self {
+            version: BXES_VERSION,
+            metadata: BxesEventLogMetadata {
+                extensions: None,
+                classifiers: None,
+                properties: None,
+                globals: None,
+            },
+            equivalence,
+            variants: vec![],
+            variants_by_hash: HashMap::new(),
+        }
+    }
+
+    pub fn add_trace(
+        &mut self,
+        metadata: Vec<(Arc<BxesValue>, Arc<BxesValue>)>,
+        events: Vec<BxesEvent>,
+    ) {
+        self.add_variant(BxesTraceVariant {
+            traces_count: 1,
+            metadata,
+            events,
+        });
+    }
+
+    /// Adds `variant.traces_count` traces of the variant.
+    pub fn add_variant(&mut self, variant: BxesTraceVariant) {
+        let mut hasher = DefaultHasher::new();
+        variant.metadata.hash(&mut hasher);
+        for event in &variant.events {
+            self.equivalence.hash(event, &mut hasher);
+        }
+
+        let bucket = self.variants_by_hash.entry(hasher.finish()).or_default();
+        for index in bucket.iter() {
+            let existing_variant = &mut self.variants[*index];
+            if existing_variant.metadata == variant.metadata
+                && existing_variant.events.len() == variant.events.len()
+                && existing_variant
+                    .events
+                    .iter()
+                    .zip(&variant.events)
+                    .all(|(first, second)| self.equivalence.equals(first, second))
+            {
+                existing_variant.traces_count = existing_variant
+                    .traces_count
+                    .saturating_add(variant.traces_count);
+                return;
+            }
+        }
+
+        bucket.push(self.variants.len());
+        self.variants.push(variant);
+    }
+
+    pub fn variants_count(&self) -> usize {
+        self.variants.len()
+    }
+
+    pub fn build(self) -> BxesEventLog {
+        BxesEventLog {
+            version: self.version,
+            metadata: self.metadata,
+            variants: self.variants,
+        }
+    }
+}
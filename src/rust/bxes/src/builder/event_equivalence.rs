@@ -0,0 +1,42 @@
+use std::hash::{Hash, Hasher};
+
+use crate::models::BxesEvent;
+
+/// Defines which events are considered equal when traces are grouped into trace variants,
+/// events which are equal must produce equal hashes.
+pub trait BxesEventEquivalence {
+    fn hash(&self, event: &BxesEvent, state: &mut dyn Hasher);
+    fn equals(&self, first: &BxesEvent, second: &BxesEvent) -> bool;
+}
+
+/// Events are equal if all their fields are equal.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BxesEventEquality;
+
+impl BxesEventEquivalence for BxesEventEquality {
+    fn hash(&self, event: &BxesEvent, mut state: &mut dyn Hasher) {
+        event.hash(&mut state);
+    }
+
+    fn equals(&self, first: &BxesEvent, second: &BxesEvent) -> bool {
+        first == second
+    }
+}
+
+/// Events are equal if their names, lifecycles and attributes are equal.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BxesIgnoreTimestampsEquivalence;
+
+impl BxesEventEquivalence for BxesIgnoreTimestampsEquivalence {
+    fn hash(&self, event: &BxesEvent, mut state: &mut dyn Hasher) {
+        event.name.hash(&mut state);
+        event.lifecycle.hash(&mut state);
+        event.attributes.hash(&mut state);
+    }
+
+    fn equals(&self, first: &BxesEvent, second: &BxesEvent) -> bool {
+        first.name == second.name
+            && first.lifecycle == second.lifecycle
+            && first.attributes == second.attributes
+    }
+}
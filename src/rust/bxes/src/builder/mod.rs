@@ -0,0 +1,2 @@
+pub mod bxes_event_log_builder;
+pub mod event_equivalence;
@@ -4,3 +4,45 @@ pub const KEY_VALUES_FILE_NAME: &'static str = "kvpair.bxes";
 pub const METADATA_FILE_NAME: &'static str = "metadata.bxes";
 
 pub const BXES_EXT: &'static str = "bxes";
+
+pub const ZIP_MAGIC: [u8; 4] = *b"PK\x03\x04";
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+pub const LZ4_MAGIC: [u8; 4] = [0x04, 0x22, 0x4D, 0x18];
+
+/// The name of the raw log inside of an archive when a single-file log is written to a stream.
+pub const ARCHIVE_LOG_FILE_NAME: &'static str = "log.bxes";
+
+pub const BXES_VERSION: u32 = 6;
+
+/// Versions of the format which can be read and written, the latest one is `BXES_VERSION`.
+pub const SUPPORTED_VERSIONS: &[u32] = &[1, 2, 3, 4, 5, 6];
+
+/// The first version of the format in which events contain lifecycle.
+pub const EVENT_LIFECYCLE_VERSION: u32 = 2;
+
+/// The first version of the format in which only the first event of a trace variant contains
+/// the absolute timestamp, timestamps of other events are zigzag-LEB128 deltas from the previous event.
+pub const DELTA_TIMESTAMPS_VERSION: u32 = 3;
+
+/// The first version of the format in which string lengths, counts, metadata indices,
+/// variants metadata and artifacts and drivers are LEB128 encoded. Counts which writers update in place
+/// are padded to `PADDED_COUNT_SIZE` bytes.
+pub const COMPACT_LENGTHS_VERSION: u32 = 4;
+
+/// The size of a LEB128 count padded with continuation bytes, which fits any `u32`.
+pub const PADDED_COUNT_SIZE: usize = 5;
+
+/// The first version of the format in which the version of each file is followed by the encoding
+/// of string values, which may be front-coded: each string is written as the length of the prefix
+/// shared with the previous string value of the values table and the rest of the string.
+pub const FRONT_CODED_STRINGS_VERSION: u32 = 5;
+
+/// The first version of the format in which the encoding of strings is followed by the kind of checksums
+/// which are written after the values, key-value pairs, metadata and each trace variant.
+/// Checksums cover counts of values, key-value pairs, traces and events, the variants count
+/// and the variants index are each followed by their own checksum.
+pub const CHECKSUMS_VERSION: u32 = 6;
+
+pub const VARIANTS_INDEX_MARKER: u32 = u32::from_le_bytes(*b"BXIX");
+pub const VARIANTS_INDEX_ENTRY_SIZE: usize = std::mem::size_of::<u64>() + std::mem::size_of::<u32>();
+pub const VARIANTS_INDEX_TRAILER_SIZE: usize = std::mem::size_of::<u64>() + std::mem::size_of::<u32>();
@@ -0,0 +1,164 @@
+use std::{collections::HashSet, ops::RangeBounds, sync::Arc};
+
+use crate::{
+    builder::bxes_event_log_builder::BxesEventLogBuilder,
+    models::{BxesEvent, BxesEventLog, BxesEventLogMetadata, BxesTraceVariant, BxesValue},
+    read::{errors::BxesReadError, lazy_bxes_reader::BxesLazyEventLog},
+};
+
+type EventPredicate = Box<dyn Fn(&BxesEvent) -> bool>;
+type VariantPredicate = Box<dyn Fn(&BxesTraceVariant) -> bool>;
+
+/// Selects a subset of a log: variants and events which satisfy all predicates of the filter,
+/// with event attributes optionally projected to the selected keys.
+///
+/// Filtered logs contain only entities which passed the filter, so values and key-value pairs
+/// which are no longer referenced are not written when the filtered log is written.
+#[derive(Default)]
+pub struct BxesLogFilter {
+    variant_predicates: Vec<VariantPredicate>,
+    event_predicates: Vec<EventPredicate>,
+    attribute_keys: Option<HashSet<Arc<BxesValue>>>,
+}
+
+impl BxesLogFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_variant_predicate(
+        mut self,
+        predicate: impl Fn(&BxesTraceVariant) -> bool + 'static,
+    ) -> Self {
+        self.variant_predicates.push(Box::new(predicate));
+        self
+    }
+
+    pub fn with_min_traces_count(self, min_traces_count: u32) -> Self {
+        self.with_variant_predicate(move |variant| variant.traces_count >= min_traces_count)
+    }
+
+    pub fn with_event_predicate(
+        mut self,
+        predicate: impl Fn(&BxesEvent) -> bool + 'static,
+    ) -> Self {
+        self.event_predicates.push(Box::new(predicate));
+        self
+    }
+
+    /// Events whose names are not strings never match the predicate.
+    pub fn with_event_name_predicate(self, predicate: impl Fn(&str) -> bool + 'static) -> Self {
+        self.with_event_predicate(move |event| match event.name.as_ref() {
+            BxesValue::String(name) => predicate(name),
+            _ => false,
+        })
+    }
+
+    pub fn with_timestamp_range(self, range: impl RangeBounds<i64> + 'static) -> Self {
+        self.with_event_predicate(move |event| range.contains(&event.timestamp))
+    }
+
+    /// Keeps only event attributes with the given keys.
+    pub fn with_attribute_keys(mut self, keys: &[&str]) -> Self {
+        self.attribute_keys = Some(
+            keys.iter()
+                .map(|key| Arc::new(BxesValue::String((*key).into())))
+                .collect(),
+        );
+
+        self
+    }
+
+    /// Filters the log, variants which become equal after filtering are merged into one variant.
+    pub fn filter_log(&self, log: &BxesEventLog) -> BxesEventLog {
+        let mut builder = create_builder(log.version, log.metadata.clone());
+        for variant in &log.variants {
+            if let Some(variant) = self.filter_variant(variant) {
+                builder.add_variant(variant);
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Reads the lazy log variant by variant and filters it.
+    pub fn filter_lazy_log(&self, log: BxesLazyEventLog) -> Result<BxesEventLog, BxesReadError> {
+        let mut builder = create_builder(log.version, log.metadata.clone());
+        for variant in self.filter_variants(log.variants()) {
+            builder.add_variant(variant?);
+        }
+
+        Ok(builder.build())
+    }
+
+    /// Filters variants of a streaming reader, variants are not merged.
+    pub fn filter_variants<'a>(
+        &'a self,
+        variants: impl Iterator<Item = Result<BxesTraceVariant, BxesReadError>> + 'a,
+    ) -> impl Iterator<Item = Result<BxesTraceVariant, BxesReadError>> + 'a {
+        variants.filter_map(|variant| match variant {
+            Ok(variant) => self.filter_variant(&variant).map(Ok),
+            Err(err) => Some(Err(err)),
+        })
+    }
+
+    /// Returns `None` if the variant does not pass the filter or none of its events do.
+    pub fn filter_variant(&self, variant: &BxesTraceVariant) -> Option<BxesTraceVariant> {
+        if !self
+            .variant_predicates
+            .iter()
+            .all(|predicate| predicate(variant))
+        {
+            return None;
+        }
+
+        let events = variant
+            .events
+            .iter()
+            .filter(|event| {
+                self.event_predicates
+                    .iter()
+                    .all(|predicate| predicate(event))
+            })
+            .map(|event| self.project_event(event))
+            .collect::<Vec<_>>();
+
+        if events.is_empty() {
+            return None;
+        }
+
+        Some(BxesTraceVariant {
+            traces_count: variant.traces_count,
+            metadata: variant.metadata.clone(),
+            events,
+        })
+    }
+
+    fn project_event(&self, event: &BxesEvent) -> BxesEvent {
+        let keys = match self.attribute_keys.as_ref() {
+            None => return event.clone(),
+            Some(keys) => keys,
+        };
+
+        let attributes = event.attributes.as_ref().map(|attributes| {
+            attributes
+                .iter()
+                .filter(|(key, _)| keys.contains(key))
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+
+        BxesEvent {
+            attributes: attributes.filter(|attributes| !attributes.is_empty()),
+            ..event.clone()
+        }
+    }
+}
+
+fn create_builder(version: u32, metadata: BxesEventLogMetadata) -> BxesEventLogBuilder {
+    let mut builder = BxesEventLogBuilder::new();
+    builder.version = version;
+    builder.metadata = metadata;
+
+    builder
+}
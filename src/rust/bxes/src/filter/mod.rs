@@ -0,0 +1 @@
+pub mod bxes_log_filter;
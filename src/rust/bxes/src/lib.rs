@@ -4,9 +4,13 @@ extern crate num;
 extern crate num_derive;
 
 pub mod binary_rw;
+pub mod builder;
 pub mod constants;
+pub mod filter;
+pub mod merge;
 pub mod models;
 pub mod read;
 pub mod type_ids;
 pub mod utils;
 pub mod writer;
+pub mod xes;
@@ -1,5 +1,469 @@
-use bxes::read::single_file_bxes_reader::read_bxes;
+use std::{error::Error, fs, path::Path, process::ExitCode, sync::Arc};
 
-pub fn main() {
-    let log = read_bxes("/Users/aero/Programming/pmide/bxes/output/procfiler_logs/loh_allocations_75/loh_allocations_75.bxes").ok().unwrap();
+use bxes::{
+    merge::{
+        bxes_logs_merger::{merge_log_files_with_options, merge_logs_with_options},
+        merge_options::{BxesMergeOptions, BxesMetadataConflictPolicy},
+    },
+    models::{BxesEventLog, BxesEventLogMetadata, BxesTraceVariant, BxesValue},
+    read::{
+        bxes_validator::{validate_bxes, validate_bxes_multiple_files},
+        lazy_bxes_reader::{BxesLazyEventLog, BxesLazyLogItem},
+        multiple_files_bxes_reader::{read_bxes_multiple_files, read_bxes_multiple_files_lazy},
+        single_file_bxes_reader::{read_bxes, read_bxes_lazy},
+    },
+    writer::{
+        multiple_file_bxes_writer::write_bxes_multiple_files_with_options,
+        single_file_bxes_writer::write_bxes_with_options,
+        stream::{
+            bxes_stream_writer::{BxesStreamEvent, BxesStreamWriter},
+            single_file_bxes_stream_writer::SingleFileBxesStreamWriter,
+        },
+        write_options::BxesWriteOptions,
+    },
+    xes::{bxes_to_xes_converter::write_xes, xes_to_bxes_converter::read_xes},
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use tempfile::TempDir;
+
+#[derive(Parser)]
+#[command(name = "bxes", about = "Tools for working with bxes event logs")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert an event log between XES, single-file bxes and multiple-files bxes
+    Convert {
+        input: String,
+        output: String,
+        /// Format of the input log, detected by the path if not specified
+        #[arg(long)]
+        from: Option<LogFormat>,
+        /// Format of the output log, detected by the path if not specified
+        #[arg(long)]
+        to: Option<LogFormat>,
+        /// Version of bxes format of the output log, the version of the input log if not specified
+        #[arg(long)]
+        target_version: Option<u32>,
+    },
+    /// Print version, sizes of tables and metadata summary of a bxes log
+    Info { path: String },
+    /// Check the structure of a log and report all found issues
+    Validate {
+        path: String,
+        #[arg(long)]
+        format: Option<LogFormat>,
+    },
+    /// Print values, key-value pairs, metadata and traces of a bxes log
+    Dump { path: String },
+    /// Merge several logs with the same version into one log
+    Merge {
+        #[arg(short, long)]
+        output: String,
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Format of the output log, detected by the path if not specified
+        #[arg(long)]
+        to: Option<LogFormat>,
+        /// How to reconcile metadata entries with the same name but different contents
+        #[arg(long, value_enum, default_value_t = MetadataConflict::KeepAll)]
+        metadata_conflict: MetadataConflict,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogFormat {
+    Xes,
+    SingleFile,
+    MultipleFiles,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MetadataConflict {
+    KeepAll,
+    KeepFirst,
+    KeepLast,
+    Fail,
+}
+
+impl From<MetadataConflict> for BxesMetadataConflictPolicy {
+    fn from(value: MetadataConflict) -> Self {
+        match value {
+            MetadataConflict::KeepAll => BxesMetadataConflictPolicy::KeepAll,
+            MetadataConflict::KeepFirst => BxesMetadataConflictPolicy::KeepFirst,
+            MetadataConflict::KeepLast => BxesMetadataConflictPolicy::KeepLast,
+            MetadataConflict::Fail => BxesMetadataConflictPolicy::Fail,
+        }
+    }
+}
+
+impl LogFormat {
+    /// `.xes` files are XES logs, `.bxes` files are single-file bxes logs,
+    /// all other paths are treated as directories of multiple-files bxes logs.
+    fn detect(path: &str) -> Self {
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("xes") => LogFormat::Xes,
+            Some(bxes::constants::BXES_EXT) => LogFormat::SingleFile,
+            _ => LogFormat::MultipleFiles,
+        }
+    }
+}
+
+pub fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Convert {
+            input,
+            output,
+            from,
+            to,
+            target_version,
+        } => convert(&input, from, &output, to, target_version),
+        Command::Info { path } => info(&path),
+        Command::Validate { path, format } => validate(&path, format),
+        Command::Dump { path } => dump(&path),
+        Command::Merge {
+            output,
+            inputs,
+            to,
+            metadata_conflict,
+        } => merge(&inputs, &output, to, metadata_conflict),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn convert(
+    input: &str,
+    from: Option<LogFormat>,
+    output: &str,
+    to: Option<LogFormat>,
+    target_version: Option<u32>,
+) -> Result<(), String> {
+    let to = to.unwrap_or(LogFormat::detect(output));
+    if to == LogFormat::Xes && target_version.is_some() {
+        return Err("--target-version can not be used with XES output".to_string());
+    }
+
+    let log = read_log(input, from.unwrap_or(LogFormat::detect(input)))?;
+    let options = BxesWriteOptions {
+        version: target_version,
+        ..Default::default()
+    };
+
+    write_log_with_options(&log, output, to, &options)
+}
+
+fn info(path: &str) -> Result<(), String> {
+    let log = read_lazy_log(path)?;
+
+    println!("version: {}", log.version);
+    println!("values: {}", log.values().len());
+    println!("key-value pairs: {}", log.kv_pairs().len());
+    print_metadata_summary(&log.metadata);
+
+    let variants_count = log.variants_count();
+    let mut traces_count = 0u64;
+    let mut events_count = 0u64;
+
+    for variant in log.variants() {
+        let variant = variant.map_err(error_message)?;
+        traces_count += variant.traces_count as u64;
+        events_count += variant.traces_count as u64 * variant.events.len() as u64;
+    }
+
+    println!("variants: {}", variants_count);
+    println!("traces: {}", traces_count);
+    println!("events: {}", events_count);
+
+    Ok(())
+}
+
+fn print_metadata_summary(metadata: &BxesEventLogMetadata) {
+    println!(
+        "properties: {}",
+        metadata.properties.as_ref().map_or(0, Vec::len)
+    );
+
+    let extensions = metadata.extensions.as_ref().map_or(vec![], |extensions| {
+        extensions
+            .iter()
+            .map(|extension| format_value(&extension.name))
+            .collect()
+    });
+
+    println!("extensions: [{}]", extensions.join(", "));
+
+    let classifiers = metadata.classifiers.as_ref().map_or(vec![], |classifiers| {
+        classifiers
+            .iter()
+            .map(|classifier| format_value(&classifier.name))
+            .collect()
+    });
+
+    println!("classifiers: [{}]", classifiers.join(", "));
+    println!("globals: {}", metadata.globals.as_ref().map_or(0, Vec::len));
+}
+
+fn validate(path: &str, format: Option<LogFormat>) -> Result<(), String> {
+    let report = match format.unwrap_or(LogFormat::detect(path)) {
+        LogFormat::Xes => {
+            let log = read_log(path, LogFormat::Xes)?;
+            println!(
+                "{} is valid: {} variants, {} traces",
+                path,
+                log.variants.len(),
+                log.variants
+                    .iter()
+                    .map(|variant| variant.traces_count as u64)
+                    .sum::<u64>()
+            );
+
+            return Ok(());
+        }
+        LogFormat::SingleFile => validate_bxes(path),
+        LogFormat::MultipleFiles => validate_bxes_multiple_files(path),
+    };
+
+    let report = report.map_err(error_message)?;
+    for issue in &report.issues {
+        println!("{}", issue);
+    }
+
+    if !report.is_valid() {
+        return Err(format!("{} has {} issues", path, report.issues.len()));
+    }
+
+    println!(
+        "{} is valid: {} variants, {} traces",
+        path, report.variants_count, report.traces_count
+    );
+
+    Ok(())
+}
+
+fn dump(path: &str) -> Result<(), String> {
+    let log = read_lazy_log(path)?;
+
+    println!("version: {}", log.version);
+
+    println!("values:");
+    for (index, value) in log.values().iter().enumerate() {
+        println!("  [{}] {}", index, format_value(value));
+    }
+
+    println!("key-value pairs:");
+    for (index, (key, value)) in log.kv_pairs().iter().enumerate() {
+        println!("  [{}] ({}, {})", index, key, value);
+    }
+
+    println!("metadata:");
+    if let Some(properties) = log.metadata.properties.as_ref() {
+        for (key, value) in properties {
+            println!("  property {} = {}", format_value(key), format_value(value));
+        }
+    }
+
+    if let Some(extensions) = log.metadata.extensions.as_ref() {
+        for extension in extensions {
+            println!(
+                "  extension {} {} {}",
+                format_value(&extension.name),
+                format_value(&extension.prefix),
+                format_value(&extension.uri)
+            );
+        }
+    }
+
+    if let Some(globals) = log.metadata.globals.as_ref() {
+        for global in globals {
+            println!("  global {:?}", global.entity_kind);
+            for (key, value) in &global.globals {
+                println!("    {} = {}", format_value(key), format_value(value));
+            }
+        }
+    }
+
+    if let Some(classifiers) = log.metadata.classifiers.as_ref() {
+        for classifier in classifiers {
+            let keys: Vec<String> = classifier.keys.iter().map(format_value).collect();
+            println!(
+                "  classifier {} [{}]",
+                format_value(&classifier.name),
+                keys.join(", ")
+            );
+        }
+    }
+
+    println!("variants:");
+    for (index, variant) in log.variants().enumerate() {
+        let variant = variant.map_err(error_message)?;
+        print_variant(index, &variant);
+    }
+
+    Ok(())
+}
+
+fn print_variant(index: usize, variant: &BxesTraceVariant) {
+    println!("  [{}] traces: {}", index, variant.traces_count);
+    for (key, value) in &variant.metadata {
+        println!("    {} = {}", format_value(key), format_value(value));
+    }
+
+    for event in &variant.events {
+        println!(
+            "    {} {} {:?}",
+            format_value(&event.name),
+            event.timestamp,
+            event.lifecycle
+        );
+        if let Some(attributes) = event.attributes.as_ref() {
+            for (key, value) in attributes {
+                println!("      {} = {}", format_value(key), format_value(value));
+            }
+        }
+    }
+}
+
+fn format_value(value: &Arc<BxesValue>) -> String {
+    match value.as_ref() {
+        BxesValue::String(string) => format!("{:?}", string),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Logs are merged without reading their trace variants into memory when all of them are bxes logs
+/// and the output is a bxes log, otherwise all logs are read into memory.
+fn merge(
+    inputs: &[String],
+    output: &str,
+    to: Option<LogFormat>,
+    metadata_conflict: MetadataConflict,
+) -> Result<(), String> {
+    let options = BxesMergeOptions {
+        metadata_conflict_policy: metadata_conflict.into(),
+        ..Default::default()
+    };
+
+    let to = to.unwrap_or(LogFormat::detect(output));
+    let all_bxes = inputs
+        .iter()
+        .all(|input| LogFormat::detect(input) != LogFormat::Xes);
+
+    if !all_bxes || to == LogFormat::Xes {
+        let logs = inputs
+            .iter()
+            .map(|input| read_log(input, LogFormat::detect(input)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let merged_log = merge_logs_with_options(&logs, &options).map_err(error_message)?;
+        return write_log(&merged_log, output, to);
+    }
+
+    let inputs = inputs.iter().map(String::as_str).collect::<Vec<_>>();
+    if to == LogFormat::MultipleFiles {
+        fs::create_dir_all(output).map_err(|err| err.to_string())?;
+        return merge_log_files_with_options(&inputs, output, &options).map_err(error_message);
+    }
+
+    let temp_dir = TempDir::new().map_err(|err| err.to_string())?;
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    merge_log_files_with_options(&inputs, temp_dir_path, &options).map_err(error_message)?;
+
+    let merged_log = read_bxes_multiple_files_lazy(temp_dir_path).map_err(error_message)?;
+    write_lazy_log(merged_log, output)
+}
+
+/// Writes the log to a single file without reading its trace variants into memory.
+fn write_lazy_log(log: BxesLazyEventLog, path: &str) -> Result<(), String> {
+    let mut writer = SingleFileBxesStreamWriter::new(path, log.version).map_err(error_message)?;
+    let metadata = log.metadata.clone();
+
+    let properties = metadata.properties.into_iter().flatten();
+    let extensions = metadata.extensions.into_iter().flatten();
+    let globals = metadata.globals.into_iter().flatten();
+    let classifiers = metadata.classifiers.into_iter().flatten();
+
+    let metadata_events = properties
+        .map(BxesStreamEvent::LogMetadataProperty)
+        .chain(extensions.map(BxesStreamEvent::LogMetadataExtension))
+        .chain(globals.map(BxesStreamEvent::LogMetadataGlobal))
+        .chain(classifiers.map(BxesStreamEvent::LogMetadataClassifier));
+
+    for event in metadata_events {
+        writer.handle_event(event).map_err(error_message)?;
+    }
+
+    for item in log.items() {
+        let event = match item.map_err(error_message)? {
+            BxesLazyLogItem::TraceVariantStart(variant_start) => {
+                BxesStreamEvent::TraceVariantStart(variant_start)
+            }
+            BxesLazyLogItem::Event(event) => BxesStreamEvent::Event(event),
+        };
+
+        writer.handle_event(event).map_err(error_message)?;
+    }
+
+    writer.finish().map_err(error_message)
+}
+
+fn read_lazy_log(path: &str) -> Result<BxesLazyEventLog, String> {
+    let log = match LogFormat::detect(path) {
+        LogFormat::Xes => return Err(format!("{} is not a bxes log", path)),
+        LogFormat::SingleFile => read_bxes_lazy(path),
+        LogFormat::MultipleFiles => read_bxes_multiple_files_lazy(path),
+    };
+
+    log.map_err(error_message)
+}
+
+fn read_log(path: &str, format: LogFormat) -> Result<BxesEventLog, String> {
+    match format {
+        LogFormat::Xes => read_xes(path).map_err(error_message),
+        LogFormat::SingleFile => read_bxes(path).map_err(error_message),
+        LogFormat::MultipleFiles => read_bxes_multiple_files(path).map_err(error_message),
+    }
+}
+
+fn write_log(log: &BxesEventLog, path: &str, format: LogFormat) -> Result<(), String> {
+    write_log_with_options(log, path, format, &BxesWriteOptions::default())
+}
+
+fn write_log_with_options(
+    log: &BxesEventLog,
+    path: &str,
+    format: LogFormat,
+    options: &BxesWriteOptions,
+) -> Result<(), String> {
+    match format {
+        LogFormat::Xes => write_xes(log, path).map_err(error_message),
+        LogFormat::SingleFile => write_bxes_with_options(path, log, options).map_err(error_message),
+        LogFormat::MultipleFiles => {
+            fs::create_dir_all(path).map_err(|err| err.to_string())?;
+            write_bxes_multiple_files_with_options(log, path, options).map_err(error_message)
+        }
+    }
+}
+
+/// Formats the error together with all its sources, as messages of errors do not include their sources.
+fn error_message(err: impl Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        message.push_str(&format!(": {}", err));
+        source = err.source();
+    }
+
+    message
 }
@@ -0,0 +1,353 @@
+use std::{collections::HashSet, path::Path, sync::Arc};
+
+use crate::{
+    builder::bxes_event_log_builder::BxesEventLogBuilder,
+    models::{
+        BxesClassifier, BxesEvent, BxesEventLog, BxesEventLogMetadata, BxesExtension, BxesGlobal,
+        BxesTraceVariant, BxesValue,
+    },
+    read::{
+        lazy_bxes_reader::BxesLazyEventLog,
+        multiple_files_bxes_reader::read_bxes_multiple_files_lazy,
+        single_file_bxes_reader::read_bxes_lazy,
+    },
+    writer::{
+        multiple_file_bxes_appender::MultipleFilesBxesAppender,
+        multiple_file_bxes_writer::write_bxes_multiple_files_with_options,
+    },
+};
+
+use super::{
+    errors::BxesMergeError,
+    merge_options::{BxesMergeOptions, BxesMetadataConflictPolicy},
+};
+
+type Attributes = Vec<(Arc<BxesValue>, Arc<BxesValue>)>;
+
+pub fn merge_logs(logs: &[BxesEventLog]) -> Result<BxesEventLog, BxesMergeError> {
+    merge_logs_with_options(logs, &BxesMergeOptions::default())
+}
+
+/// Merges logs of the same version into one log: equal values are shared between merged entities,
+/// identical trace variants are merged by summing their traces counts.
+pub fn merge_logs_with_options(
+    logs: &[BxesEventLog],
+    options: &BxesMergeOptions,
+) -> Result<BxesEventLog, BxesMergeError> {
+    let mut version = None;
+    let mut metadata = create_empty_metadata();
+    let mut interner = ValuesInterner::new();
+    let mut builder = BxesEventLogBuilder::new();
+
+    for log in logs {
+        try_check_version(&mut version, log.version)?;
+        try_merge_metadata(&mut metadata, &log.metadata, options)?;
+
+        for variant in &log.variants {
+            builder.add_variant(interner.intern_variant(variant));
+        }
+    }
+
+    builder.version = match version {
+        Some(version) => version,
+        None => return Err(BxesMergeError::NoLogsToMerge),
+    };
+
+    builder.metadata = interner.intern_metadata(&metadata);
+
+    Ok(builder.build())
+}
+
+/// Merges single-file or multiple-files logs into a multiple-files log in the given directory,
+/// trace variants are streamed from the input logs without reading them into memory.
+///
+/// Memory still grows with the merged log: values and key-value pairs of the merged log
+/// are kept in memory, along with a hash, an offset and a traces count for each distinct variant.
+pub fn merge_log_files(paths: &[&str], output_directory_path: &str) -> Result<(), BxesMergeError> {
+    merge_log_files_with_options(paths, output_directory_path, &BxesMergeOptions::default())
+}
+
+pub fn merge_log_files_with_options(
+    paths: &[&str],
+    output_directory_path: &str,
+    options: &BxesMergeOptions,
+) -> Result<(), BxesMergeError> {
+    let mut version = None;
+    let mut metadata = create_empty_metadata();
+
+    for path in paths {
+        let log = try_read_lazy_log(path)?;
+        try_check_version(&mut version, log.version)?;
+        try_merge_metadata(&mut metadata, &log.metadata, options)?;
+    }
+
+    let version = match version {
+        Some(version) => version,
+        None => return Err(BxesMergeError::NoLogsToMerge),
+    };
+
+    let log = BxesEventLog {
+        version,
+        metadata,
+        variants: vec![],
+    };
+
+    let write_options = &options.write_options;
+    if let Err(err) =
+        write_bxes_multiple_files_with_options(&log, output_directory_path, write_options)
+    {
+        return Err(BxesMergeError::BxesWrite(err));
+    }
+
+    let mut appender = match MultipleFilesBxesAppender::open(output_directory_path) {
+        Ok(appender) => appender,
+        Err(err) => return Err(BxesMergeError::BxesWrite(err)),
+    };
+
+    for path in paths {
+        for variant in try_read_lazy_log(path)?.variants() {
+            let variant = match variant {
+                Ok(variant) => variant,
+                Err(err) => return Err(BxesMergeError::BxesRead(err)),
+            };
+
+            if let Err(err) = appender.append_variant(&variant) {
+                return Err(BxesMergeError::BxesWrite(err));
+            }
+        }
+    }
+
+    match appender.finish() {
+        Ok(()) => Ok(()),
+        Err(err) => Err(BxesMergeError::BxesWrite(err)),
+    }
+}
+
+fn try_read_lazy_log(path: &str) -> Result<BxesLazyEventLog, BxesMergeError> {
+    let log = match Path::new(path).is_dir() {
+        true => read_bxes_multiple_files_lazy(path),
+        false => read_bxes_lazy(path),
+    };
+
+    match log {
+        Ok(log) => Ok(log),
+        Err(err) => Err(BxesMergeError::BxesRead(err)),
+    }
+}
+
+fn try_check_version(version: &mut Option<u32>, log_version: u32) -> Result<(), BxesMergeError> {
+    match *version {
+        None => {
+            *version = Some(log_version);
+            Ok(())
+        }
+        Some(version) if version == log_version => Ok(()),
+        Some(version) => Err(BxesMergeError::VersionsMismatch(version, log_version)),
+    }
+}
+
+fn create_empty_metadata() -> BxesEventLogMetadata {
+    BxesEventLogMetadata {
+        extensions: None,
+        classifiers: None,
+        properties: None,
+        globals: None,
+    }
+}
+
+/// Makes equal values of the merged log share the same allocation.
+struct ValuesInterner {
+    values: HashSet<Arc<BxesValue>>,
+}
+
+impl ValuesInterner {
+    fn new() -> Self {
+        Self {
+            values: HashSet::new(),
+        }
+    }
+
+    fn intern(&mut self, value: &Arc<BxesValue>) -> Arc<BxesValue> {
+        if let Some(existing_value) = self.values.get(value) {
+            return existing_value.clone();
+        }
+
+        self.values.insert(value.clone());
+        value.clone()
+    }
+
+    fn intern_attributes(&mut self, attributes: &Attributes) -> Attributes {
+        attributes
+            .iter()
+            .map(|(key, value)| (self.intern(key), self.intern(value)))
+            .collect()
+    }
+
+    fn intern_variant(&mut self, variant: &BxesTraceVariant) -> BxesTraceVariant {
+        BxesTraceVariant {
+            traces_count: variant.traces_count,
+            metadata: self.intern_attributes(&variant.metadata),
+            events: variant
+                .events
+                .iter()
+                .map(|event| self.intern_event(event))
+                .collect(),
+        }
+    }
+
+    fn intern_event(&mut self, event: &BxesEvent) -> BxesEvent {
+        BxesEvent {
+            name: self.intern(&event.name),
+            timestamp: event.timestamp,
+            lifecycle: event.lifecycle.clone(),
+            attributes: event
+                .attributes
+                .as_ref()
+                .map(|attributes| self.intern_attributes(attributes)),
+        }
+    }
+
+    fn intern_metadata(&mut self, metadata: &BxesEventLogMetadata) -> BxesEventLogMetadata {
+        BxesEventLogMetadata {
+            extensions: metadata.extensions.as_ref().map(|extensions| {
+                extensions
+                    .iter()
+                    .map(|extension| BxesExtension {
+                        name: self.intern(&extension.name),
+                        prefix: self.intern(&extension.prefix),
+                        uri: self.intern(&extension.uri),
+                    })
+                    .collect()
+            }),
+            classifiers: metadata.classifiers.as_ref().map(|classifiers| {
+                classifiers
+                    .iter()
+                    .map(|classifier| BxesClassifier {
+                        name: self.intern(&classifier.name),
+                        keys: classifier.keys.iter().map(|key| self.intern(key)).collect(),
+                    })
+                    .collect()
+            }),
+            properties: metadata
+                .properties
+                .as_ref()
+                .map(|properties| self.intern_attributes(properties)),
+            globals: metadata.globals.as_ref().map(|globals| {
+                globals
+                    .iter()
+                    .map(|global| BxesGlobal {
+                        entity_kind: global.entity_kind.clone(),
+                        globals: self.intern_attributes(&global.globals),
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+fn try_merge_metadata(
+    metadata: &mut BxesEventLogMetadata,
+    other: &BxesEventLogMetadata,
+    options: &BxesMergeOptions,
+) -> Result<(), BxesMergeError> {
+    let policy = options.metadata_conflict_policy;
+
+    try_merge_entries(
+        &mut metadata.properties,
+        other.properties.as_ref(),
+        policy,
+        |first, second| first.0 == second.0,
+        |(key, _)| format!("property {:?}", key),
+    )?;
+
+    try_merge_entries(
+        &mut metadata.extensions,
+        other.extensions.as_ref(),
+        policy,
+        |first, second| first.name == second.name,
+        |extension| format!("extension {:?}", extension.name),
+    )?;
+
+    try_merge_entries(
+        &mut metadata.classifiers,
+        other.classifiers.as_ref(),
+        policy,
+        |first, second| first.name == second.name,
+        |classifier| format!("classifier {:?}", classifier.name),
+    )?;
+
+    try_merge_globals(&mut metadata.globals, other.globals.as_ref(), policy)
+}
+
+fn try_merge_globals(
+    globals: &mut Option<Vec<BxesGlobal>>,
+    other_globals: Option<&Vec<BxesGlobal>>,
+    policy: BxesMetadataConflictPolicy,
+) -> Result<(), BxesMergeError> {
+    if policy == BxesMetadataConflictPolicy::KeepAll {
+        return try_merge_entries(
+            globals,
+            other_globals,
+            policy,
+            |first, second| first.entity_kind == second.entity_kind,
+            |global| format!("globals of {:?}", global.entity_kind),
+        );
+    }
+
+    for other_global in other_globals.into_iter().flatten() {
+        let globals = globals.get_or_insert_with(Vec::new);
+        let global = globals
+            .iter_mut()
+            .find(|global| global.entity_kind == other_global.entity_kind);
+
+        match global {
+            None => globals.push(other_global.clone()),
+            Some(global) => {
+                let mut entries = Some(std::mem::take(&mut global.globals));
+                try_merge_entries(
+                    &mut entries,
+                    Some(&other_global.globals),
+                    policy,
+                    |first, second| first.0 == second.0,
+                    |(key, _)| format!("global {:?} of {:?}", key, other_global.entity_kind),
+                )?;
+
+                global.globals = entries.unwrap_or_default();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn try_merge_entries<T: PartialEq + Clone>(
+    entries: &mut Option<Vec<T>>,
+    other_entries: Option<&Vec<T>>,
+    policy: BxesMetadataConflictPolicy,
+    same_identity: impl Fn(&T, &T) -> bool,
+    describe: impl Fn(&T) -> String,
+) -> Result<(), BxesMergeError> {
+    for other_entry in other_entries.into_iter().flatten() {
+        let entries = entries.get_or_insert_with(Vec::new);
+        if entries.contains(other_entry) {
+            continue;
+        }
+
+        match entries
+            .iter()
+            .position(|entry| same_identity(entry, other_entry))
+        {
+            None => entries.push(other_entry.clone()),
+            Some(index) => match policy {
+                BxesMetadataConflictPolicy::KeepAll => entries.push(other_entry.clone()),
+                BxesMetadataConflictPolicy::KeepFirst => {}
+                BxesMetadataConflictPolicy::KeepLast => entries[index] = other_entry.clone(),
+                BxesMetadataConflictPolicy::Fail => {
+                    return Err(BxesMergeError::MetadataConflict(describe(other_entry)))
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+use crate::{read::errors::BxesReadError, writer::errors::BxesWriteError};
+
+#[derive(Debug, Error)]
+pub enum BxesMergeError {
+    #[error("No logs to merge")]
+    NoLogsToMerge,
+    #[error("Can not merge logs with different versions: {0} and {1}")]
+    VersionsMismatch(u32, u32),
+    #[error("Conflicting log metadata: {0}")]
+    MetadataConflict(String),
+    #[error("Failed to read merged log")]
+    BxesRead(#[from] BxesReadError),
+    #[error("Failed to write merged log")]
+    BxesWrite(#[from] BxesWriteError),
+}
@@ -0,0 +1,30 @@
+use crate::writer::write_options::BxesWriteOptions;
+
+/// Defines how log metadata entries which have the same identity (the key of a property,
+/// the name of an extension or classifier, the kind and key of a global) but different
+/// contents are reconciled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BxesMetadataConflictPolicy {
+    /// All distinct entries are kept.
+    KeepAll,
+    KeepFirst,
+    KeepLast,
+    /// Merging fails with `BxesMergeError::MetadataConflict`.
+    Fail,
+}
+
+#[derive(Clone, Debug)]
+pub struct BxesMergeOptions {
+    pub metadata_conflict_policy: BxesMetadataConflictPolicy,
+    /// Options with which the merged log is written by `merge_log_files_with_options`.
+    pub write_options: BxesWriteOptions,
+}
+
+impl Default for BxesMergeOptions {
+    fn default() -> Self {
+        Self {
+            metadata_conflict_policy: BxesMetadataConflictPolicy::KeepAll,
+            write_options: BxesWriteOptions::default(),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod bxes_logs_merger;
+pub mod errors;
+pub mod merge_options;
@@ -1,7 +1,7 @@
 use num_derive::FromPrimitive;
 use num_traits::ToBytes;
 use std::hash::Hash;
-use std::rc::Rc;
+use std::sync::Arc;
 use variant_count::VariantCount;
 
 #[derive(Clone, Debug)]
@@ -12,7 +12,7 @@ pub enum BxesValue {
     Uint64(u64),
     Float32(f32),
     Float64(f64),
-    String(Rc<Box<String>>),
+    String(Arc<str>),
     Bool(bool),
     Timestamp(i64),
     BrafLifecycle(BrafLifecycle),
@@ -65,9 +65,9 @@ impl PartialEq for BxesArtifact {
 
 #[derive(Clone, Debug)]
 pub struct BxesArtifactItem {
-    pub model: Rc<Box<BxesValue>>,
-    pub instance: Rc<Box<BxesValue>>,
-    pub transition: Rc<Box<BxesValue>>,
+    pub model: Arc<BxesValue>,
+    pub instance: Arc<BxesValue>,
+    pub transition: Arc<BxesValue>,
 }
 
 impl Hash for BxesArtifactItem {
@@ -115,8 +115,8 @@ impl PartialEq for BxesDrivers {
 #[derive(Clone, Debug)]
 pub struct BxesDriver {
     pub amount: BxesValue,
-    pub name: Rc<Box<BxesValue>>,
-    pub driver_type: Rc<Box<BxesValue>>,
+    pub name: Arc<BxesValue>,
+    pub driver_type: Arc<BxesValue>,
 }
 
 impl BxesDriver {
@@ -192,12 +192,18 @@ impl PartialEq for BxesValue {
 
 impl Eq for BxesValue {}
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Lifecycle {
     Braf(BrafLifecycle),
     Standard(StandardLifecycle),
 }
 
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Lifecycle::Standard(StandardLifecycle::Unspecified)
+    }
+}
+
 #[derive(FromPrimitive, ToPrimitive, Clone, Debug, PartialEq, Eq, Hash, VariantCount)]
 pub enum BrafLifecycle {
     Unspecified = 0,
@@ -240,59 +246,100 @@ pub enum StandardLifecycle {
     Withdraw = 13,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct BxesEventLog {
     pub version: u32,
     pub metadata: BxesEventLogMetadata,
     pub variants: Vec<BxesTraceVariant>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BxesEventLogMetadata {
     pub extensions: Option<Vec<BxesExtension>>,
     pub classifiers: Option<Vec<BxesClassifier>>,
-    pub properties: Option<Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>>,
+    pub properties: Option<Vec<(Arc<BxesValue>, Arc<BxesValue>)>>,
     pub globals: Option<Vec<BxesGlobal>>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BxesExtension {
-    pub name: Rc<Box<BxesValue>>,
-    pub prefix: Rc<Box<BxesValue>>,
-    pub uri: Rc<Box<BxesValue>>,
+    pub name: Arc<BxesValue>,
+    pub prefix: Arc<BxesValue>,
+    pub uri: Arc<BxesValue>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BxesClassifier {
-    pub name: Rc<Box<BxesValue>>,
-    pub keys: Vec<Rc<Box<BxesValue>>>,
+    pub name: Arc<BxesValue>,
+    pub keys: Vec<Arc<BxesValue>>,
 }
 
-#[derive(Debug, FromPrimitive, ToPrimitive, VariantCount, PartialEq, Eq)]
+#[derive(Clone, Debug, FromPrimitive, ToPrimitive, VariantCount, PartialEq, Eq)]
 pub enum BxesGlobalKind {
     Event = 0,
     Trace = 1,
     Log = 2,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The encoding of string values of the values table, written since `FRONT_CODED_STRINGS_VERSION`.
+#[derive(Clone, Copy, Debug, Default, FromPrimitive, ToPrimitive, PartialEq, Eq)]
+pub enum BxesStringEncoding {
+    #[default]
+    Plain = 0,
+    /// Each string is written as the length of the prefix shared with the previous string value
+    /// and the rest of the string.
+    FrontCoded = 1,
+}
+
+/// Checksums which are written after each section of a log since `CHECKSUMS_VERSION`.
+#[derive(Clone, Copy, Debug, Default, FromPrimitive, ToPrimitive, PartialEq, Eq)]
+pub enum BxesChecksum {
+    #[default]
+    None = 0,
+    Crc32c = 1,
+}
+
+impl BxesChecksum {
+    /// The number of bytes which each checksum takes.
+    pub fn size(&self) -> usize {
+        match self {
+            BxesChecksum::None => 0,
+            BxesChecksum::Crc32c => std::mem::size_of::<u32>(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BxesGlobal {
     pub entity_kind: BxesGlobalKind,
-    pub globals: Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>,
+    pub globals: Vec<(Arc<BxesValue>, Arc<BxesValue>)>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct BxesTraceVariant {
     pub traces_count: u32,
-    pub metadata: Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>,
+    pub metadata: Vec<(Arc<BxesValue>, Arc<BxesValue>)>,
     pub events: Vec<BxesEvent>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+pub struct BxesTraceVariantStart {
+    pub traces_count: u32,
+    pub metadata: Vec<(Arc<BxesValue>, Arc<BxesValue>)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BxesVariantIndexEntry {
+    pub offset: u64,
+    pub events_count: u32,
+}
+
+#[derive(Clone, Debug)]
 pub struct BxesEvent {
-    pub name: Rc<Box<BxesValue>>,
+    pub name: Arc<BxesValue>,
     pub timestamp: i64,
-    pub attributes: Option<Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>>,
+    pub lifecycle: Lifecycle,
+    pub attributes: Option<Vec<(Arc<BxesValue>, Arc<BxesValue>)>>,
 }
 
 impl PartialEq for BxesEvent {
@@ -305,15 +352,34 @@ impl PartialEq for BxesEvent {
     }
 }
 
+impl Eq for BxesEvent {}
+
+impl Hash for BxesEvent {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.timestamp.hash(state);
+        self.lifecycle.hash(state);
+
+        if let Some(attributes) = self.attributes.as_ref() {
+            for (key, value) in attributes {
+                key.hash(state);
+                value.hash(state);
+            }
+        }
+    }
+}
+
 impl BxesEvent {
     fn compare_events_by_properties(&self, other: &Self) -> bool {
-        self.name == other.name && self.timestamp == other.timestamp
+        self.name == other.name
+            && self.timestamp == other.timestamp
+            && self.lifecycle == other.lifecycle
     }
 }
 
 fn compare_list_of_attributes(
-    first_attributes: &Option<Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>>,
-    second_attributes: &Option<Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>>,
+    first_attributes: &Option<Vec<(Arc<BxesValue>, Arc<BxesValue>)>>,
+    second_attributes: &Option<Vec<(Arc<BxesValue>, Arc<BxesValue>)>>,
 ) -> bool {
     if first_attributes.is_none() && second_attributes.is_none() {
         return true;
@@ -339,8 +405,8 @@ fn compare_list_of_attributes(
 }
 
 fn attributes_equals(
-    first_attribute: &(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>),
-    second_attribute: &(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>),
+    first_attribute: &(Arc<BxesValue>, Arc<BxesValue>),
+    second_attribute: &(Arc<BxesValue>, Arc<BxesValue>),
 ) -> bool {
     first_attribute.0.eq(&second_attribute.0) && first_attribute.1.eq(&second_attribute.1)
 }
@@ -0,0 +1,903 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Display, fs, io::Read, path::Path};
+
+use num_traits::FromPrimitive;
+
+use crate::{
+    binary_rw::core::SeekStream,
+    constants::*,
+    models::{
+        BrafLifecycle, BxesChecksum, BxesGlobalKind, BxesStringEncoding, SoftwareEventType,
+        StandardLifecycle,
+    },
+    type_ids::TypeIds,
+};
+
+use super::{
+    errors::{BxesReadError, BxesSection, FailedToReadValueError},
+    read_utils::try_open_raw_log_stream,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BxesValidationIssueKind {
+    UnsupportedVersion(u32),
+    VersionsMismatch { expected: u32, actual: u32 },
+    UnsupportedStringEncoding(u8),
+    StringEncodingsMismatch { expected: BxesStringEncoding, actual: BxesStringEncoding },
+    UnsupportedChecksum(u8),
+    ChecksumKindsMismatch { expected: BxesChecksum, actual: BxesChecksum },
+    ChecksumMismatch,
+    Truncated,
+    InvalidLeb128,
+    InvalidTypeId(u8),
+    InvalidEnumValue(u8),
+    InvalidUtf8,
+    InvalidSharedPrefixLength(u64),
+    DanglingValueIndex(u32),
+    DanglingKeyValueIndex(u32),
+    NonStringAttributeKey(u32),
+    DuplicatedValue { index: u32, first_index: u32 },
+    UnusedValue(u32),
+    InvalidVariantsIndex,
+    TrailingBytes(usize),
+}
+
+/// An issue found by the validator, the offset is relative to the start of the raw log
+/// for single-file logs and to the start of the section file for multiple-files logs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BxesValidationIssue {
+    pub section: BxesSection,
+    pub offset: usize,
+    pub kind: BxesValidationIssueKind,
+}
+
+impl Display for BxesValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match &self.kind {
+            BxesValidationIssueKind::UnsupportedVersion(version) => format!(
+                "unsupported version {}, supported versions: {:?}",
+                version, SUPPORTED_VERSIONS
+            ),
+            BxesValidationIssueKind::VersionsMismatch { expected, actual } => {
+                format!(
+                    "version {} differs from version {} of the log",
+                    actual, expected
+                )
+            }
+            BxesValidationIssueKind::UnsupportedStringEncoding(string_encoding) => {
+                format!("unsupported string encoding {}", string_encoding)
+            }
+            BxesValidationIssueKind::StringEncodingsMismatch { expected, actual } => {
+                format!(
+                    "{:?} strings differ from {:?} strings of the log",
+                    actual, expected
+                )
+            }
+            BxesValidationIssueKind::UnsupportedChecksum(checksum) => {
+                format!("unsupported checksum kind {}", checksum)
+            }
+            BxesValidationIssueKind::ChecksumKindsMismatch { expected, actual } => {
+                format!(
+                    "{:?} checksums differ from {:?} checksums of the log",
+                    actual, expected
+                )
+            }
+            BxesValidationIssueKind::ChecksumMismatch => {
+                "checksum does not match the contents".to_string()
+            }
+            BxesValidationIssueKind::Truncated => "unexpected end of data".to_string(),
+            BxesValidationIssueKind::InvalidLeb128 => "invalid LEB128 number".to_string(),
+            BxesValidationIssueKind::InvalidTypeId(type_id) => {
+                format!("invalid type id {}", type_id)
+            }
+            BxesValidationIssueKind::InvalidEnumValue(value) => {
+                format!("enum value {} is out of range", value)
+            }
+            BxesValidationIssueKind::InvalidUtf8 => "string is not valid UTF-8".to_string(),
+            BxesValidationIssueKind::InvalidSharedPrefixLength(length) => format!(
+                "shared prefix length {} exceeds the length of the previous string",
+                length
+            ),
+            BxesValidationIssueKind::DanglingValueIndex(index) => {
+                format!("value index {} is out of range", index)
+            }
+            BxesValidationIssueKind::DanglingKeyValueIndex(index) => {
+                format!("key-value pair index {} is out of range", index)
+            }
+            BxesValidationIssueKind::NonStringAttributeKey(index) => {
+                format!("key of key-value pair {} is not a string", index)
+            }
+            BxesValidationIssueKind::DuplicatedValue { index, first_index } => {
+                format!("value {} duplicates value {}", index, first_index)
+            }
+            BxesValidationIssueKind::UnusedValue(index) => {
+                format!("value {} is not referenced", index)
+            }
+            BxesValidationIssueKind::InvalidVariantsIndex => {
+                "variants index does not match trace variants".to_string()
+            }
+            BxesValidationIssueKind::TrailingBytes(count) => {
+                format!("{} unexpected bytes after the end of data", count)
+            }
+        };
+
+        write!(
+            f,
+            "{:?} at offset {}: {}",
+            self.section, self.offset, description
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BxesValidationReport {
+    pub issues: Vec<BxesValidationIssue>,
+    pub variants_count: u32,
+    pub traces_count: u64,
+}
+
+impl BxesValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Validates a single-file log of any container without building the model of the log.
+pub fn validate_bxes(path: &str) -> Result<BxesValidationReport, BxesReadError> {
+    let mut stream = try_open_raw_log_stream(path, false)?;
+    let length = match stream.len() {
+        Ok(length) => length,
+        Err(err) => return Err(BxesReadError::FailedToReadPos(err)),
+    };
+
+    let mut raw_log = vec![0; length];
+    if let Err(err) = stream.read_exact(&mut raw_log) {
+        return Err(BxesReadError::FailedToReadValue(
+            FailedToReadValueError::new(0, err.into()),
+        ));
+    }
+
+    Ok(validate_bxes_raw(&raw_log))
+}
+
+/// Validates an uncompressed single-file log.
+pub fn validate_bxes_raw(raw_log: &[u8]) -> BxesValidationReport {
+    let mut validator = BxesValidator::new();
+    let mut reader = SectionReader::new(raw_log, BxesSection::Values);
+
+    let result = validator.validate_header(&mut reader, None).and_then(|_| {
+        validator.validate_values(&mut reader)?;
+        reader.section = BxesSection::KeyValues;
+        validator.validate_key_values(&mut reader)?;
+        reader.section = BxesSection::Metadata;
+        validator.validate_metadata(&mut reader)?;
+        reader.section = BxesSection::Variants;
+        validator.validate_variants(&mut reader)?;
+        reader.section = BxesSection::VariantsIndex;
+        validator.validate_variants_index(&mut reader)
+    });
+
+    validator.finish(result)
+}
+
+/// Validates a multiple-files log, all files are validated even if some of them are broken.
+pub fn validate_bxes_multiple_files(
+    directory_path: &str,
+) -> Result<BxesValidationReport, BxesReadError> {
+    let read_file = |file_name: &str| -> Result<Vec<u8>, BxesReadError> {
+        let path = Path::new(directory_path).join(file_name);
+        match fs::read(&path) {
+            Ok(bytes) => Ok(bytes),
+            Err(err) => Err(BxesReadError::FailedToOpenFile {
+                path: path.to_string_lossy().into_owned(),
+                source: err.into(),
+            }),
+        }
+    };
+
+    let values = read_file(VALUES_FILE_NAME)?;
+    let kv_pairs = read_file(KEY_VALUES_FILE_NAME)?;
+    let metadata = read_file(METADATA_FILE_NAME)?;
+    let variants = read_file(VARIANTS_FILE_NAME)?;
+
+    let mut validator = BxesValidator::new();
+    let mut values_reader = SectionReader::new(&values, BxesSection::Values);
+    if let Err(issue) = validator.validate_header(&mut values_reader, None) {
+        return Ok(validator.finish(Err(issue)));
+    }
+
+    let result = validator
+        .validate_values(&mut values_reader)
+        .and_then(|_| values_reader.validate_end());
+
+    if let Err(issue) = result {
+        validator.issues.push(issue);
+        validator.complete = false;
+    }
+
+    let version = validator.version;
+    let mut validate_file = |bytes: &[u8], section: BxesSection, action: SectionAction| {
+        let mut reader = SectionReader::new(bytes, section);
+        let result = validator
+            .validate_header(&mut reader, Some(version))
+            .and_then(|_| action(&mut validator, &mut reader))
+            .and_then(|_| reader.validate_end());
+
+        if let Err(issue) = result {
+            validator.issues.push(issue);
+            validator.complete = false;
+        }
+    };
+
+    validate_file(&kv_pairs, BxesSection::KeyValues, |validator, reader| {
+        validator.validate_key_values(reader)
+    });
+
+    validate_file(&metadata, BxesSection::Metadata, |validator, reader| {
+        validator.validate_metadata(reader)
+    });
+
+    validate_file(&variants, BxesSection::Variants, |validator, reader| {
+        validator.validate_variants(reader)
+    });
+
+    Ok(validator.finish(Ok(())))
+}
+
+type SectionAction = fn(&mut BxesValidator, &mut SectionReader) -> Result<(), BxesValidationIssue>;
+
+struct SectionReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    section: BxesSection,
+}
+
+impl<'a> SectionReader<'a> {
+    fn new(bytes: &'a [u8], section: BxesSection) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            section,
+        }
+    }
+
+    fn issue(&self, offset: usize, kind: BxesValidationIssueKind) -> BxesValidationIssue {
+        BxesValidationIssue {
+            section: self.section,
+            offset,
+            kind,
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], BxesValidationIssue> {
+        if length > self.remaining() {
+            return Err(self.issue(self.position, BxesValidationIssueKind::Truncated));
+        }
+
+        let bytes = &self.bytes[self.position..self.position + length];
+        self.position += length;
+
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, BxesValidationIssue> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32, BxesValidationIssue> {
+        Ok(u32::from_le_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, BxesValidationIssue> {
+        Ok(u64::from_le_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+
+    /// Reads a count or an index, see `try_read_compact_u32`.
+    fn read_compact_u32(&mut self, version: u32) -> Result<u32, BxesValidationIssue> {
+        if version >= COMPACT_LENGTHS_VERSION {
+            self.read_leb128()
+        } else {
+            self.read_u32()
+        }
+    }
+
+    fn read_leb128(&mut self) -> Result<u32, BxesValidationIssue> {
+        let offset = self.position;
+        match u32::try_from(self.read_leb128_u64()?) {
+            Ok(value) => Ok(value),
+            Err(_) => Err(self.issue(offset, BxesValidationIssueKind::InvalidLeb128)),
+        }
+    }
+
+    fn read_leb128_u64(&mut self) -> Result<u64, BxesValidationIssue> {
+        let offset = self.position;
+        let mut value = 0u64;
+
+        for shift in (0..70).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(self.issue(offset, BxesValidationIssueKind::InvalidLeb128))
+    }
+
+    /// Fails if a collection of `count` items, each of at least `min_item_size` bytes,
+    /// can not fit into the remaining bytes.
+    fn validate_count(&self, count: u32, min_item_size: usize) -> Result<(), BxesValidationIssue> {
+        match (count as usize).checked_mul(min_item_size) {
+            Some(size) if size <= self.remaining() => Ok(()),
+            _ => Err(self.issue(self.bytes.len(), BxesValidationIssueKind::Truncated)),
+        }
+    }
+
+    fn validate_end(&self) -> Result<(), BxesValidationIssue> {
+        match self.remaining() {
+            0 => Ok(()),
+            count => Err(self.issue(self.position, BxesValidationIssueKind::TrailingBytes(count))),
+        }
+    }
+}
+
+struct BxesValidator {
+    issues: Vec<BxesValidationIssue>,
+    complete: bool,
+    version: u32,
+    string_encoding: BxesStringEncoding,
+    checksum: BxesChecksum,
+    values_offsets: Vec<usize>,
+    string_values: Vec<bool>,
+    previous_string: Vec<u8>,
+    used_values: Vec<bool>,
+    kv_pairs_count: u32,
+    variants: Vec<(usize, u32)>,
+    traces_count: u64,
+}
+
+impl BxesValidator {
+    fn new() -> Self {
+        Self {
+            issues: vec![],
+            complete: true,
+            version: BXES_VERSION,
+            string_encoding: BxesStringEncoding::Plain,
+            checksum: BxesChecksum::None,
+            values_offsets: vec![],
+            string_values: vec![],
+            previous_string: vec![],
+            used_values: vec![],
+            kv_pairs_count: 0,
+            variants: vec![],
+            traces_count: 0,
+        }
+    }
+
+    fn finish(mut self, result: Result<(), BxesValidationIssue>) -> BxesValidationReport {
+        if let Err(issue) = result {
+            self.issues.push(issue);
+            self.complete = false;
+        }
+
+        // Values of partially read logs may be referenced from the unread part.
+        if self.complete {
+            for (index, used) in self.used_values.iter().enumerate() {
+                if !used {
+                    self.issues.push(BxesValidationIssue {
+                        section: BxesSection::Values,
+                        offset: self.values_offsets[index],
+                        kind: BxesValidationIssueKind::UnusedValue(index as u32),
+                    });
+                }
+            }
+        }
+
+        BxesValidationReport {
+            issues: self.issues,
+            variants_count: self.variants.len() as u32,
+            traces_count: self.traces_count,
+        }
+    }
+
+    /// Validates the version, the encoding of strings which follows it since `FRONT_CODED_STRINGS_VERSION`
+    /// and the kind of checksums which follows the encoding since `CHECKSUMS_VERSION`.
+    fn validate_header(
+        &mut self,
+        reader: &mut SectionReader,
+        expected_version: Option<u32>,
+    ) -> Result<(), BxesValidationIssue> {
+        let version = reader.read_u32()?;
+        if !SUPPORTED_VERSIONS.contains(&version) {
+            return Err(reader.issue(0, BxesValidationIssueKind::UnsupportedVersion(version)));
+        }
+
+        match expected_version {
+            None => self.version = version,
+            Some(expected) if expected != version => {
+                let kind = BxesValidationIssueKind::VersionsMismatch {
+                    expected,
+                    actual: version,
+                };
+
+                self.issues.push(reader.issue(0, kind));
+            }
+            Some(_) => {}
+        }
+
+        // Mismatched files are reported and then validated with the layout of the log's version.
+        let layout_version = expected_version.unwrap_or(version);
+        let string_encoding = match layout_version >= FRONT_CODED_STRINGS_VERSION {
+            true => {
+                let offset = reader.position;
+                let string_encoding = reader.read_u8()?;
+                match BxesStringEncoding::from_u8(string_encoding) {
+                    Some(string_encoding) => string_encoding,
+                    None => {
+                        let kind =
+                            BxesValidationIssueKind::UnsupportedStringEncoding(string_encoding);
+                        return Err(reader.issue(offset, kind));
+                    }
+                }
+            }
+            false => BxesStringEncoding::Plain,
+        };
+
+        match expected_version {
+            None => self.string_encoding = string_encoding,
+            Some(_) if string_encoding != self.string_encoding => {
+                let kind = BxesValidationIssueKind::StringEncodingsMismatch {
+                    expected: self.string_encoding,
+                    actual: string_encoding,
+                };
+
+                return Err(reader.issue(0, kind));
+            }
+            Some(_) => {}
+        }
+
+        let checksum = match layout_version >= CHECKSUMS_VERSION {
+            true => {
+                let offset = reader.position;
+                let checksum = reader.read_u8()?;
+                match BxesChecksum::from_u8(checksum) {
+                    Some(checksum) => checksum,
+                    None => {
+                        let kind = BxesValidationIssueKind::UnsupportedChecksum(checksum);
+                        return Err(reader.issue(offset, kind));
+                    }
+                }
+            }
+            false => BxesChecksum::None,
+        };
+
+        match expected_version {
+            None => self.checksum = checksum,
+            Some(_) if checksum != self.checksum => {
+                let kind = BxesValidationIssueKind::ChecksumKindsMismatch {
+                    expected: self.checksum,
+                    actual: checksum,
+                };
+
+                return Err(reader.issue(0, kind));
+            }
+            Some(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Reads the checksum which follows the section starting at `start` and reports a mismatch.
+    fn validate_checksum(
+        &mut self,
+        reader: &mut SectionReader,
+        start: usize,
+    ) -> Result<(), BxesValidationIssue> {
+        if self.checksum == BxesChecksum::None {
+            return Ok(());
+        }
+
+        let checksum = crc32c::crc32c(&reader.bytes[start..reader.position]);
+        if reader.read_u32()? != checksum {
+            self.issues
+                .push(reader.issue(start, BxesValidationIssueKind::ChecksumMismatch));
+        }
+
+        Ok(())
+    }
+
+    fn validate_values(&mut self, reader: &mut SectionReader) -> Result<(), BxesValidationIssue> {
+        let start = reader.position;
+        let count = reader.read_compact_u32(self.version)?;
+        reader.validate_count(count, 2)?;
+
+        self.used_values = vec![false; count as usize];
+
+        let mut first_indices = HashMap::new();
+        for index in 0..count {
+            let offset = reader.position;
+            self.values_offsets.push(offset);
+            self.validate_value(reader)?;
+
+            // Equal front-coded strings may be encoded differently, so they are compared by contents.
+            let front_coded = self.string_encoding == BxesStringEncoding::FrontCoded;
+            let value_bytes = match self.string_values.last() {
+                Some(true) if front_coded => {
+                    Cow::Owned([&[reader.bytes[offset]], self.previous_string.as_slice()].concat())
+                }
+                _ => Cow::Borrowed(&reader.bytes[offset..reader.position]),
+            };
+
+            match first_indices.get(&value_bytes) {
+                Some(first_index) => self.issues.push(reader.issue(
+                    offset,
+                    BxesValidationIssueKind::DuplicatedValue {
+                        index,
+                        first_index: *first_index,
+                    },
+                )),
+                None => {
+                    first_indices.insert(value_bytes, index);
+                }
+            }
+        }
+
+        self.validate_checksum(reader, start)
+    }
+
+    fn validate_value(&mut self, reader: &mut SectionReader) -> Result<(), BxesValidationIssue> {
+        let offset = reader.position;
+        let type_id = reader.read_u8()?;
+        let type_id = match TypeIds::from_u8(type_id) {
+            Some(type_id) => type_id,
+            None => {
+                return Err(reader.issue(offset, BxesValidationIssueKind::InvalidTypeId(type_id)))
+            }
+        };
+
+        self.string_values.push(matches!(type_id, TypeIds::String));
+
+        let version = self.version;
+        let index_size = if version >= COMPACT_LENGTHS_VERSION {
+            1
+        } else {
+            4
+        };
+
+        match type_id {
+            TypeIds::I32 | TypeIds::U32 | TypeIds::F32 => reader.read_bytes(4).map(|_| ()),
+            TypeIds::I64 | TypeIds::U64 | TypeIds::F64 | TypeIds::Timestamp => {
+                reader.read_bytes(8).map(|_| ())
+            }
+            TypeIds::Bool => reader.read_bytes(1).map(|_| ()),
+            TypeIds::Guid => reader.read_bytes(16).map(|_| ()),
+            TypeIds::String => {
+                let mut string = vec![];
+                if self.string_encoding == BxesStringEncoding::FrontCoded {
+                    let prefix_offset = reader.position;
+                    let prefix_length = reader.read_leb128_u64()?;
+
+                    match self.previous_string.get(..prefix_length as usize) {
+                        Some(prefix) => string.extend_from_slice(prefix),
+                        None => self.issues.push(reader.issue(
+                            prefix_offset,
+                            BxesValidationIssueKind::InvalidSharedPrefixLength(prefix_length),
+                        )),
+                    }
+                }
+
+                let length = if version >= COMPACT_LENGTHS_VERSION {
+                    reader.read_leb128_u64()?
+                } else {
+                    reader.read_u64()?
+                };
+
+                let string_offset = reader.position;
+                let bytes = match usize::try_from(length) {
+                    Ok(length) => reader.read_bytes(length)?,
+                    Err(_) => {
+                        return Err(reader.issue(string_offset, BxesValidationIssueKind::Truncated))
+                    }
+                };
+
+                string.extend_from_slice(bytes);
+                if std::str::from_utf8(&string).is_err() {
+                    self.issues
+                        .push(reader.issue(string_offset, BxesValidationIssueKind::InvalidUtf8));
+                }
+
+                self.previous_string = string;
+
+                Ok(())
+            }
+            TypeIds::BrafLifecycle => self.validate_enum::<BrafLifecycle>(reader),
+            TypeIds::StandardLifecycle => self.validate_enum::<StandardLifecycle>(reader),
+            TypeIds::SoftwareEventType => self.validate_enum::<SoftwareEventType>(reader),
+            TypeIds::Artifact => {
+                let items_count = reader.read_compact_u32(version)?;
+                reader.validate_count(items_count, 3 * index_size)?;
+
+                for _ in 0..items_count * 3 {
+                    self.validate_nested_value_index(reader)?;
+                }
+
+                Ok(())
+            }
+            TypeIds::Drivers => {
+                let drivers_count = reader.read_compact_u32(version)?;
+                reader.validate_count(drivers_count, 8 + 2 * index_size)?;
+
+                for _ in 0..drivers_count {
+                    reader.read_bytes(8)?;
+                    self.validate_nested_value_index(reader)?;
+                    self.validate_nested_value_index(reader)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn validate_enum<T: FromPrimitive>(
+        &mut self,
+        reader: &mut SectionReader,
+    ) -> Result<(), BxesValidationIssue> {
+        let offset = reader.position;
+        let value = reader.read_u8()?;
+        if T::from_u8(value).is_none() {
+            self.issues
+                .push(reader.issue(offset, BxesValidationIssueKind::InvalidEnumValue(value)));
+        }
+
+        Ok(())
+    }
+
+    fn validate_key_values(
+        &mut self,
+        reader: &mut SectionReader,
+    ) -> Result<(), BxesValidationIssue> {
+        let start = reader.position;
+        let count = reader.read_compact_u32(self.version)?;
+        reader.validate_count(count, 2)?;
+        self.kv_pairs_count = count;
+
+        for index in 0..count {
+            let offset = reader.position;
+            let key_index = self.validate_value_index(reader, |reader| reader.read_leb128())?;
+            self.validate_value_index(reader, |reader| reader.read_leb128())?;
+
+            if let Some(false) = self.string_values.get(key_index as usize) {
+                self.issues.push(reader.issue(
+                    offset,
+                    BxesValidationIssueKind::NonStringAttributeKey(index),
+                ));
+            }
+        }
+
+        self.validate_checksum(reader, start)
+    }
+
+    fn validate_metadata(&mut self, reader: &mut SectionReader) -> Result<(), BxesValidationIssue> {
+        let version = self.version;
+        let read_compact_u32 = |reader: &mut SectionReader| reader.read_compact_u32(version);
+
+        let start = reader.position;
+        let properties_count = read_compact_u32(reader)?;
+        for _ in 0..properties_count {
+            self.validate_kv_index(reader, read_compact_u32)?;
+        }
+
+        let extensions_count = read_compact_u32(reader)?;
+        for _ in 0..extensions_count {
+            for _ in 0..3 {
+                self.validate_value_index(reader, read_compact_u32)?;
+            }
+        }
+
+        let globals_count = read_compact_u32(reader)?;
+        for _ in 0..globals_count {
+            self.validate_enum::<BxesGlobalKind>(reader)?;
+
+            let entity_globals_count = read_compact_u32(reader)?;
+            for _ in 0..entity_globals_count {
+                self.validate_kv_index(reader, read_compact_u32)?;
+            }
+        }
+
+        let classifiers_count = read_compact_u32(reader)?;
+        for _ in 0..classifiers_count {
+            self.validate_value_index(reader, read_compact_u32)?;
+
+            let keys_count = read_compact_u32(reader)?;
+            for _ in 0..keys_count {
+                self.validate_value_index(reader, read_compact_u32)?;
+            }
+        }
+
+        self.validate_checksum(reader, start)
+    }
+
+    fn validate_variants(&mut self, reader: &mut SectionReader) -> Result<(), BxesValidationIssue> {
+        let version = self.version;
+        let read_compact_u32 = |reader: &mut SectionReader| reader.read_compact_u32(version);
+
+        let start = reader.position;
+        let count = read_compact_u32(reader)?;
+        self.validate_checksum(reader, start)?;
+
+        let count_size = if version >= COMPACT_LENGTHS_VERSION {
+            1
+        } else {
+            4
+        };
+        reader.validate_count(count, 3 * count_size + self.checksum.size())?;
+
+        for _ in 0..count {
+            let offset = reader.position;
+            let traces_count = read_compact_u32(reader)?;
+            self.traces_count += traces_count as u64;
+
+            let metadata_count = read_compact_u32(reader)?;
+            for _ in 0..metadata_count {
+                self.validate_kv_index(reader, read_compact_u32)?;
+            }
+
+            let events_count = read_compact_u32(reader)?;
+            for index in 0..events_count {
+                self.validate_event(reader, index == 0)?;
+            }
+
+            self.validate_checksum(reader, offset)?;
+            self.variants.push((offset, events_count));
+        }
+
+        Ok(())
+    }
+
+    fn validate_event(
+        &mut self,
+        reader: &mut SectionReader,
+        first_in_variant: bool,
+    ) -> Result<(), BxesValidationIssue> {
+        self.validate_value_index(reader, |reader| reader.read_leb128())?;
+        if self.version >= DELTA_TIMESTAMPS_VERSION && !first_in_variant {
+            reader.read_leb128_u64()?;
+        } else {
+            reader.read_bytes(8)?;
+        }
+
+        if self.version >= EVENT_LIFECYCLE_VERSION {
+            let offset = reader.position;
+            match TypeIds::from_u8(reader.read_u8()?) {
+                Some(TypeIds::BrafLifecycle) => self.validate_enum::<BrafLifecycle>(reader)?,
+                Some(TypeIds::StandardLifecycle) => {
+                    self.validate_enum::<StandardLifecycle>(reader)?
+                }
+                _ => {
+                    let kind = BxesValidationIssueKind::InvalidTypeId(reader.bytes[offset]);
+                    self.issues.push(reader.issue(offset, kind));
+                    reader.read_u8()?;
+                }
+            }
+        }
+
+        let attributes_count = reader.read_leb128()?;
+        for _ in 0..attributes_count {
+            self.validate_kv_index(reader, |reader| reader.read_leb128())?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_variants_index(
+        &mut self,
+        reader: &mut SectionReader,
+    ) -> Result<(), BxesValidationIssue> {
+        let index_offset = reader.position;
+        let remaining = reader.remaining();
+        if remaining == 0 {
+            return Ok(());
+        }
+
+        let checksum_size = self.checksum.size();
+        let index_size = self.variants.len() * VARIANTS_INDEX_ENTRY_SIZE
+            + 4
+            + VARIANTS_INDEX_TRAILER_SIZE
+            + checksum_size;
+        if remaining < 4 + checksum_size {
+            return reader.validate_end();
+        }
+
+        let marker_offset = reader.bytes.len() - 4 - checksum_size;
+        if reader.bytes[marker_offset..marker_offset + 4] != VARIANTS_INDEX_MARKER.to_le_bytes() {
+            return reader.validate_end();
+        }
+
+        if remaining != index_size {
+            return Err(reader.issue(index_offset, BxesValidationIssueKind::InvalidVariantsIndex));
+        }
+
+        for (variant_offset, events_count) in &self.variants {
+            let entry_offset = reader.position;
+            if reader.read_u64()? != *variant_offset as u64 || reader.read_u32()? != *events_count {
+                return Err(
+                    reader.issue(entry_offset, BxesValidationIssueKind::InvalidVariantsIndex)
+                );
+            }
+        }
+
+        let trailer_offset = reader.position;
+        if reader.read_u32()? != self.variants.len() as u32
+            || reader.read_u64()? != index_offset as u64
+        {
+            return Err(reader.issue(
+                trailer_offset,
+                BxesValidationIssueKind::InvalidVariantsIndex,
+            ));
+        }
+
+        reader.read_u32()?;
+
+        self.validate_checksum(reader, index_offset)
+    }
+
+    fn validate_value_index(
+        &mut self,
+        reader: &mut SectionReader,
+        read_index: impl FnOnce(&mut SectionReader) -> Result<u32, BxesValidationIssue>,
+    ) -> Result<u32, BxesValidationIssue> {
+        let offset = reader.position;
+        let index = read_index(reader)?;
+
+        match self.used_values.get_mut(index as usize) {
+            Some(used) => *used = true,
+            None => self
+                .issues
+                .push(reader.issue(offset, BxesValidationIssueKind::DanglingValueIndex(index))),
+        }
+
+        Ok(index)
+    }
+
+    /// Values nested in artifacts and drivers are read before the value which references them,
+    /// so they must precede it.
+    fn validate_nested_value_index(
+        &mut self,
+        reader: &mut SectionReader,
+    ) -> Result<(), BxesValidationIssue> {
+        let offset = reader.position;
+        let index = reader.read_compact_u32(self.version)?;
+
+        let value_index = self.values_offsets.len() - 1;
+        match self.used_values.get_mut(index as usize) {
+            Some(used) if (index as usize) < value_index => *used = true,
+            _ => self
+                .issues
+                .push(reader.issue(offset, BxesValidationIssueKind::DanglingValueIndex(index))),
+        }
+
+        Ok(())
+    }
+
+    fn validate_kv_index(
+        &mut self,
+        reader: &mut SectionReader,
+        read_index: impl FnOnce(&mut SectionReader) -> Result<u32, BxesValidationIssue>,
+    ) -> Result<(), BxesValidationIssue> {
+        let offset = reader.position;
+        let index = read_index(reader)?;
+
+        if index >= self.kv_pairs_count {
+            self.issues.push(reader.issue(
+                offset,
+                BxesValidationIssueKind::DanglingKeyValueIndex(index),
+            ));
+        }
+
+        Ok(())
+    }
+}
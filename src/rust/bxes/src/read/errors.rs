@@ -1,79 +1,223 @@
-use std::string::FromUtf8Error;
-use crate::models::BxesValue;
+use std::{fmt::Display, io, str::Utf8Error};
 
-#[derive(Debug)]
+use thiserror::Error;
+use zip::result::ZipError;
+
+use crate::{
+    binary_rw::error::BinaryError,
+    constants::SUPPORTED_VERSIONS,
+    models::{BxesChecksum, BxesStringEncoding, BxesValue},
+};
+
+#[derive(Debug, Error)]
 pub enum BxesReadError {
-    FailedToOpenFile(String),
-    FailedToReadValue(FailedToReadValueError),
-    FailedToReadPos(String),
-    FailedToCreateUtf8String(FromUtf8Error),
-    FailedToParseTypeId(u8),
+    #[error("Failed to open file {path}")]
+    FailedToOpenFile { path: String, source: BinaryError },
+    #[error(transparent)]
+    FailedToReadValue(#[from] FailedToReadValueError),
+    #[error("Failed to get or set stream position")]
+    FailedToReadPos(#[source] BinaryError),
+    #[error("Failed to create string at offset {offset}")]
+    FailedToCreateUtf8String { offset: usize, source: Utf8Error },
+    #[error("Shared prefix length {length} at offset {offset} exceeds the length of the previous string")]
+    InvalidSharedPrefixLength { length: u64, offset: usize },
+    #[error("Failed to parse type id {type_id} at offset {offset}")]
+    FailedToParseTypeId { type_id: u8, offset: usize },
+    #[error("Failed to find bxes value for index: {0}")]
     FailedToIndexValue(usize),
+    #[error("Failed to find kv pair for index: {0}")]
     FailedToIndexKeyValue(usize),
-    LifecycleOfEventOutOfRange,
+    #[error("Lifecycle value {value} at offset {offset} is out of range")]
+    LifecycleOfEventOutOfRange { value: u8, offset: usize },
+    #[error("Event attribute key is not a string")]
     EventAttributeKeyIsNotAString,
-    VersionsMismatchError(VersionsMismatchError),
-    FailedToExtractArchive,
-    TooManyFilesInArchive,
-    FailedToCreateTempDir,
+    #[error(transparent)]
+    VersionsMismatchError(#[from] VersionsMismatchError),
+    #[error("Invalid bxes archive: {0}")]
     InvalidArchive(String),
+    #[error("Failed to read zip archive")]
+    FailedToReadArchive(#[from] ZipError),
+    #[error("Failed to detect container of the log")]
+    FailedToDetectContainer(#[source] io::Error),
+    #[error("Expected string value, found: {0:?}")]
     ExpectedString(BxesValue),
-    Leb128ReadError(String),
+    #[error("Failed to read LEB128 encoded value at offset {offset}")]
+    Leb128ReadError {
+        offset: usize,
+        source: leb128::read::Error,
+    },
+    #[error("The log does not contain variants index")]
+    MissingVariantsIndex,
+    #[error("There is no trace variant with index {0}")]
+    VariantIndexOutOfRange(usize),
+    #[error("Unsupported bxes version: {0}, supported versions: {SUPPORTED_VERSIONS:?}")]
+    UnsupportedVersion(u32),
+    #[error("Unsupported string encoding: {0}")]
+    UnsupportedStringEncoding(u8),
+    #[error("String encodings mismatch: expected {expected:?} strings, found {actual:?} strings")]
+    StringEncodingsMismatch {
+        expected: BxesStringEncoding,
+        actual: BxesStringEncoding,
+    },
+    #[error("Unsupported checksum kind: {0}")]
+    UnsupportedChecksum(u8),
+    #[error("Checksums mismatch: expected {expected:?} checksums, found {actual:?} checksums")]
+    ChecksumKindsMismatch {
+        expected: BxesChecksum,
+        actual: BxesChecksum,
+    },
+    #[error("Checksum of {section} starting at offset {offset} does not match its contents")]
+    ChecksumMismatch { section: BxesSection, offset: usize },
+    #[error("Failed to decompress log")]
+    FailedToDecompress(#[source] io::Error),
+    #[error("Error {location}")]
+    Located {
+        location: BxesErrorLocation,
+        source: Box<BxesReadError>,
+    },
 }
 
-impl ToString for BxesReadError {
-    fn to_string(&self) -> String {
+impl BxesReadError {
+    /// Attaches the location to the error, parts of the location which are
+    /// already known from the place where the error occurred are kept.
+    pub(crate) fn located(self, location: BxesErrorLocation) -> Self {
+        match self {
+            BxesReadError::Located {
+                location: existing_location,
+                source,
+            } => BxesReadError::Located {
+                location: existing_location.or(location),
+                source,
+            },
+            err => BxesReadError::Located {
+                location,
+                source: Box::new(err),
+            },
+        }
+    }
+
+    /// Returns the location at which the error occurred, if it is known.
+    pub fn location(&self) -> Option<&BxesErrorLocation> {
         match self {
-            BxesReadError::FailedToOpenFile(value) => format!("Failed to open file {}", value),
-            BxesReadError::FailedToReadValue(err) => format!("Failed to read value: {}", err.to_string()),
-            BxesReadError::FailedToReadPos(error_message) => format!("Failed to read pos from stream: {}", error_message),
-            BxesReadError::FailedToCreateUtf8String(err) => format!("Failed to create string: {}", err.to_string()),
-            BxesReadError::FailedToParseTypeId(type_id) => format!("Failed to parse type id: {}", type_id),
-            BxesReadError::FailedToIndexValue(index) => format!("Failed to find bxes value for index: {}", index),
-            BxesReadError::FailedToIndexKeyValue(index) => format!("Failed to find kv pair for index: {}", index),
-            BxesReadError::LifecycleOfEventOutOfRange => format!("LifecycleOfEventOutOfRange"),
-            BxesReadError::EventAttributeKeyIsNotAString => format!("EventAttributeKeyIsNotAString"),
-            BxesReadError::VersionsMismatchError(err) => err.to_string(),
-            BxesReadError::FailedToExtractArchive => format!("FailedToExtractArchive"),
-            BxesReadError::TooManyFilesInArchive => format!("TooManyFilesInArchive"),
-            BxesReadError::FailedToCreateTempDir => format!("FailedToCreateTempDir"),
-            BxesReadError::InvalidArchive(message) => format!("Invalid bxes archive: {}", message),
-            BxesReadError::ExpectedString(value) => format!("Expected string value, found: {:?}", value),
-            BxesReadError::Leb128ReadError(message) => format!("Failed to read LEB128 encoded value: {}", message)
+            BxesReadError::Located { location, .. } => Some(location),
+            _ => None,
         }
     }
 }
 
-#[derive(Debug)]
-pub struct FailedToReadValueError {
-    pub offset: usize,
-    pub message: String,
+/// A part of a bxes log: a file of a multiple-files log or a section of a single-file log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BxesSection {
+    Values,
+    KeyValues,
+    Metadata,
+    Variants,
+    VariantsIndex,
+}
+
+impl Display for BxesSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BxesSection::Values => "values",
+            BxesSection::KeyValues => "key-value pairs",
+            BxesSection::Metadata => "metadata",
+            BxesSection::Variants => "variants",
+            BxesSection::VariantsIndex => "variants index",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Location of a read or write error, offsets are counted from the start of the file
+/// of a multiple-files log or from the start of the raw single-file log.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BxesErrorLocation {
+    pub section: Option<BxesSection>,
+    pub offset: Option<usize>,
+    pub variant_index: Option<usize>,
+    pub event_index: Option<usize>,
+}
+
+impl BxesErrorLocation {
+    pub fn section(section: BxesSection, offset: usize) -> Self {
+        Self {
+            section: Some(section),
+            offset: Some(offset),
+            ..Default::default()
+        }
+    }
+
+    pub fn variant(variant_index: usize) -> Self {
+        Self {
+            section: Some(BxesSection::Variants),
+            variant_index: Some(variant_index),
+            ..Default::default()
+        }
+    }
+
+    pub fn event(offset: usize, event_index: usize) -> Self {
+        Self {
+            section: Some(BxesSection::Variants),
+            offset: Some(offset),
+            event_index: Some(event_index),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn or(self, other: BxesErrorLocation) -> Self {
+        Self {
+            section: self.section.or(other.section),
+            offset: self.offset.or(other.offset),
+            variant_index: self.variant_index.or(other.variant_index),
+            event_index: self.event_index.or(other.event_index),
+        }
+    }
 }
 
-impl ToString for FailedToReadValueError {
-    fn to_string(&self) -> String {
-        format!("Failed to read value at offset {}, error: {}", self.offset, self.message)
+impl Display for BxesErrorLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.section {
+            Some(section) => write!(f, "in {}", section)?,
+            None => write!(f, "in log")?,
+        }
+
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {}", offset)?;
+        }
+
+        if let Some(variant_index) = self.variant_index {
+            write!(f, ", variant {}", variant_index)?;
+        }
+
+        if let Some(event_index) = self.event_index {
+            write!(f, ", event {}", event_index)?;
+        }
+
+        Ok(())
     }
 }
 
+#[derive(Debug, Error)]
+#[error("Failed to read value at offset {offset}")]
+pub struct FailedToReadValueError {
+    pub offset: usize,
+    pub source: BinaryError,
+}
+
 impl FailedToReadValueError {
-    pub fn new(offset: usize, message: String) -> Self {
-        Self { offset, message }
+    pub fn new(offset: usize, source: BinaryError) -> Self {
+        Self { offset, source }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[error("Versions mismatch: previous version: {previous_version}, current version: {current_version}")]
 pub struct VersionsMismatchError {
     previous_version: u32,
     current_version: u32,
 }
 
-impl ToString for VersionsMismatchError {
-    fn to_string(&self) -> String {
-        format!("Versions mismatch: previous version: {}, current version: {}", self.previous_version, self.current_version)
-    }
-}
-
 impl VersionsMismatchError {
     pub fn new(previous_version: u32, current_version: u32) -> Self {
         Self {
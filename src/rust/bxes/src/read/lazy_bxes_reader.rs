@@ -0,0 +1,328 @@
+use std::{io::Read, sync::Arc};
+
+use crate::{
+    binary_rw::core::{BinaryReader, Endian, ReadStream, SeekStream},
+    models::*,
+};
+
+use super::{
+    errors::{BxesErrorLocation, BxesReadError, BxesSection, FailedToReadValueError},
+    parallel_bxes_reader::try_read_traces_variants_parallel,
+    read_utils::*,
+};
+
+/// An event log whose values, key-value pairs and metadata are already read,
+/// while trace variants are decoded on demand from the underlying stream.
+pub struct BxesLazyEventLog {
+    pub version: u32,
+    pub string_encoding: BxesStringEncoding,
+    pub checksum: BxesChecksum,
+    pub metadata: BxesEventLogMetadata,
+    pub(crate) values: Vec<Arc<BxesValue>>,
+    pub(crate) kv_pairs: Vec<(u32, u32)>,
+    pub(crate) variants_count: u32,
+    pub(crate) stream: Box<dyn ReadStream + Send>,
+}
+
+pub enum BxesLazyLogItem {
+    TraceVariantStart(BxesTraceVariantStart),
+    Event(BxesEvent),
+}
+
+impl BxesLazyEventLog {
+    pub(crate) fn new(
+        version: u32,
+        string_encoding: BxesStringEncoding,
+        checksum: BxesChecksum,
+        metadata: BxesEventLogMetadata,
+        values: Vec<Arc<BxesValue>>,
+        kv_pairs: Vec<(u32, u32)>,
+        mut stream: Box<dyn ReadStream + Send>,
+    ) -> Result<Self, BxesReadError> {
+        let mut reader = BinaryReader::new(&mut stream, Endian::Little);
+        let variants_count = try_read_variants_count(&mut reader, version, checksum)?;
+
+        Ok(Self {
+            version,
+            string_encoding,
+            checksum,
+            metadata,
+            values,
+            kv_pairs,
+            variants_count,
+            stream,
+        })
+    }
+
+    pub fn variants_count(&self) -> u32 {
+        self.variants_count
+    }
+
+    pub fn values(&self) -> &Vec<Arc<BxesValue>> {
+        &self.values
+    }
+
+    pub fn kv_pairs(&self) -> &Vec<(u32, u32)> {
+        &self.kv_pairs
+    }
+
+    pub fn variants(self) -> BxesTraceVariantsIterator {
+        BxesTraceVariantsIterator {
+            remaining_variants: self.variants_count,
+            log: self,
+        }
+    }
+
+    pub fn items(self) -> BxesLazyLogItemsIterator {
+        BxesLazyLogItemsIterator {
+            remaining_variants: self.variants_count,
+            events_count: 0,
+            remaining_events: 0,
+            last_timestamp: None,
+            variant_offset: 0,
+            checksum: None,
+            log: self,
+        }
+    }
+
+    pub fn into_event_log(self) -> Result<BxesEventLog, BxesReadError> {
+        let version = self.version;
+        let metadata = self.metadata.clone();
+        let variants = self.variants().collect::<Result<Vec<_>, _>>()?;
+
+        Ok(BxesEventLog {
+            version,
+            metadata,
+            variants,
+        })
+    }
+
+    /// Reads all remaining trace variants into memory and decodes them in parallel.
+    pub fn into_event_log_parallel(mut self) -> Result<BxesEventLog, BxesReadError> {
+        let (position, bytes) = self.try_read_remaining_bytes()?;
+        let variants = try_read_traces_variants_parallel(
+            &bytes,
+            position,
+            self.version,
+            self.checksum,
+            self.variants_count,
+            &self.values,
+            &self.kv_pairs,
+        )?;
+
+        Ok(BxesEventLog {
+            version: self.version,
+            metadata: self.metadata,
+            variants,
+        })
+    }
+
+    /// Reads all remaining trace variants one by one, passing each along with the offset
+    /// at which it starts in the stream to the action.
+    pub(crate) fn try_for_each_variant_with_offset(
+        &mut self,
+        mut action: impl FnMut(usize, BxesTraceVariant),
+    ) -> Result<(), BxesReadError> {
+        let version = self.version;
+        let checksum = self.checksum;
+
+        for index in 0..self.variants_count as usize {
+            let variant = self.execute_with_reader(|reader, values, kv_pairs| {
+                let offset = try_tell(reader)?;
+
+                let variant = try_read_trace_variant(reader, version, checksum, values, kv_pairs)?;
+                Ok((offset, variant))
+            });
+
+            match variant {
+                Ok((offset, variant)) => action(offset, variant),
+                Err(err) => return Err(err.located(BxesErrorLocation::variant(index))),
+            }
+        }
+
+        self.variants_count = 0;
+        Ok(())
+    }
+
+    /// Returns the current position of the stream and all bytes after it.
+    fn try_read_remaining_bytes(&mut self) -> Result<(usize, Vec<u8>), BxesReadError> {
+        let position = match self.stream.tell() {
+            Ok(position) => position,
+            Err(err) => return Err(BxesReadError::FailedToReadPos(err)),
+        };
+
+        let length = match self.stream.len() {
+            Ok(length) => length,
+            Err(err) => return Err(BxesReadError::FailedToReadPos(err)),
+        };
+
+        let mut bytes = vec![0; length - position];
+        match self.stream.read_exact(&mut bytes) {
+            Ok(()) => Ok((position, bytes)),
+            Err(err) => Err(BxesReadError::FailedToReadValue(FailedToReadValueError::new(
+                position,
+                err.into(),
+            ))),
+        }
+    }
+
+    fn execute_with_reader<T>(
+        &mut self,
+        reader_func: impl FnOnce(
+            &mut BinaryReader,
+            &Vec<Arc<BxesValue>>,
+            &Vec<(u32, u32)>,
+        ) -> Result<T, BxesReadError>,
+    ) -> Result<T, BxesReadError> {
+        let mut reader = BinaryReader::new(&mut self.stream, Endian::Little);
+        reader_func(&mut reader, &self.values, &self.kv_pairs)
+    }
+}
+
+pub struct BxesTraceVariantsIterator {
+    log: BxesLazyEventLog,
+    remaining_variants: u32,
+}
+
+impl Iterator for BxesTraceVariantsIterator {
+    type Item = Result<BxesTraceVariant, BxesReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining_variants == 0 {
+            return None;
+        }
+
+        let version = self.log.version;
+        let checksum = self.log.checksum;
+        let variant_index = (self.log.variants_count - self.remaining_variants) as usize;
+        let variant = self
+            .log
+            .execute_with_reader(|reader, values, kv_pairs| {
+                try_read_trace_variant(reader, version, checksum, values, kv_pairs)
+            })
+            .map_err(|err| err.located(BxesErrorLocation::variant(variant_index)));
+
+        self.remaining_variants = if variant.is_ok() {
+            self.remaining_variants - 1
+        } else {
+            0
+        };
+
+        Some(variant)
+    }
+}
+
+pub struct BxesLazyLogItemsIterator {
+    log: BxesLazyEventLog,
+    remaining_variants: u32,
+    events_count: u32,
+    remaining_events: u32,
+    last_timestamp: Option<i64>,
+    variant_offset: usize,
+    checksum: Option<u32>,
+}
+
+impl Iterator for BxesLazyLogItemsIterator {
+    type Item = Result<BxesLazyLogItem, BxesReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let version = self.log.version;
+        let item = if self.remaining_events > 0 {
+            let location = BxesErrorLocation {
+                section: Some(BxesSection::Variants),
+                variant_index: Some(self.current_variant_index()),
+                event_index: Some((self.events_count - self.remaining_events) as usize),
+                ..BxesErrorLocation::default()
+            };
+
+            self.remaining_events -= 1;
+            let previous_timestamp = self.last_timestamp;
+            let checksum = self.checksum;
+
+            self.log
+                .execute_with_reader(|reader, values, kv_pairs| {
+                    if let Some(checksum) = checksum {
+                        reader.start_checksum(checksum);
+                    }
+
+                    let event =
+                        try_read_event(reader, version, previous_timestamp, values, kv_pairs)?;
+
+                    Ok((event, reader.stop_checksum()))
+                })
+                .and_then(|(event, checksum)| {
+                    self.checksum = checksum;
+                    if self.remaining_events == 0 {
+                        self.try_verify_variant_checksum()?;
+                    }
+
+                    self.last_timestamp = Some(event.timestamp);
+                    Ok(BxesLazyLogItem::Event(event))
+                })
+                .map_err(|err| err.located(location))
+        } else if self.remaining_variants > 0 {
+            self.remaining_variants -= 1;
+            let location = BxesErrorLocation::variant(self.current_variant_index());
+            let checksummed = self.log.checksum != BxesChecksum::None;
+
+            self.log
+                .execute_with_reader(|reader, values, kv_pairs| {
+                    let offset = try_tell(reader)?;
+                    if checksummed {
+                        reader.start_checksum(0);
+                    }
+
+                    let variant_start =
+                        try_read_trace_variant_start(reader, version, values, kv_pairs)?;
+
+                    Ok((offset, reader.stop_checksum(), variant_start))
+                })
+                .and_then(|(offset, checksum, (variant_start, events_count))| {
+                    self.variant_offset = offset;
+                    self.checksum = checksum;
+                    if events_count == 0 {
+                        self.try_verify_variant_checksum()?;
+                    }
+
+                    self.events_count = events_count;
+                    self.remaining_events = events_count;
+                    self.last_timestamp = None;
+                    Ok(BxesLazyLogItem::TraceVariantStart(variant_start))
+                })
+                .map_err(|err| err.located(location))
+        } else {
+            return None;
+        };
+
+        if item.is_err() {
+            self.remaining_variants = 0;
+            self.remaining_events = 0;
+        }
+
+        Some(item)
+    }
+}
+
+impl BxesLazyLogItemsIterator {
+    fn current_variant_index(&self) -> usize {
+        (self.log.variants_count - self.remaining_variants - 1) as usize
+    }
+
+    /// Verifies the checksum of the current variant after its last event is read.
+    fn try_verify_variant_checksum(&mut self) -> Result<(), BxesReadError> {
+        let offset = self.variant_offset;
+        match self.checksum.take() {
+            Some(checksum) => self.log.execute_with_reader(|reader, _, _| {
+                try_verify_checksum(reader, BxesSection::Variants, offset, checksum)
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn try_tell(reader: &mut BinaryReader) -> Result<usize, BxesReadError> {
+    match reader.tell() {
+        Ok(offset) => Ok(offset),
+        Err(err) => Err(BxesReadError::FailedToReadPos(err)),
+    }
+}
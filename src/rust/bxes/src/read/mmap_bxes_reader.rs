@@ -0,0 +1,598 @@
+use std::{borrow::Cow, fs::File, path::Path, sync::Arc};
+
+use memmap2::Mmap;
+use num_traits::FromPrimitive;
+
+use crate::{
+    binary_rw::{
+        core::{BinaryReader, Endian, SeekStream},
+        error::BinaryError,
+    },
+    constants::*,
+    models::*,
+    type_ids::TypeIds,
+    utils::memory_stream::MemoryReadStream,
+};
+
+use super::{
+    errors::{BxesErrorLocation, BxesReadError, BxesSection, FailedToReadValueError},
+    read_utils::*,
+};
+
+/// Memory-mapped files of an uncompressed log, either a single-file log written
+/// without compression or a directory of a multiple-files log.
+pub struct BxesMappedLogFiles {
+    files: MappedFiles,
+}
+
+enum MappedFiles {
+    Single(Mmap),
+    Multiple {
+        values: Mmap,
+        kv_pairs: Mmap,
+        metadata: Mmap,
+        variants: Mmap,
+    },
+}
+
+impl BxesMappedLogFiles {
+    pub fn open(path: &str) -> Result<Self, BxesReadError> {
+        let mut file = try_open_file(Path::new(path))?;
+        if try_detect_container(&mut file)? != BxesContainer::Raw {
+            return Err(BxesReadError::InvalidArchive(
+                "Only uncompressed logs can be memory-mapped".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            files: MappedFiles::Single(try_map_file(&file, Path::new(path))?),
+        })
+    }
+
+    pub fn open_multiple_files(directory_path: &str) -> Result<Self, BxesReadError> {
+        let map = |file_name: &str| -> Result<Mmap, BxesReadError> {
+            let path = Path::new(directory_path).join(file_name);
+            try_map_file(&try_open_file(&path)?, &path)
+        };
+
+        Ok(Self {
+            files: MappedFiles::Multiple {
+                values: map(VALUES_FILE_NAME)?,
+                kv_pairs: map(KEY_VALUES_FILE_NAME)?,
+                metadata: map(METADATA_FILE_NAME)?,
+                variants: map(VARIANTS_FILE_NAME)?,
+            },
+        })
+    }
+
+    /// Reads values and key-value pairs, string values are borrowed from the mapped files.
+    pub fn read(&self) -> Result<BxesMappedEventLog<'_>, BxesReadError> {
+        match &self.files {
+            MappedFiles::Single(bytes) => read_single_file(bytes),
+            MappedFiles::Multiple {
+                values,
+                kv_pairs,
+                metadata,
+                variants,
+            } => read_multiple_files(values, kv_pairs, metadata, variants),
+        }
+    }
+}
+
+/// A value of a memory-mapped log, strings are not copied from the mapped file
+/// unless they are front-coded and share a prefix with the previous string.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BxesValueRef<'a> {
+    String(Cow<'a, str>),
+    Value(BxesValue),
+}
+
+impl<'a> BxesValueRef<'a> {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            BxesValueRef::String(string) => Some(string),
+            BxesValueRef::Value(_) => None,
+        }
+    }
+
+    pub fn to_value(&self) -> BxesValue {
+        match self {
+            BxesValueRef::String(string) => BxesValue::String(Arc::from(string.as_ref())),
+            BxesValueRef::Value(value) => value.clone(),
+        }
+    }
+}
+
+/// An event whose name and attributes are indices of values and key-value pairs of the log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BxesEventRef {
+    pub name_index: u32,
+    pub timestamp: i64,
+    pub lifecycle: Lifecycle,
+    pub attributes: Vec<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BxesTraceVariantRef {
+    pub traces_count: u32,
+    pub metadata: Vec<u32>,
+    pub events: Vec<BxesEventRef>,
+}
+
+pub struct BxesMappedEventLog<'a> {
+    pub version: u32,
+    pub string_encoding: BxesStringEncoding,
+    pub checksum: BxesChecksum,
+    values: Vec<BxesValueRef<'a>>,
+    kv_pairs: Vec<(u32, u32)>,
+    metadata: &'a [u8],
+    variants: &'a [u8],
+    variants_count: u32,
+}
+
+impl<'a> BxesMappedEventLog<'a> {
+    pub fn values(&self) -> &[BxesValueRef<'a>] {
+        &self.values
+    }
+
+    pub fn kv_pairs(&self) -> &[(u32, u32)] {
+        &self.kv_pairs
+    }
+
+    pub fn value(&self, index: u32) -> Result<&BxesValueRef<'a>, BxesReadError> {
+        match self.values.get(index as usize) {
+            Some(value) => Ok(value),
+            None => Err(BxesReadError::FailedToIndexValue(index as usize)),
+        }
+    }
+
+    pub fn kv_pair(
+        &self,
+        index: u32,
+    ) -> Result<(&BxesValueRef<'a>, &BxesValueRef<'a>), BxesReadError> {
+        match self.kv_pairs.get(index as usize) {
+            Some((key, value)) => Ok((self.value(*key)?, self.value(*value)?)),
+            None => Err(BxesReadError::FailedToIndexKeyValue(index as usize)),
+        }
+    }
+
+    /// Reads the log metadata, unlike the rest of the log it is built from owned values.
+    pub fn metadata(&self) -> Result<BxesEventLogMetadata, BxesReadError> {
+        let values = self
+            .values
+            .iter()
+            .map(|value| Arc::new(value.to_value()))
+            .collect();
+
+        let mut stream = MemoryReadStream::new(self.metadata);
+        let mut reader = BinaryReader::new(&mut stream, Endian::Little);
+
+        try_read_event_log_metadata(&mut reader, self.version, &values, &self.kv_pairs)
+    }
+
+    pub fn variants_count(&self) -> u32 {
+        self.variants_count
+    }
+
+    pub fn variants(&self) -> BxesMappedVariantsIterator<'a> {
+        BxesMappedVariantsIterator {
+            stream: MemoryReadStream::new(self.variants),
+            version: self.version,
+            checksum: self.checksum,
+            variant_index: 0,
+            remaining_variants: self.variants_count,
+        }
+    }
+}
+
+pub struct BxesMappedVariantsIterator<'a> {
+    stream: MemoryReadStream<'a>,
+    version: u32,
+    checksum: BxesChecksum,
+    variant_index: usize,
+    remaining_variants: u32,
+}
+
+impl<'a> Iterator for BxesMappedVariantsIterator<'a> {
+    type Item = Result<BxesTraceVariantRef, BxesReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining_variants == 0 {
+            return None;
+        }
+
+        let version = self.version;
+        let mut reader = BinaryReader::new(&mut self.stream, Endian::Little);
+        let variant = try_read_checksummed(
+            &mut reader,
+            self.checksum,
+            BxesSection::Variants,
+            |reader| try_read_trace_variant_ref(reader, version),
+        )
+        .map_err(|err| err.located(BxesErrorLocation::variant(self.variant_index)));
+
+        self.variant_index += 1;
+        self.remaining_variants = if variant.is_ok() {
+            self.remaining_variants - 1
+        } else {
+            0
+        };
+
+        Some(variant)
+    }
+}
+
+fn read_single_file(bytes: &[u8]) -> Result<BxesMappedEventLog<'_>, BxesReadError> {
+    let mut stream = MemoryReadStream::new(bytes);
+    let mut reader = BinaryReader::new(&mut stream, Endian::Little);
+
+    let version = try_read_version(&mut reader)?;
+    let string_encoding = try_read_string_encoding(&mut reader, version)?;
+    let checksum = try_read_checksum_kind(&mut reader, version)?;
+    let values = try_read_values_refs(&mut reader, version, string_encoding, checksum, bytes)?;
+    let kv_pairs = try_read_checksummed(&mut reader, checksum, BxesSection::KeyValues, |reader| {
+        try_read_key_values(reader, version)
+    })?;
+
+    let (metadata_start, metadata_end) = try_skip_metadata(&mut reader, version, checksum)?;
+
+    let variants_count = try_read_variants_count(&mut reader, version, checksum)?;
+    let variants_start = try_tell(&mut reader)?;
+
+    Ok(BxesMappedEventLog {
+        version,
+        string_encoding,
+        checksum,
+        values,
+        kv_pairs,
+        metadata: &bytes[metadata_start..metadata_end],
+        variants: &bytes[variants_start..],
+        variants_count,
+    })
+}
+
+fn read_multiple_files<'a>(
+    values: &'a [u8],
+    kv_pairs: &'a [u8],
+    metadata: &'a [u8],
+    variants: &'a [u8],
+) -> Result<BxesMappedEventLog<'a>, BxesReadError> {
+    let mut values_stream = MemoryReadStream::new(values);
+    let mut reader = BinaryReader::new(&mut values_stream, Endian::Little);
+    let version = try_read_version(&mut reader)?;
+    let string_encoding = try_read_string_encoding(&mut reader, version)?;
+    let checksum = try_read_checksum_kind(&mut reader, version)?;
+    let values = try_read_values_refs(&mut reader, version, string_encoding, checksum, values)?;
+
+    let mut kv_pairs_stream = MemoryReadStream::new(kv_pairs);
+    let mut reader = BinaryReader::new(&mut kv_pairs_stream, Endian::Little);
+    try_read_header_matching(&mut reader, version, string_encoding, checksum)?;
+    let kv_pairs = try_read_checksummed(&mut reader, checksum, BxesSection::KeyValues, |reader| {
+        try_read_key_values(reader, version)
+    })?;
+
+    let mut metadata_stream = MemoryReadStream::new(metadata);
+    let mut reader = BinaryReader::new(&mut metadata_stream, Endian::Little);
+    try_read_header_matching(&mut reader, version, string_encoding, checksum)?;
+    let (metadata_start, metadata_end) = try_skip_metadata(&mut reader, version, checksum)?;
+
+    let mut variants_stream = MemoryReadStream::new(variants);
+    let mut reader = BinaryReader::new(&mut variants_stream, Endian::Little);
+    try_read_header_matching(&mut reader, version, string_encoding, checksum)?;
+    let variants_count = try_read_variants_count(&mut reader, version, checksum)?;
+    let variants_start = try_tell(&mut reader)?;
+
+    Ok(BxesMappedEventLog {
+        version,
+        string_encoding,
+        checksum,
+        values,
+        kv_pairs,
+        metadata: &metadata[metadata_start..metadata_end],
+        variants: &variants[variants_start..],
+        variants_count,
+    })
+}
+
+/// Skips the metadata and verifies its checksum, returns the range of the metadata.
+fn try_skip_metadata(
+    reader: &mut BinaryReader,
+    version: u32,
+    checksum: BxesChecksum,
+) -> Result<(usize, usize), BxesReadError> {
+    let metadata_start = try_tell(reader)?;
+    let metadata_end = try_read_checksummed(reader, checksum, BxesSection::Metadata, |reader| {
+        try_skip_event_log_metadata(reader, version)?;
+        try_tell(reader)
+    })?;
+
+    Ok((metadata_start, metadata_end))
+}
+
+/// Strings are skipped by seeking, so the checksum of values is computed from the mapped bytes.
+fn try_read_values_refs<'a>(
+    reader: &mut BinaryReader,
+    version: u32,
+    string_encoding: BxesStringEncoding,
+    checksum: BxesChecksum,
+    bytes: &'a [u8],
+) -> Result<Vec<BxesValueRef<'a>>, BxesReadError> {
+    let section_offset = try_tell(reader)?;
+    let values_count = try_read_count(reader, version)?;
+    let mut values: Vec<BxesValueRef> = vec![];
+    let mut previous_string_index: Option<usize> = None;
+
+    for index in 0..values_count as usize {
+        let offset = try_tell(reader)?;
+        let previous_string = previous_string_index.and_then(|index| values[index].as_str());
+
+        let value = try_read_value_ref(
+            reader,
+            version,
+            string_encoding,
+            bytes,
+            &values,
+            previous_string,
+        );
+
+        match value {
+            Ok(value) => {
+                if let BxesValueRef::String(_) = value {
+                    previous_string_index = Some(index);
+                }
+
+                values.push(value)
+            }
+            Err(err) => {
+                return Err(err.located(BxesErrorLocation::section(BxesSection::Values, offset)))
+            }
+        }
+    }
+
+    if checksum != BxesChecksum::None {
+        let actual_checksum = crc32c::crc32c(&bytes[section_offset..try_tell(reader)?]);
+        try_verify_checksum(reader, BxesSection::Values, section_offset, actual_checksum)?;
+    }
+
+    Ok(values)
+}
+
+fn try_read_value_ref<'a>(
+    reader: &mut BinaryReader,
+    version: u32,
+    string_encoding: BxesStringEncoding,
+    bytes: &'a [u8],
+    values: &[BxesValueRef<'a>],
+    previous_string: Option<&str>,
+) -> Result<BxesValueRef<'a>, BxesReadError> {
+    let type_id_position = try_tell(reader)?;
+    let type_id_byte = try_read_u8(reader)?;
+
+    match TypeIds::from_u8(type_id_byte) {
+        Some(TypeIds::String) if string_encoding == BxesStringEncoding::FrontCoded => {
+            let prefix_offset = try_tell(reader)?;
+            let prefix_length = try_read_leb128_u64(reader)?;
+            let previous_string = previous_string.unwrap_or_default().as_bytes();
+
+            let prefix = match previous_string.get(..prefix_length as usize) {
+                Some(prefix) => prefix,
+                None => {
+                    return Err(BxesReadError::InvalidSharedPrefixLength {
+                        length: prefix_length,
+                        offset: prefix_offset,
+                    })
+                }
+            };
+
+            let (offset, suffix) = try_read_string_bytes(reader, version, bytes)?;
+            if prefix.is_empty() {
+                return Ok(BxesValueRef::String(Cow::Borrowed(try_create_str(
+                    offset, suffix,
+                )?)));
+            }
+
+            let string = [prefix, suffix].concat();
+            let string = try_create_str(offset, &string)?.to_owned();
+
+            Ok(BxesValueRef::String(Cow::Owned(string)))
+        }
+        Some(TypeIds::String) => {
+            let (offset, string) = try_read_string_bytes(reader, version, bytes)?;
+            Ok(BxesValueRef::String(Cow::Borrowed(try_create_str(
+                offset, string,
+            )?)))
+        }
+        Some(TypeIds::Artifact) => {
+            let items_count = try_read_compact_u32(reader, version)?;
+            let mut items = vec![];
+
+            for _ in 0..items_count {
+                items.push(BxesArtifactItem {
+                    model: try_get_owned_value(values, try_read_compact_u32(reader, version)?)?,
+                    instance: try_get_owned_value(values, try_read_compact_u32(reader, version)?)?,
+                    transition: try_get_owned_value(
+                        values,
+                        try_read_compact_u32(reader, version)?,
+                    )?,
+                });
+            }
+
+            Ok(BxesValueRef::Value(BxesValue::Artifact(BxesArtifact {
+                items,
+            })))
+        }
+        Some(TypeIds::Drivers) => {
+            let drivers_count = try_read_compact_u32(reader, version)?;
+            let mut drivers = vec![];
+
+            for _ in 0..drivers_count {
+                drivers.push(BxesDriver {
+                    amount: BxesValue::Float64(try_read_f64(reader)?),
+                    name: try_get_owned_value(values, try_read_compact_u32(reader, version)?)?,
+                    driver_type: try_get_owned_value(
+                        values,
+                        try_read_compact_u32(reader, version)?,
+                    )?,
+                });
+            }
+
+            Ok(BxesValueRef::Value(BxesValue::Drivers(BxesDrivers {
+                drivers,
+            })))
+        }
+        _ => {
+            try_seek(reader, type_id_position)?;
+            Ok(BxesValueRef::Value(try_read_bxes_value(
+                reader,
+                version,
+                string_encoding,
+                &Vec::new(),
+                None,
+            )?))
+        }
+    }
+}
+
+/// Reads the length and the bytes of the string which are borrowed from the mapped file,
+/// returns the offset of the bytes along with them.
+fn try_read_string_bytes<'a>(
+    reader: &mut BinaryReader,
+    version: u32,
+    bytes: &'a [u8],
+) -> Result<(usize, &'a [u8]), BxesReadError> {
+    let length = if version >= COMPACT_LENGTHS_VERSION {
+        try_read_leb128_u64(reader)?
+    } else {
+        try_read_u64(reader)?
+    } as usize;
+
+    let start = try_tell(reader)?;
+
+    let end = start.checked_add(length);
+    let string_bytes = match end.and_then(|end| bytes.get(start..end)) {
+        Some(string_bytes) => string_bytes,
+        None => return Err(read_value_error(start, BinaryError::ReadPastEof)),
+    };
+
+    try_seek(reader, start + string_bytes.len())?;
+    Ok((start, string_bytes))
+}
+
+fn try_create_str(offset: usize, bytes: &[u8]) -> Result<&str, BxesReadError> {
+    match std::str::from_utf8(bytes) {
+        Ok(string) => Ok(string),
+        Err(err) => Err(BxesReadError::FailedToCreateUtf8String {
+            offset,
+            source: err,
+        }),
+    }
+}
+
+fn try_get_owned_value(
+    values: &[BxesValueRef],
+    index: u32,
+) -> Result<Arc<BxesValue>, BxesReadError> {
+    match values.get(index as usize) {
+        Some(value) => Ok(Arc::new(value.to_value())),
+        None => Err(BxesReadError::FailedToIndexValue(index as usize)),
+    }
+}
+
+fn try_read_trace_variant_ref(
+    reader: &mut BinaryReader,
+    version: u32,
+) -> Result<BxesTraceVariantRef, BxesReadError> {
+    let traces_count = try_read_count(reader, version)?;
+
+    let metadata_count = try_read_compact_u32(reader, version)?;
+    let mut metadata = vec![];
+    for _ in 0..metadata_count {
+        metadata.push(try_read_compact_u32(reader, version)?);
+    }
+
+    let events_count = try_read_count(reader, version)?;
+    let mut events = vec![];
+    for index in 0..events_count as usize {
+        let offset = try_tell(reader)?;
+        let previous_timestamp = events.last().map(|event: &BxesEventRef| event.timestamp);
+
+        match try_read_event_ref(reader, version, previous_timestamp) {
+            Ok(event) => events.push(event),
+            Err(err) => return Err(err.located(BxesErrorLocation::event(offset, index))),
+        }
+    }
+
+    Ok(BxesTraceVariantRef {
+        traces_count,
+        metadata,
+        events,
+    })
+}
+
+fn try_read_event_ref(
+    reader: &mut BinaryReader,
+    version: u32,
+    previous_timestamp: Option<i64>,
+) -> Result<BxesEventRef, BxesReadError> {
+    let name_index = try_read_leb128(reader)?;
+    let timestamp = try_read_timestamp(reader, version, previous_timestamp)?;
+    let lifecycle = if version >= EVENT_LIFECYCLE_VERSION {
+        try_read_lifecycle(reader)?
+    } else {
+        Lifecycle::default()
+    };
+
+    let attributes_count = try_read_leb128(reader)?;
+    let mut attributes = vec![];
+    for _ in 0..attributes_count {
+        attributes.push(try_read_leb128(reader)?);
+    }
+
+    Ok(BxesEventRef {
+        name_index,
+        timestamp,
+        lifecycle,
+        attributes,
+    })
+}
+
+fn try_open_file(path: &Path) -> Result<File, BxesReadError> {
+    match File::open(path) {
+        Ok(file) => Ok(file),
+        Err(err) => Err(open_file_error(path, err)),
+    }
+}
+
+fn try_map_file(file: &File, path: &Path) -> Result<Mmap, BxesReadError> {
+    // SAFETY: the log files must not be modified while they are mapped,
+    // the same requirement holds for all other readers of a log.
+    match unsafe { Mmap::map(file) } {
+        Ok(map) => Ok(map),
+        Err(err) => Err(open_file_error(path, err)),
+    }
+}
+
+fn open_file_error(path: &Path, err: std::io::Error) -> BxesReadError {
+    BxesReadError::FailedToOpenFile {
+        path: path.to_string_lossy().into_owned(),
+        source: err.into(),
+    }
+}
+
+fn try_tell(reader: &mut BinaryReader) -> Result<usize, BxesReadError> {
+    match reader.tell() {
+        Ok(position) => Ok(position),
+        Err(err) => Err(BxesReadError::FailedToReadPos(err)),
+    }
+}
+
+fn try_seek(reader: &mut BinaryReader, position: usize) -> Result<(), BxesReadError> {
+    match reader.seek(position) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(BxesReadError::FailedToReadPos(err)),
+    }
+}
+
+fn read_value_error(offset: usize, err: BinaryError) -> BxesReadError {
+    BxesReadError::FailedToReadValue(FailedToReadValueError::new(offset, err))
+}
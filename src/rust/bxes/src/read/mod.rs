@@ -1,4 +1,9 @@
+pub mod bxes_validator;
 pub mod errors;
+pub mod lazy_bxes_reader;
+pub mod mmap_bxes_reader;
 pub mod multiple_files_bxes_reader;
+pub mod parallel_bxes_reader;
+pub mod random_access_bxes_reader;
 pub mod read_utils;
 pub mod single_file_bxes_reader;
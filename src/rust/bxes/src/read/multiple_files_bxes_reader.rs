@@ -6,61 +6,59 @@ use crate::{
     models::*,
 };
 
-use super::{errors::*, read_utils::*};
+use super::{errors::*, lazy_bxes_reader::BxesLazyEventLog, read_utils::*};
 
 pub fn read_bxes_multiple_files(directory_path: &str) -> Result<BxesEventLog, BxesReadError> {
+    read_bxes_multiple_files_lazy(directory_path)?.into_event_log()
+}
+
+pub fn read_bxes_multiple_files_lazy(
+    directory_path: &str,
+) -> Result<BxesLazyEventLog, BxesReadError> {
     let mut version = 0u32;
+    let mut string_encoding = BxesStringEncoding::Plain;
+    let mut checksum = BxesChecksum::None;
     let values = read_file(directory_path, VALUES_FILE_NAME, |reader| {
-        version = try_read_u32(reader)?;
-        try_read_values(reader)
+        version = try_read_version(reader)?;
+        string_encoding = try_read_string_encoding(reader, version)?;
+        checksum = try_read_checksum_kind(reader, version)?;
+        try_read_checksummed(reader, checksum, BxesSection::Values, |reader| {
+            try_read_values(reader, version, string_encoding)
+        })
     })?;
 
     let kv_pairs = read_file(directory_path, KEY_VALUES_FILE_NAME, |reader| {
-        if let Some(error) = read_version(&mut version, reader) {
-            return Err(error);
-        }
-
-        try_read_key_values(reader)
+        try_read_header_matching(reader, version, string_encoding, checksum)?;
+        try_read_checksummed(reader, checksum, BxesSection::KeyValues, |reader| {
+            try_read_key_values(reader, version)
+        })
     })?;
 
     let metadata = read_file(directory_path, METADATA_FILE_NAME, |reader| {
-        if let Some(error) = read_version(&mut version, reader) {
-            return Err(error);
-        }
-
-        try_read_event_log_metadata(reader, &values, &kv_pairs)
+        try_read_header_matching(reader, version, string_encoding, checksum)?;
+        try_read_checksummed(reader, checksum, BxesSection::Metadata, |reader| {
+            try_read_event_log_metadata(reader, version, &values, &kv_pairs)
+        })
     })?;
 
-    let variants = read_file(directory_path, VARIANTS_FILE_NAME, |reader| {
-        if let Some(error) = read_version(&mut version, reader) {
-            return Err(error);
-        }
-
-        try_read_traces_variants(reader, &values, &kv_pairs)
-    })?;
+    let variants_file_path = Path::new(directory_path).join(VARIANTS_FILE_NAME);
+    let mut stream = try_open_file_stream(variants_file_path.to_str().unwrap())?;
+    try_read_header_matching(
+        &mut BinaryReader::new(&mut stream, Endian::Little),
+        version,
+        string_encoding,
+        checksum,
+    )?;
 
-    Ok(BxesEventLog {
+    BxesLazyEventLog::new(
         version,
+        string_encoding,
+        checksum,
         metadata,
-        variants,
-    })
-}
-
-fn read_version(previous_version: &mut u32, reader: &mut BinaryReader) -> Option<BxesReadError> {
-    let current_version = try_read_u32(reader);
-    if current_version.is_err() {
-        return Some(current_version.err().unwrap());
-    }
-
-    let current_version = current_version.ok().unwrap();
-    if *previous_version != current_version {
-        Some(BxesReadError::VersionsMismatchError(
-            VersionsMismatchError::new(*previous_version, current_version),
-        ))
-    } else {
-        *previous_version = current_version;
-        None
-    }
+        values,
+        kv_pairs,
+        Box::new(stream),
+    )
 }
 
 fn read_file<T>(
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use rayon::prelude::*;
+
+use crate::{
+    binary_rw::core::{BinaryReader, Endian, SeekStream},
+    models::{BxesChecksum, BxesEventLog, BxesTraceVariant, BxesValue},
+    utils::memory_stream::MemoryReadStream,
+};
+
+use super::{
+    errors::{BxesErrorLocation, BxesReadError},
+    multiple_files_bxes_reader::read_bxes_multiple_files_lazy,
+    read_utils::{try_read_trace_variant, try_skip_trace_variant},
+    single_file_bxes_reader::read_bxes_lazy,
+};
+
+/// Same as `read_bxes`, but trace variants are decoded on the rayon thread pool.
+pub fn read_bxes_parallel(path: &str) -> Result<BxesEventLog, BxesReadError> {
+    read_bxes_lazy(path)?.into_event_log_parallel()
+}
+
+/// Same as `read_bxes_multiple_files`, but trace variants are decoded on the rayon thread pool.
+pub fn read_bxes_multiple_files_parallel(
+    directory_path: &str,
+) -> Result<BxesEventLog, BxesReadError> {
+    read_bxes_multiple_files_lazy(directory_path)?.into_event_log_parallel()
+}
+
+/// Decodes `variants_count` trace variants of the given format version
+/// which are stored one after another in `bytes`, starting at `base_offset` in the log stream.
+/// Variants boundaries are found by a sequential scan which does not resolve values,
+/// then variants are decoded independently.
+pub fn try_read_traces_variants_parallel(
+    bytes: &[u8],
+    base_offset: usize,
+    version: u32,
+    checksum: BxesChecksum,
+    variants_count: u32,
+    values: &Vec<Arc<BxesValue>>,
+    kv_pairs: &Vec<(u32, u32)>,
+) -> Result<Vec<BxesTraceVariant>, BxesReadError> {
+    let offsets = try_find_variants_offsets(bytes, base_offset, version, checksum, variants_count)?;
+
+    offsets
+        .par_iter()
+        .enumerate()
+        .map(|(index, offset)| {
+            let variant_bytes = &bytes[*offset - base_offset..];
+            let mut stream = MemoryReadStream::with_base_offset(variant_bytes, *offset);
+            let mut reader = BinaryReader::new(&mut stream, Endian::Little);
+
+            try_read_trace_variant(&mut reader, version, checksum, values, kv_pairs)
+                .map_err(|err| err.located(BxesErrorLocation::variant(index)))
+        })
+        .collect()
+}
+
+fn try_find_variants_offsets(
+    bytes: &[u8],
+    base_offset: usize,
+    version: u32,
+    checksum: BxesChecksum,
+    variants_count: u32,
+) -> Result<Vec<usize>, BxesReadError> {
+    let mut stream = MemoryReadStream::with_base_offset(bytes, base_offset);
+    let mut reader = BinaryReader::new(&mut stream, Endian::Little);
+    let mut offsets = vec![];
+
+    for index in 0..variants_count as usize {
+        let offset = match reader.tell() {
+            Ok(offset) => offset,
+            Err(err) => return Err(BxesReadError::FailedToReadPos(err)),
+        };
+
+        offsets.push(offset);
+        if let Err(err) = try_skip_trace_variant(&mut reader, version, checksum) {
+            return Err(err.located(BxesErrorLocation {
+                offset: Some(offset),
+                ..BxesErrorLocation::variant(index)
+            }));
+        }
+    }
+
+    Ok(offsets)
+}
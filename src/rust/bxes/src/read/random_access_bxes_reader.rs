@@ -0,0 +1,135 @@
+use crate::{
+    binary_rw::core::{BinaryReader, Endian, ReadStream, SeekStream},
+    constants::{VARIANTS_INDEX_ENTRY_SIZE, VARIANTS_INDEX_MARKER, VARIANTS_INDEX_TRAILER_SIZE},
+    models::*,
+};
+
+use super::{
+    errors::{BxesErrorLocation, BxesReadError, BxesSection},
+    lazy_bxes_reader::BxesLazyEventLog, read_utils::*,
+    single_file_bxes_reader::read_bxes_lazy_seekable,
+};
+
+/// Reads trace variants of a single-file log by their numbers using the variants index,
+/// only values, key-value pairs and metadata are read when the reader is opened.
+pub struct BxesRandomAccessReader {
+    log: BxesLazyEventLog,
+    index: Vec<BxesVariantIndexEntry>,
+}
+
+impl BxesRandomAccessReader {
+    pub fn open(path: &str) -> Result<Self, BxesReadError> {
+        let mut log = read_bxes_lazy_seekable(path)?;
+        let index = try_read_variants_index(&mut log.stream, log.variants_count, log.checksum)?;
+
+        Ok(Self { log, index })
+    }
+
+    pub fn version(&self) -> u32 {
+        self.log.version
+    }
+
+    pub fn metadata(&self) -> &BxesEventLogMetadata {
+        &self.log.metadata
+    }
+
+    pub fn variants_count(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn events_count(&self, variant_index: usize) -> Result<u32, BxesReadError> {
+        match self.index.get(variant_index) {
+            Some(entry) => Ok(entry.events_count),
+            None => Err(BxesReadError::VariantIndexOutOfRange(variant_index)),
+        }
+    }
+
+    pub fn read_variant(
+        &mut self,
+        variant_index: usize,
+    ) -> Result<BxesTraceVariant, BxesReadError> {
+        let offset = match self.index.get(variant_index) {
+            Some(entry) => entry.offset as usize,
+            None => return Err(BxesReadError::VariantIndexOutOfRange(variant_index)),
+        };
+
+        let mut reader = BinaryReader::new(&mut self.log.stream, Endian::Little);
+        try_seek(&mut reader, offset)?;
+
+        try_read_trace_variant(
+            &mut reader,
+            self.log.version,
+            self.log.checksum,
+            &self.log.values,
+            &self.log.kv_pairs,
+        )
+        .map_err(|err| err.located(BxesErrorLocation::variant(variant_index)))
+    }
+}
+
+/// The variants index is followed by its checksum when the log is written with checksums,
+/// the checksum covers entries of the index and its trailer.
+fn try_read_variants_index(
+    stream: &mut Box<dyn ReadStream + Send>,
+    variants_count: u32,
+    checksum: BxesChecksum,
+) -> Result<Vec<BxesVariantIndexEntry>, BxesReadError> {
+    let length = match stream.len() {
+        Ok(length) => length,
+        Err(err) => return Err(BxesReadError::FailedToReadPos(err)),
+    };
+
+    let trailer_size = VARIANTS_INDEX_TRAILER_SIZE + checksum.size();
+    if length < trailer_size {
+        return Err(BxesReadError::MissingVariantsIndex);
+    }
+
+    let mut reader = BinaryReader::new(stream, Endian::Little);
+    try_seek(&mut reader, length - trailer_size)?;
+
+    let index_offset = try_read_u64(&mut reader)? as usize;
+    if try_read_u32(&mut reader)? != VARIANTS_INDEX_MARKER || index_offset >= length {
+        return Err(BxesReadError::MissingVariantsIndex);
+    }
+
+    // Each entry is an offset (`u64`) and an events count (`u32`), followed by the number of entries.
+    let entries_size = variants_count as u64 * VARIANTS_INDEX_ENTRY_SIZE as u64;
+    let index_size = entries_size + std::mem::size_of::<u32>() as u64 + trailer_size as u64;
+    if index_size != (length - index_offset) as u64 {
+        return Err(BxesReadError::MissingVariantsIndex);
+    }
+
+    try_seek(&mut reader, index_offset)?;
+
+    try_read_checksummed(
+        &mut reader,
+        checksum,
+        BxesSection::VariantsIndex,
+        |reader| {
+            let mut index = Vec::with_capacity(variants_count as usize);
+            for _ in 0..variants_count {
+                index.push(BxesVariantIndexEntry {
+                    offset: try_read_u64(reader)?,
+                    events_count: try_read_u32(reader)?,
+                });
+            }
+
+            if try_read_u32(reader)? != variants_count {
+                return Err(BxesReadError::MissingVariantsIndex);
+            }
+
+            // The trailer was already read, it is read again to be covered by the checksum.
+            try_read_u64(reader)?;
+            try_read_u32(reader)?;
+
+            Ok(index)
+        },
+    )
+}
+
+fn try_seek(reader: &mut BinaryReader, offset: usize) -> Result<(), BxesReadError> {
+    match reader.seek(offset) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(BxesReadError::FailedToReadPos(err)),
+    }
+}
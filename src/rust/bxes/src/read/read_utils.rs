@@ -1,59 +1,273 @@
-use std::{fs::File, io::Read, rc::Rc};
+use std::{
+    fs::File,
+    io::{self, BufReader, Cursor, Read, Seek, SeekFrom},
+    sync::Arc,
+};
+
+use flate2::read::DeflateDecoder;
 
 use num_traits::FromPrimitive;
-use tempfile::TempDir;
 use uuid::Uuid;
-use zip::ZipArchive;
+use zip::{CompressionMethod, ZipArchive};
 
 use crate::{
     binary_rw::{
-        core::{BinaryReader, SeekStream},
+        core::{BinaryReader, ReadStream, SeekStream},
         file_stream::FileStream,
     },
+    constants::{
+        CHECKSUMS_VERSION, COMPACT_LENGTHS_VERSION, DELTA_TIMESTAMPS_VERSION,
+        EVENT_LIFECYCLE_VERSION, FRONT_CODED_STRINGS_VERSION, LZ4_MAGIC, SUPPORTED_VERSIONS,
+        ZIP_MAGIC, ZSTD_MAGIC,
+    },
     models::*,
     type_ids::TypeIds,
-    utils::buffered_stream::BufferedReadFileStream,
+    utils::{
+        buffered_stream::BufferedReadFileStream, forward_stream::ForwardReadStream,
+        std_stream::StdStream,
+    },
 };
 
 use super::errors::*;
 
+pub fn try_read_version(reader: &mut BinaryReader) -> Result<u32, BxesReadError> {
+    let version = try_read_u32(reader)?;
+    match SUPPORTED_VERSIONS.contains(&version) {
+        true => Ok(version),
+        false => Err(BxesReadError::UnsupportedVersion(version)),
+    }
+}
+
+/// Reads the version of one of the files of a multiple-files log,
+/// all files of a log must have the same version.
+pub fn try_read_version_matching(
+    reader: &mut BinaryReader,
+    expected_version: u32,
+) -> Result<(), BxesReadError> {
+    let version = try_read_version(reader)?;
+    match version == expected_version {
+        true => Ok(()),
+        false => Err(BxesReadError::VersionsMismatchError(
+            VersionsMismatchError::new(expected_version, version),
+        )),
+    }
+}
+
+/// Reads the encoding of strings which follows the version since `FRONT_CODED_STRINGS_VERSION`,
+/// strings of logs of previous versions are plain.
+pub fn try_read_string_encoding(
+    reader: &mut BinaryReader,
+    version: u32,
+) -> Result<BxesStringEncoding, BxesReadError> {
+    if version < FRONT_CODED_STRINGS_VERSION {
+        return Ok(BxesStringEncoding::Plain);
+    }
+
+    let string_encoding = try_read_u8(reader)?;
+    match BxesStringEncoding::from_u8(string_encoding) {
+        Some(string_encoding) => Ok(string_encoding),
+        None => Err(BxesReadError::UnsupportedStringEncoding(string_encoding)),
+    }
+}
+
+/// Reads the encoding of strings of one of the files of a multiple-files log,
+/// all files of a log must have the same encoding of strings.
+fn try_read_string_encoding_matching(
+    reader: &mut BinaryReader,
+    version: u32,
+    expected_string_encoding: BxesStringEncoding,
+) -> Result<(), BxesReadError> {
+    let string_encoding = try_read_string_encoding(reader, version)?;
+    match string_encoding == expected_string_encoding {
+        true => Ok(()),
+        false => Err(BxesReadError::StringEncodingsMismatch {
+            expected: expected_string_encoding,
+            actual: string_encoding,
+        }),
+    }
+}
+
+/// Reads the kind of checksums which follows the encoding of strings since `CHECKSUMS_VERSION`,
+/// logs of previous versions do not contain checksums.
+pub fn try_read_checksum_kind(
+    reader: &mut BinaryReader,
+    version: u32,
+) -> Result<BxesChecksum, BxesReadError> {
+    if version < CHECKSUMS_VERSION {
+        return Ok(BxesChecksum::None);
+    }
+
+    let checksum = try_read_u8(reader)?;
+    match BxesChecksum::from_u8(checksum) {
+        Some(checksum) => Ok(checksum),
+        None => Err(BxesReadError::UnsupportedChecksum(checksum)),
+    }
+}
+
+/// Reads the kind of checksums of one of the files of a multiple-files log,
+/// all files of a log must have the same kind of checksums.
+fn try_read_checksum_kind_matching(
+    reader: &mut BinaryReader,
+    version: u32,
+    expected_checksum: BxesChecksum,
+) -> Result<(), BxesReadError> {
+    let checksum = try_read_checksum_kind(reader, version)?;
+    match checksum == expected_checksum {
+        true => Ok(()),
+        false => Err(BxesReadError::ChecksumKindsMismatch {
+            expected: expected_checksum,
+            actual: checksum,
+        }),
+    }
+}
+
+/// Reads the version, the encoding of strings and the kind of checksums
+/// of one of the files of a multiple-files log.
+pub fn try_read_header_matching(
+    reader: &mut BinaryReader,
+    expected_version: u32,
+    expected_string_encoding: BxesStringEncoding,
+    expected_checksum: BxesChecksum,
+) -> Result<(), BxesReadError> {
+    try_read_version_matching(reader, expected_version)?;
+    try_read_string_encoding_matching(reader, expected_version, expected_string_encoding)?;
+    try_read_checksum_kind_matching(reader, expected_version, expected_checksum)
+}
+
+/// Reads the section and the checksum which follows it, the section starts at the current position.
+pub fn try_read_checksummed<T>(
+    reader: &mut BinaryReader,
+    checksum: BxesChecksum,
+    section: BxesSection,
+    read_func: impl FnOnce(&mut BinaryReader) -> Result<T, BxesReadError>,
+) -> Result<T, BxesReadError> {
+    if checksum == BxesChecksum::None {
+        return read_func(reader);
+    }
+
+    let offset = try_tell_pos(reader)?;
+    reader.start_checksum(0);
+    let result = read_func(reader);
+    let actual_checksum = reader.stop_checksum().unwrap_or_default();
+
+    let result = result?;
+    try_verify_checksum(reader, section, offset, actual_checksum)?;
+
+    Ok(result)
+}
+
+/// Reads the checksum of the section which starts at `offset` and compares it with the actual one.
+pub fn try_verify_checksum(
+    reader: &mut BinaryReader,
+    section: BxesSection,
+    offset: usize,
+    actual_checksum: u32,
+) -> Result<(), BxesReadError> {
+    match try_read_u32(reader)? == actual_checksum {
+        true => Ok(()),
+        false => Err(BxesReadError::ChecksumMismatch { section, offset }),
+    }
+}
+
+/// Reads a count of values, key-value pairs, variants, traces or events,
+/// padded and minimal LEB128 encodings are both accepted.
+pub fn try_read_count(reader: &mut BinaryReader, version: u32) -> Result<u32, BxesReadError> {
+    try_read_compact_u32(reader, version)
+}
+
+/// The number of variants does not belong to any section, so it is followed by its own checksum
+/// when the log is written with checksums.
+pub fn try_read_variants_count(
+    reader: &mut BinaryReader,
+    version: u32,
+    checksum: BxesChecksum,
+) -> Result<u32, BxesReadError> {
+    try_read_checksummed(reader, checksum, BxesSection::Variants, |reader| {
+        try_read_count(reader, version)
+    })
+}
+
 pub fn try_read_event_log_metadata(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
 ) -> Result<BxesEventLogMetadata, BxesReadError> {
-    let properties = try_read_attributes(reader, values, kv_pairs, false)?;
-    let extensions = try_read_extensions(reader, values, kv_pairs)?;
-    let globals = try_read_globals(reader, values, kv_pairs)?;
-    let classifiers = try_read_classifiers(reader, values, kv_pairs)?;
-
-    Ok(BxesEventLogMetadata {
-        extensions,
-        classifiers,
-        properties,
-        globals,
-    })
+    let offset = try_tell_pos(reader)?;
+    let compact = version >= COMPACT_LENGTHS_VERSION;
+    let read_metadata = |reader: &mut BinaryReader| -> Result<BxesEventLogMetadata, BxesReadError> {
+        let properties = try_read_attributes(reader, values, kv_pairs, compact)?;
+        let extensions = try_read_extensions(reader, version, values, kv_pairs)?;
+        let globals = try_read_globals(reader, version, values, kv_pairs)?;
+        let classifiers = try_read_classifiers(reader, version, values, kv_pairs)?;
+
+        Ok(BxesEventLogMetadata {
+            extensions,
+            classifiers,
+            properties,
+            globals,
+        })
+    };
+
+    read_metadata(reader)
+        .map_err(|err| err.located(BxesErrorLocation::section(BxesSection::Metadata, offset)))
+}
+
+/// Moves the reader to the end of the event log metadata without resolving values.
+pub fn try_skip_event_log_metadata(
+    reader: &mut BinaryReader,
+    version: u32,
+) -> Result<(), BxesReadError> {
+    let skip_u32s = |reader: &mut BinaryReader, count: u32| -> Result<(), BxesReadError> {
+        for _ in 0..count {
+            try_read_compact_u32(reader, version)?;
+        }
+
+        Ok(())
+    };
+
+    let properties_count = try_read_compact_u32(reader, version)?;
+    skip_u32s(reader, properties_count)?;
+
+    let extensions_count = try_read_compact_u32(reader, version)?;
+    skip_u32s(reader, extensions_count * 3)?;
+
+    let globals_count = try_read_compact_u32(reader, version)?;
+    for _ in 0..globals_count {
+        try_read_u8(reader)?;
+        let entity_globals_count = try_read_compact_u32(reader, version)?;
+        skip_u32s(reader, entity_globals_count)?;
+    }
+
+    let classifiers_count = try_read_compact_u32(reader, version)?;
+    for _ in 0..classifiers_count {
+        try_read_compact_u32(reader, version)?;
+        let keys_count = try_read_compact_u32(reader, version)?;
+        skip_u32s(reader, keys_count)?;
+    }
+
+    Ok(())
 }
 
 pub fn try_read_classifiers(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
 ) -> Result<Option<Vec<BxesClassifier>>, BxesReadError> {
-    let count = try_read_u32(reader)?;
+    let count = try_read_compact_u32(reader, version)?;
     if count == 0 {
         Ok(None)
     } else {
         let mut classifiers = vec![];
 
         for _ in 0..count {
-            let name = values.get(try_read_u32(reader)? as usize).unwrap().clone();
+            let name = try_get_value(values, try_read_compact_u32(reader, version)?)?;
 
-            let keys_count = try_read_u32(reader)?;
+            let keys_count = try_read_compact_u32(reader, version)?;
             let mut keys = vec![];
             for _ in 0..keys_count {
-                let key_value = values.get(try_read_u32(reader)? as usize).unwrap();
-                keys.push(key_value.clone());
+                keys.push(try_get_value(values, try_read_compact_u32(reader, version)?)?);
             }
 
             classifiers.push(BxesClassifier { name, keys });
@@ -65,22 +279,24 @@ pub fn try_read_classifiers(
 
 pub fn try_read_globals(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
 ) -> Result<Option<Vec<BxesGlobal>>, BxesReadError> {
-    let count = try_read_u32(reader)?;
+    let compact = version >= COMPACT_LENGTHS_VERSION;
+    let count = try_read_compact_u32(reader, version)?;
     if count == 0 {
         Ok(None)
     } else {
         let mut globals = vec![];
 
         for _ in 0..count {
-            let entity_kind = BxesGlobalKind::from_u8(try_read_u8(reader)?).unwrap();
-            let globals_count = try_read_u32(reader)?;
+            let entity_kind = try_read_enum::<BxesGlobalKind>(reader, type_id_out_of_range)?;
+            let globals_count = try_read_compact_u32(reader, version)?;
             let mut entity_globals = vec![];
 
             for _ in 0..globals_count {
-                entity_globals.push(try_read_kv_pair(reader, values, kv_pairs, false)?);
+                entity_globals.push(try_read_kv_pair(reader, values, kv_pairs, compact)?);
             }
 
             globals.push(BxesGlobal {
@@ -95,19 +311,20 @@ pub fn try_read_globals(
 
 pub fn try_read_extensions(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
 ) -> Result<Option<Vec<BxesExtension>>, BxesReadError> {
-    let count = try_read_u32(reader)?;
+    let count = try_read_compact_u32(reader, version)?;
     if count == 0 {
         Ok(None)
     } else {
         let mut extensions = vec![];
 
         for _ in 0..count {
-            let name = values.get(try_read_u32(reader)? as usize).unwrap().clone();
-            let prefix = values.get(try_read_u32(reader)? as usize).unwrap().clone();
-            let uri = values.get(try_read_u32(reader)? as usize).unwrap().clone();
+            let name = try_get_value(values, try_read_compact_u32(reader, version)?)?;
+            let prefix = try_get_value(values, try_read_compact_u32(reader, version)?)?;
+            let uri = try_get_value(values, try_read_compact_u32(reader, version)?)?;
 
             extensions.push(BxesExtension { name, prefix, uri })
         }
@@ -117,13 +334,51 @@ pub fn try_read_extensions(
 }
 
 pub fn try_read_leb128(reader: &mut BinaryReader) -> Result<u32, BxesReadError> {
+    Ok(try_read_leb128_u64(reader)? as u32)
+}
+
+/// Reads a count or an index which is LEB128 encoded since `COMPACT_LENGTHS_VERSION`
+/// and is a fixed-width `u32` in previous versions.
+pub fn try_read_compact_u32(reader: &mut BinaryReader, version: u32) -> Result<u32, BxesReadError> {
+    if version >= COMPACT_LENGTHS_VERSION {
+        try_read_leb128(reader)
+    } else {
+        try_read_u32(reader)
+    }
+}
+
+pub fn try_read_leb128_u64(reader: &mut BinaryReader) -> Result<u64, BxesReadError> {
+    let offset = try_tell_pos(reader)?;
     match leb128::read::unsigned(reader) {
-        Ok(value) => Ok(value as u32),
-        Err(err) => Err(BxesReadError::Leb128ReadError(err.to_string())),
+        Ok(value) => Ok(value),
+        Err(err) => Err(BxesReadError::Leb128ReadError {
+            offset,
+            source: err,
+        }),
     }
 }
 
-fn string_or_err(value: &BxesValue) -> Result<Rc<Box<String>>, BxesReadError> {
+/// Reads the timestamp of an event, `previous_timestamp` is the timestamp of the previous event
+/// of the trace variant, it is `None` for the first event.
+pub fn try_read_timestamp(
+    reader: &mut BinaryReader,
+    version: u32,
+    previous_timestamp: Option<i64>,
+) -> Result<i64, BxesReadError> {
+    match previous_timestamp {
+        Some(previous_timestamp) if version >= DELTA_TIMESTAMPS_VERSION => {
+            let delta = zigzag_decode(try_read_leb128_u64(reader)?);
+            Ok(previous_timestamp.wrapping_add(delta))
+        }
+        _ => try_read_i64(reader),
+    }
+}
+
+fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn string_or_err(value: &BxesValue) -> Result<Arc<str>, BxesReadError> {
     if let BxesValue::String(string) = value {
         Ok(string.clone())
     } else {
@@ -133,49 +388,140 @@ fn string_or_err(value: &BxesValue) -> Result<Rc<Box<String>>, BxesReadError> {
 
 pub fn try_read_traces_variants(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    checksum: BxesChecksum,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
 ) -> Result<Vec<BxesTraceVariant>, BxesReadError> {
     let mut variants = vec![];
-    let variant_count = try_read_u32(reader)?;
+    let variant_count = try_read_variants_count(reader, version, checksum)?;
 
-    for _ in 0..variant_count {
-        variants.push(try_read_trace_variant(reader, values, kv_pairs)?);
+    for index in 0..variant_count as usize {
+        match try_read_trace_variant(reader, version, checksum, values, kv_pairs) {
+            Ok(variant) => variants.push(variant),
+            Err(err) => return Err(err.located(BxesErrorLocation::variant(index))),
+        }
     }
 
     Ok(variants)
 }
 
-fn try_read_trace_variant(
+/// Errors of the variant are located at the start of the variant or at the failed event,
+/// the checksum of the variant is verified when checksums are written.
+pub fn try_read_trace_variant(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    checksum: BxesChecksum,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
 ) -> Result<BxesTraceVariant, BxesReadError> {
-    let traces_count = try_read_u32(reader)?;
+    try_read_checksummed(reader, checksum, BxesSection::Variants, |reader| {
+        try_read_trace_variant_contents(reader, version, values, kv_pairs)
+    })
+}
 
-    let mut variant_metadata = vec![];
-    let metadata_count = try_read_u32(reader)?;
-    for _ in 0..metadata_count {
-        variant_metadata.push(try_read_kv_pair(reader, values, kv_pairs, false)?);
-    }
+fn try_read_trace_variant_contents(
+    reader: &mut BinaryReader,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
+    kv_pairs: &Vec<(u32, u32)>,
+) -> Result<BxesTraceVariant, BxesReadError> {
+    let offset = try_tell_pos(reader)?;
+    let (variant_start, events_count) =
+        match try_read_trace_variant_start(reader, version, values, kv_pairs) {
+            Ok(variant_start) => variant_start,
+            Err(err) => {
+                return Err(err.located(BxesErrorLocation::section(BxesSection::Variants, offset)))
+            }
+        };
+
+    let mut events: Vec<BxesEvent> = vec![];
 
-    let events_count = try_read_u32(reader)?;
-    let mut events = vec![];
+    for index in 0..events_count as usize {
+        let offset = try_tell_pos(reader)?;
+        let previous_timestamp = events.last().map(|event| event.timestamp);
 
-    for _ in 0..events_count {
-        events.push(try_read_event(reader, values, kv_pairs)?);
+        match try_read_event(reader, version, previous_timestamp, values, kv_pairs) {
+            Ok(event) => events.push(event),
+            Err(err) => return Err(err.located(BxesErrorLocation::event(offset, index))),
+        }
     }
 
     Ok(BxesTraceVariant {
-        traces_count,
-        metadata: variant_metadata,
+        traces_count: variant_start.traces_count,
+        metadata: variant_start.metadata,
         events,
     })
 }
 
-fn try_read_event(
+pub fn try_read_trace_variant_start(
+    reader: &mut BinaryReader,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
+    kv_pairs: &Vec<(u32, u32)>,
+) -> Result<(BxesTraceVariantStart, u32), BxesReadError> {
+    let traces_count = try_read_count(reader, version)?;
+
+    let compact = version >= COMPACT_LENGTHS_VERSION;
+    let mut metadata = vec![];
+    let metadata_count = try_read_compact_u32(reader, version)?;
+    for _ in 0..metadata_count {
+        metadata.push(try_read_kv_pair(reader, values, kv_pairs, compact)?);
+    }
+
+    let events_count = try_read_count(reader, version)?;
+
+    Ok((
+        BxesTraceVariantStart {
+            traces_count,
+            metadata,
+        },
+        events_count,
+    ))
+}
+
+/// Moves the reader to the start of the next trace variant without
+/// resolving values and key-value pairs.
+pub fn try_skip_trace_variant(
+    reader: &mut BinaryReader,
+    version: u32,
+    checksum: BxesChecksum,
+) -> Result<(), BxesReadError> {
+    try_read_count(reader, version)?;
+
+    let metadata_count = try_read_compact_u32(reader, version)?;
+    for _ in 0..metadata_count {
+        try_read_compact_u32(reader, version)?;
+    }
+
+    let events_count = try_read_count(reader, version)?;
+    for index in 0..events_count {
+        try_read_leb128(reader)?;
+        try_read_timestamp(reader, version, (index > 0).then_some(0))?;
+
+        if version >= EVENT_LIFECYCLE_VERSION {
+            try_read_u8(reader)?;
+            try_read_u8(reader)?;
+        }
+
+        let attributes_count = try_read_leb128(reader)?;
+        for _ in 0..attributes_count {
+            try_read_leb128(reader)?;
+        }
+    }
+
+    if checksum != BxesChecksum::None {
+        try_read_u32(reader)?;
+    }
+
+    Ok(())
+}
+
+pub fn try_read_event(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    previous_timestamp: Option<i64>,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
 ) -> Result<BxesEvent, BxesReadError> {
     let name_index = try_read_leb128(reader)? as usize;
@@ -185,21 +531,27 @@ fn try_read_event(
         return Err(BxesReadError::FailedToIndexValue(name_index));
     }
 
-    let timestamp = try_read_i64(reader)?;
+    let timestamp = try_read_timestamp(reader, version, previous_timestamp)?;
+    let lifecycle = if version >= EVENT_LIFECYCLE_VERSION {
+        try_read_lifecycle(reader)?
+    } else {
+        Lifecycle::default()
+    };
 
     Ok(BxesEvent {
         name: name.unwrap().clone(),
         timestamp,
+        lifecycle,
         attributes: try_read_attributes(reader, values, kv_pairs, true)?,
     })
 }
 
 fn try_read_attributes(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
     leb_128: bool,
-) -> Result<Option<Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>>, BxesReadError> {
+) -> Result<Option<Vec<(Arc<BxesValue>, Arc<BxesValue>)>>, BxesReadError> {
     let attributes_count = if leb_128 {
         try_read_leb128(reader)?
     } else {
@@ -221,10 +573,10 @@ fn try_read_attributes(
 
 fn try_read_kv_pair(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    values: &Vec<Arc<BxesValue>>,
     kv_pairs: &Vec<(u32, u32)>,
     leb_128: bool,
-) -> Result<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>), BxesReadError> {
+) -> Result<(Arc<BxesValue>, Arc<BxesValue>), BxesReadError> {
     let kv_index = if leb_128 {
         try_read_leb128(reader)?
     } else {
@@ -251,12 +603,22 @@ fn try_read_kv_pair(
     Ok((key.clone(), value.clone()))
 }
 
-pub fn try_read_key_values(reader: &mut BinaryReader) -> Result<Vec<(u32, u32)>, BxesReadError> {
+pub fn try_read_key_values(
+    reader: &mut BinaryReader,
+    version: u32,
+) -> Result<Vec<(u32, u32)>, BxesReadError> {
     let mut key_values = vec![];
 
-    let key_values_count = try_read_u32(reader)?;
+    let key_values_count = try_read_count(reader, version)?;
     for _ in 0..key_values_count {
-        key_values.push((try_read_leb128(reader)?, try_read_leb128(reader)?));
+        let offset = try_tell_pos(reader)?;
+        let to_located = |err: BxesReadError| {
+            err.located(BxesErrorLocation::section(BxesSection::KeyValues, offset))
+        };
+
+        let key = try_read_leb128(reader).map_err(to_located)?;
+        let value = try_read_leb128(reader).map_err(to_located)?;
+        key_values.push((key, value));
     }
 
     Ok(key_values)
@@ -264,24 +626,48 @@ pub fn try_read_key_values(reader: &mut BinaryReader) -> Result<Vec<(u32, u32)>,
 
 pub fn try_read_values(
     reader: &mut BinaryReader,
-) -> Result<Vec<Rc<Box<BxesValue>>>, BxesReadError> {
+    version: u32,
+    string_encoding: BxesStringEncoding,
+) -> Result<Vec<Arc<BxesValue>>, BxesReadError> {
     let mut values = vec![];
+    let mut previous_string: Option<Arc<str>> = None;
 
-    let values_count = try_read_u32(reader)?;
+    let values_count = try_read_count(reader, version)?;
     for _ in 0..values_count {
-        values.push(Rc::new(Box::new(try_read_bxes_value(reader, &values)?)));
+        let offset = try_tell_pos(reader)?;
+        let previous = previous_string.as_deref();
+        match try_read_bxes_value(reader, version, string_encoding, &values, previous) {
+            Ok(value) => {
+                if let BxesValue::String(string) = &value {
+                    previous_string = Some(string.clone());
+                }
+
+                values.push(Arc::new(value))
+            }
+            Err(err) => {
+                return Err(err.located(BxesErrorLocation::section(BxesSection::Values, offset)))
+            }
+        }
     }
 
     Ok(values)
 }
 
-fn try_read_bxes_value(
+/// `previous_string` is the last string value of the values table before this value,
+/// front-coded strings are reconstructed from it.
+pub fn try_read_bxes_value(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    string_encoding: BxesStringEncoding,
+    values: &Vec<Arc<BxesValue>>,
+    previous_string: Option<&str>,
 ) -> Result<BxesValue, BxesReadError> {
+    let offset = try_tell_pos(reader)?;
+    let type_id_error = |type_id: u8| BxesReadError::FailedToParseTypeId { type_id, offset };
+
     let type_id_byte = try_read_u8(reader)?;
     let type_id = match TypeIds::from_u8(type_id_byte) {
-        None => return Err(BxesReadError::FailedToParseTypeId(type_id_byte)),
+        None => return Err(type_id_error(type_id_byte)),
         Some(id) => id,
     };
 
@@ -293,31 +679,37 @@ fn try_read_bxes_value(
         TypeIds::F32 => Ok(BxesValue::Float32(try_read_f32(reader)?)),
         TypeIds::F64 => Ok(BxesValue::Float64(try_read_f64(reader)?)),
         TypeIds::Bool => Ok(BxesValue::Bool(try_read_bool(reader)?)),
-        TypeIds::String => Ok(BxesValue::String(Rc::new(Box::new(try_read_string(reader)?)))),
+        TypeIds::String => Ok(BxesValue::String(Arc::from(try_read_string(
+            reader,
+            version,
+            string_encoding,
+            previous_string,
+        )?))),
         TypeIds::Timestamp => Ok(BxesValue::Timestamp(try_read_i64(reader)?)),
         TypeIds::BrafLifecycle => Ok(BxesValue::BrafLifecycle(try_read_braf_lifecycle(reader)?)),
         TypeIds::StandardLifecycle => {
             Ok(BxesValue::StandardLifecycle(try_read_standard_lifecycle(reader)?))
         }
         TypeIds::Guid => Ok(BxesValue::Guid(try_read_guid(reader)?)),
-        TypeIds::Artifact => Ok(BxesValue::Artifact(try_read_artifact(reader, values)?)),
-        TypeIds::Drivers => Ok(BxesValue::Drivers(try_read_drivers(reader, values)?)),
+        TypeIds::Artifact => Ok(BxesValue::Artifact(try_read_artifact(reader, version, values)?)),
+        TypeIds::Drivers => Ok(BxesValue::Drivers(try_read_drivers(reader, version, values)?)),
         TypeIds::SoftwareEventType => {
             Ok(BxesValue::SoftwareEventType(try_read_software_event_type(reader)?))
         }
-        _ => Err(BxesReadError::FailedToParseTypeId(type_id_byte)),
+        _ => Err(type_id_error(type_id_byte)),
     }
 }
 
 pub fn try_read_drivers(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
 ) -> Result<BxesDrivers, BxesReadError> {
-    let drivers_count = try_read_u32(reader)?;
+    let drivers_count = try_read_compact_u32(reader, version)?;
     let mut drivers = vec![];
 
     for _ in 0..drivers_count {
-        drivers.push(try_read_driver(reader, values)?);
+        drivers.push(try_read_driver(reader, version, values)?);
     }
 
     Ok(BxesDrivers { drivers })
@@ -325,28 +717,30 @@ pub fn try_read_drivers(
 
 pub fn try_read_driver(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
 ) -> Result<BxesDriver, BxesReadError> {
     let amount = try_read_f64(reader)?;
-    let name_index = try_read_u32(reader)? as usize;
-    let driver_type_index = try_read_u32(reader)? as usize;
+    let name = try_get_value(values, try_read_compact_u32(reader, version)?)?;
+    let driver_type = try_get_value(values, try_read_compact_u32(reader, version)?)?;
 
     Ok(BxesDriver {
         amount: BxesValue::Float64(amount),
-        name: values[name_index].clone(),
-        driver_type: values[driver_type_index].clone(),
+        name,
+        driver_type,
     })
 }
 
 pub fn try_read_artifact(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
 ) -> Result<BxesArtifact, BxesReadError> {
-    let artifacts_count = try_read_u32(reader)?;
+    let artifacts_count = try_read_compact_u32(reader, version)?;
     let mut artifacts = vec![];
 
     for _ in 0..artifacts_count {
-        artifacts.push(try_read_artifact_item(reader, values)?);
+        artifacts.push(try_read_artifact_item(reader, version, values)?);
     }
 
     Ok(BxesArtifact { items: artifacts })
@@ -354,19 +748,27 @@ pub fn try_read_artifact(
 
 pub fn try_read_artifact_item(
     reader: &mut BinaryReader,
-    values: &Vec<Rc<Box<BxesValue>>>,
+    version: u32,
+    values: &Vec<Arc<BxesValue>>,
 ) -> Result<BxesArtifactItem, BxesReadError> {
-    let model_index = try_read_u32(reader)? as usize;
-    let instance_index = try_read_u32(reader)? as usize;
-    let transition_index = try_read_u32(reader)? as usize;
+    let model = try_get_value(values, try_read_compact_u32(reader, version)?)?;
+    let instance = try_get_value(values, try_read_compact_u32(reader, version)?)?;
+    let transition = try_get_value(values, try_read_compact_u32(reader, version)?)?;
 
     Ok(BxesArtifactItem {
-        model: values[model_index].clone(),
-        instance: values[instance_index].clone(),
-        transition: values[transition_index].clone(),
+        model,
+        instance,
+        transition,
     })
 }
 
+fn try_get_value(values: &[Arc<BxesValue>], index: u32) -> Result<Arc<BxesValue>, BxesReadError> {
+    match values.get(index as usize) {
+        Some(value) => Ok(value.clone()),
+        None => Err(BxesReadError::FailedToIndexValue(index as usize)),
+    }
+}
+
 pub fn try_read_guid(reader: &mut BinaryReader) -> Result<Uuid, BxesReadError> {
     try_read(try_tell_pos(reader)?, || {
         let mut buf = [0; 16];
@@ -379,7 +781,7 @@ pub fn try_read_guid(reader: &mut BinaryReader) -> Result<Uuid, BxesReadError> {
 pub fn try_read_software_event_type(
     reader: &mut BinaryReader,
 ) -> Result<SoftwareEventType, BxesReadError> {
-    try_read_enum::<SoftwareEventType>(reader)
+    try_read_enum::<SoftwareEventType>(reader, type_id_out_of_range)
 }
 
 pub fn try_read_i32(reader: &mut BinaryReader) -> Result<i32, BxesReadError> {
@@ -414,13 +816,44 @@ pub fn try_read_u8(reader: &mut BinaryReader) -> Result<u8, BxesReadError> {
     try_read(try_tell_pos(reader)?, || reader.read_u8())
 }
 
-fn try_read_string(reader: &mut BinaryReader) -> Result<String, BxesReadError> {
-    let string_length = try_read_u64(reader)?;
-    let bytes = try_read_bytes(reader, string_length as usize)?;
+fn try_read_string(
+    reader: &mut BinaryReader,
+    version: u32,
+    string_encoding: BxesStringEncoding,
+    previous_string: Option<&str>,
+) -> Result<String, BxesReadError> {
+    let mut bytes = vec![];
+    if string_encoding == BxesStringEncoding::FrontCoded {
+        let offset = try_tell_pos(reader)?;
+        let prefix_length = try_read_leb128_u64(reader)?;
+        let previous_string = previous_string.unwrap_or_default().as_bytes();
+
+        match previous_string.get(..prefix_length as usize) {
+            Some(prefix) => bytes.extend_from_slice(prefix),
+            None => {
+                return Err(BxesReadError::InvalidSharedPrefixLength {
+                    length: prefix_length,
+                    offset,
+                })
+            }
+        }
+    }
+
+    let string_length = if version >= COMPACT_LENGTHS_VERSION {
+        try_read_leb128_u64(reader)?
+    } else {
+        try_read_u64(reader)?
+    };
+
+    let offset = try_tell_pos(reader)?;
+    bytes.extend(try_read_bytes(reader, string_length as usize)?);
 
     match String::from_utf8(bytes) {
         Ok(string) => Ok(string),
-        Err(err) => Err(BxesReadError::FailedToCreateUtf8String(err)),
+        Err(err) => Err(BxesReadError::FailedToCreateUtf8String {
+            offset,
+            source: err.utf8_error(),
+        }),
     }
 }
 
@@ -429,60 +862,236 @@ fn try_read_bytes(reader: &mut BinaryReader, length: usize) -> Result<Vec<u8>, B
     let mut buf = vec![0; length];
     match reader.read(&mut buf) {
         Ok(_) => Ok(buf),
-        Err(err) => Err(
-            BxesReadError::FailedToReadValue(FailedToReadValueError::new(offset, err.to_string()))
-        ),
+        Err(err) => Err(BxesReadError::FailedToReadValue(FailedToReadValueError::new(
+            offset,
+            err.into(),
+        ))),
+    }
+}
+
+pub fn try_read_lifecycle(reader: &mut BinaryReader) -> Result<Lifecycle, BxesReadError> {
+    let offset = try_tell_pos(reader)?;
+    let type_id_byte = try_read_u8(reader)?;
+    match TypeIds::from_u8(type_id_byte) {
+        Some(TypeIds::BrafLifecycle) => Ok(Lifecycle::Braf(try_read_braf_lifecycle(reader)?)),
+        Some(TypeIds::StandardLifecycle) => {
+            Ok(Lifecycle::Standard(try_read_standard_lifecycle(reader)?))
+        }
+        _ => Err(BxesReadError::FailedToParseTypeId {
+            type_id: type_id_byte,
+            offset,
+        }),
     }
 }
 
 fn try_read_braf_lifecycle(reader: &mut BinaryReader) -> Result<BrafLifecycle, BxesReadError> {
-    try_read_enum::<BrafLifecycle>(reader)
+    try_read_enum::<BrafLifecycle>(reader, lifecycle_out_of_range)
 }
 
-fn try_read_enum<T: FromPrimitive>(reader: &mut BinaryReader) -> Result<T, BxesReadError> {
+/// Reads a one-byte enum, `out_of_range` creates the error for a byte
+/// which does not correspond to any of the enum values.
+fn try_read_enum<T: FromPrimitive>(
+    reader: &mut BinaryReader,
+    out_of_range: fn(u8, usize) -> BxesReadError,
+) -> Result<T, BxesReadError> {
     let offset = try_tell_pos(reader)?;
     match reader.read_u8() {
-        Ok(byte) => Ok(T::from_u8(byte).unwrap()),
-        Err(err) => Err(
-            BxesReadError::FailedToReadValue(FailedToReadValueError::new(offset, err.to_string()))
-        ),
+        Ok(byte) => match T::from_u8(byte) {
+            Some(value) => Ok(value),
+            None => Err(out_of_range(byte, offset)),
+        },
+        Err(err) => Err(BxesReadError::FailedToReadValue(FailedToReadValueError::new(
+            offset, err,
+        ))),
     }
 }
 
+fn type_id_out_of_range(type_id: u8, offset: usize) -> BxesReadError {
+    BxesReadError::FailedToParseTypeId { type_id, offset }
+}
+
+fn lifecycle_out_of_range(value: u8, offset: usize) -> BxesReadError {
+    BxesReadError::LifecycleOfEventOutOfRange { value, offset }
+}
+
 fn try_read_standard_lifecycle(
     reader: &mut BinaryReader,
 ) -> Result<StandardLifecycle, BxesReadError> {
-    try_read_enum::<StandardLifecycle>(reader)
+    try_read_enum::<StandardLifecycle>(reader, lifecycle_out_of_range)
+}
+
+/// The container of a single-file log, detected by its first bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BxesContainer {
+    Zip,
+    Zstd,
+    Lz4,
+    Raw,
+}
+
+/// Detects the container of the log without changing the position of the reader.
+pub fn try_detect_container<R: Read + Seek>(reader: &mut R) -> Result<BxesContainer, BxesReadError> {
+    let to_read_error = BxesReadError::FailedToDetectContainer;
+
+    let position = reader.stream_position().map_err(to_read_error)?;
+    let mut magic = vec![];
+    reader
+        .take(ZIP_MAGIC.len() as u64)
+        .read_to_end(&mut magic)
+        .map_err(to_read_error)?;
+
+    reader
+        .seek(SeekFrom::Start(position))
+        .map_err(to_read_error)?;
+
+    Ok(match magic.as_slice() {
+        magic if magic == ZIP_MAGIC => BxesContainer::Zip,
+        magic if magic == ZSTD_MAGIC => BxesContainer::Zstd,
+        magic if magic == LZ4_MAGIC => BxesContainer::Lz4,
+        _ => BxesContainer::Raw,
+    })
 }
 
-pub fn try_extract_archive(path: &str) -> Result<TempDir, BxesReadError> {
-    let fs = match File::open(path) {
-        Ok(fs) => fs,
-        Err(err) => return Err(BxesReadError::FailedToOpenFile(err.to_string())),
+/// Copies the decompressed raw log from the Zstandard or LZ4 container into the writer.
+pub fn try_decompress<R: Read, W: io::Write>(
+    container: BxesContainer,
+    reader: R,
+    writer: &mut W,
+) -> Result<(), BxesReadError> {
+    let result = match container {
+        BxesContainer::Zstd => zstd::stream::copy_decode(reader, writer),
+        BxesContainer::Lz4 => {
+            io::copy(&mut lz4_flex::frame::FrameDecoder::new(reader), writer).map(|_| ())
+        }
+        BxesContainer::Raw => io::copy(&mut { reader }, writer).map(|_| ()),
+        BxesContainer::Zip => {
+            return Err(BxesReadError::FailedToDecompress(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "ZIP archives must be extracted",
+            )))
+        }
     };
 
-    let mut archive = match ZipArchive::new(fs) {
-        Ok(archive) => archive,
-        Err(err) => return Err(BxesReadError::FailedToOpenFile(err.to_string())),
+    result.map_err(BxesReadError::FailedToDecompress)
+}
+
+/// Opens the raw log of a single-file log, compressed logs are decompressed on the fly.
+/// Entries of ZIP archives are read with a forward-only stream unless `seekable` is set,
+/// in which case they are decompressed into memory, as are Zstandard and LZ4 logs.
+pub fn try_open_raw_log_stream(
+    path: &str,
+    seekable: bool,
+) -> Result<Box<dyn ReadStream + Send>, BxesReadError> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) => {
+            return Err(BxesReadError::FailedToOpenFile {
+                path: path.to_owned(),
+                source: err.into(),
+            })
+        }
     };
 
-    let temp_dir = match TempDir::new() {
-        Ok(temp_dir) => temp_dir,
-        Err(_) => return Err(BxesReadError::FailedToCreateTempDir),
+    match try_detect_container(&mut file)? {
+        BxesContainer::Raw => Ok(Box::new(try_open_file_stream(path)?)),
+        BxesContainer::Zip => {
+            let (entry_reader, length) = try_open_archive_entry(file)?;
+            if !seekable {
+                let entry_reader = Box::new(entry_reader);
+                return Ok(Box::new(ForwardReadStream::new(entry_reader, length)));
+            }
+
+            // The uncompressed size of the entry is not trusted to preallocate the log.
+            let mut raw_log = vec![];
+            try_decompress(BxesContainer::Raw, entry_reader, &mut raw_log)?;
+
+            Ok(Box::new(try_create_memory_stream(raw_log)?))
+        }
+        container => {
+            let mut raw_log = vec![];
+            try_decompress(container, file, &mut raw_log)?;
+
+            Ok(Box::new(try_create_memory_stream(raw_log)?))
+        }
+    }
+}
+
+/// Decompressing reader of the entry of the archive, see `try_open_archive_entry`.
+pub enum ArchiveEntryReader<R: Read> {
+    Stored(io::Take<BufReader<R>>),
+    Deflated(DeflateDecoder<io::Take<BufReader<R>>>),
+}
+
+impl<R: Read> Read for ArchiveEntryReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            ArchiveEntryReader::Stored(reader) => reader.read(buf),
+            ArchiveEntryReader::Deflated(reader) => reader.read(buf),
+        }
+    }
+}
+
+/// Returns the decompressing reader of the only entry of the archive and its uncompressed size.
+pub fn try_open_archive_entry<R: Read + Seek>(
+    reader: R,
+) -> Result<(ArchiveEntryReader<R>, usize), BxesReadError> {
+    let mut archive = ZipArchive::new(reader)?;
+    if archive.len() != 1 {
+        return Err(BxesReadError::InvalidArchive(format!(
+            "Expected one file, got {}",
+            archive.len()
+        )));
+    }
+
+    let (data_start, compressed_size, size, compression) = {
+        let entry = archive.by_index_raw(0)?;
+
+        (
+            entry.data_start(),
+            entry.compressed_size(),
+            entry.size() as usize,
+            entry.compression(),
+        )
     };
 
-    match archive.extract(temp_dir.path()) {
-        Ok(_) => {}
-        Err(_) => return Err(BxesReadError::FailedToExtractArchive),
+    let mut reader = archive.into_inner();
+    reader
+        .seek(SeekFrom::Start(data_start))
+        .map_err(|err| BxesReadError::FailedToReadPos(err.into()))?;
+
+    let compressed_data = BufReader::new(reader).take(compressed_size);
+    let reader = match compression {
+        CompressionMethod::Stored => ArchiveEntryReader::Stored(compressed_data),
+        CompressionMethod::Deflated => {
+            ArchiveEntryReader::Deflated(DeflateDecoder::new(compressed_data))
+        }
+        method => {
+            return Err(BxesReadError::InvalidArchive(format!(
+                "Unsupported compression method {}",
+                method
+            )))
+        }
     };
 
-    return Ok(temp_dir);
+    Ok((reader, size))
+}
+
+fn try_create_memory_stream(
+    raw_log: Vec<u8>,
+) -> Result<StdStream<Cursor<Vec<u8>>>, BxesReadError> {
+    match StdStream::new(Cursor::new(raw_log)) {
+        Ok(stream) => Ok(stream),
+        Err(err) => Err(BxesReadError::FailedToReadPos(err.into())),
+    }
 }
 
 pub fn try_open_file_stream(path: &str) -> Result<BufferedReadFileStream, BxesReadError> {
     match FileStream::open(path) {
         Ok(fs) => Ok(BufferedReadFileStream::new(fs, 1024 * 8)),
-        Err(err) => Err(BxesReadError::FailedToOpenFile(err.to_string())),
+        Err(err) => Err(BxesReadError::FailedToOpenFile {
+            path: path.to_owned(),
+            source: err,
+        }),
     }
 }
 
@@ -493,7 +1102,7 @@ fn try_read<T>(
     match read_func() {
         Ok(value) => Ok(value),
         Err(err) => Err(BxesReadError::FailedToReadValue(
-            FailedToReadValueError::new(reader_pos, err.to_string()),
+            FailedToReadValueError::new(reader_pos, err),
         )),
     }
 }
@@ -501,6 +1110,6 @@ fn try_read<T>(
 fn try_tell_pos(reader: &mut BinaryReader) -> Result<usize, BxesReadError> {
     match reader.tell() {
         Ok(pos) => Ok(pos),
-        Err(err) => Err(BxesReadError::FailedToReadPos(err.to_string())),
+        Err(err) => Err(BxesReadError::FailedToReadPos(err)),
     }
 }
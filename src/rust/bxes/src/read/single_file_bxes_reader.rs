@@ -1,33 +1,81 @@
-use std::fs;
+use std::{
+    io::{Cursor, Read, Seek},
+    sync::Arc,
+};
 
-use super::{errors::BxesReadError, read_utils::*};
+use super::{
+    errors::{BxesReadError, BxesSection},
+    lazy_bxes_reader::BxesLazyEventLog,
+    read_utils::*,
+};
 use crate::{
-    binary_rw::core::{BinaryReader, Endian},
+    binary_rw::core::{BinaryReader, Endian, ReadStream},
     models::*,
+    utils::memory_stream::MemoryReadStream,
 };
 
 pub fn read_bxes(path: &str) -> Result<BxesEventLog, BxesReadError> {
-    let extracted_files_dir = try_extract_archive(path)?;
-    let extracted_files_dir = extracted_files_dir.path();
+    read_bxes_lazy(path)?.into_event_log()
+}
 
-    let files = fs::read_dir(extracted_files_dir)
-        .unwrap()
-        .into_iter()
-        .map(|r| r.unwrap().path().to_str().unwrap().to_string())
-        .collect::<Vec<String>>();
+pub fn read_bxes_lazy(path: &str) -> Result<BxesLazyEventLog, BxesReadError> {
+    try_read_bxes_lazy(path, false)
+}
 
-    if files.len() != 1 {
-        return Err(BxesReadError::InvalidArchive(format!("Expected one file, got {:?}", files)));
-    }
+/// Same as `read_bxes_lazy`, but the underlying stream of the log supports seeking backward.
+pub(crate) fn read_bxes_lazy_seekable(path: &str) -> Result<BxesLazyEventLog, BxesReadError> {
+    try_read_bxes_lazy(path, true)
+}
 
-    let mut stream = try_open_file_stream(files[0].as_str())?;
+fn try_read_bxes_lazy(path: &str, seekable: bool) -> Result<BxesLazyEventLog, BxesReadError> {
+    let mut stream = try_open_raw_log_stream(path, seekable)?;
     let mut reader = BinaryReader::new(&mut stream, Endian::Little);
-    let version = try_read_u32(&mut reader)?;
+    let version = try_read_version(&mut reader)?;
+    let string_encoding = try_read_string_encoding(&mut reader, version)?;
+    let checksum = try_read_checksum_kind(&mut reader, version)?;
+    let sections = try_read_sections(&mut reader, version, string_encoding, checksum)?;
+    let (values, kv_pairs, metadata) = sections;
+
+    BxesLazyEventLog::new(
+        version,
+        string_encoding,
+        checksum,
+        metadata,
+        values,
+        kv_pairs,
+        stream,
+    )
+}
+
+/// Reads the single-file log from the given stream without touching the file system,
+/// the container of the log is detected automatically.
+pub fn read_bxes_from<R: Read + Seek>(mut reader: R) -> Result<BxesEventLog, BxesReadError> {
+    let container = try_detect_container(&mut reader)?;
+
+    let mut raw_log = vec![];
+    if container == BxesContainer::Zip {
+        let (entry_reader, _) = try_open_archive_entry(reader)?;
+        try_decompress(BxesContainer::Raw, entry_reader, &mut raw_log)?;
+    } else {
+        try_decompress(container, reader, &mut raw_log)?;
+    }
+
+    try_read_raw_log(&mut MemoryReadStream::new(&raw_log))
+}
+
+pub fn read_bxes_from_bytes(bytes: &[u8]) -> Result<BxesEventLog, BxesReadError> {
+    read_bxes_from(Cursor::new(bytes))
+}
+
+fn try_read_raw_log(stream: &mut impl ReadStream) -> Result<BxesEventLog, BxesReadError> {
+    let mut reader = BinaryReader::new(stream, Endian::Little);
+    let version = try_read_version(&mut reader)?;
+    let string_encoding = try_read_string_encoding(&mut reader, version)?;
+    let checksum = try_read_checksum_kind(&mut reader, version)?;
 
-    let values = try_read_values(&mut reader)?;
-    let kv_pairs = try_read_key_values(&mut reader)?;
-    let metadata = try_read_event_log_metadata(&mut reader, &values, &kv_pairs)?;
-    let variants = try_read_traces_variants(&mut reader, &values, &kv_pairs)?;
+    let sections = try_read_sections(&mut reader, version, string_encoding, checksum)?;
+    let (values, kv_pairs, metadata) = sections;
+    let variants = try_read_traces_variants(&mut reader, version, checksum, &values, &kv_pairs)?;
 
     Ok(BxesEventLog {
         version,
@@ -35,3 +83,27 @@ pub fn read_bxes(path: &str) -> Result<BxesEventLog, BxesReadError> {
         variants,
     })
 }
+
+type BxesSections = (Vec<Arc<BxesValue>>, Vec<(u32, u32)>, BxesEventLogMetadata);
+
+/// Reads values, key-value pairs and metadata which precede trace variants.
+fn try_read_sections(
+    reader: &mut BinaryReader,
+    version: u32,
+    string_encoding: BxesStringEncoding,
+    checksum: BxesChecksum,
+) -> Result<BxesSections, BxesReadError> {
+    let values = try_read_checksummed(reader, checksum, BxesSection::Values, |reader| {
+        try_read_values(reader, version, string_encoding)
+    })?;
+
+    let kv_pairs = try_read_checksummed(reader, checksum, BxesSection::KeyValues, |reader| {
+        try_read_key_values(reader, version)
+    })?;
+
+    let metadata = try_read_checksummed(reader, checksum, BxesSection::Metadata, |reader| {
+        try_read_event_log_metadata(reader, version, &values, &kv_pairs)
+    })?;
+
+    Ok((values, kv_pairs, metadata))
+}
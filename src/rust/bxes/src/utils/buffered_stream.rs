@@ -82,6 +82,7 @@ impl SeekStream for BufferedReadFileStream {
     fn seek(&mut self, to: usize) -> crate::binary_rw::core::Result<usize> {
         self.next_buffer_index = 0;
         self.occupied_size = 0;
+        self.total_read_bytes = to;
 
         self.stream.seek(to)
     }
@@ -121,7 +122,7 @@ impl SeekStream for BufferedWriteFileStream {
     }
 
     fn tell(&mut self) -> crate::binary_rw::core::Result<usize> {
-        self.stream.tell()
+        Ok(self.stream.tell()? + self.written_bytes_count)
     }
 
     fn len(&self) -> crate::binary_rw::core::Result<usize> {
@@ -133,7 +134,7 @@ impl Write for BufferedWriteFileStream {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         if buf.len() > self.buffer.len() {
             self.flush()?;
-            self.stream.write(buf)?;
+            self.stream.write_all(buf)?;
         } else {
             let remained_space = self.buffer.len() - self.written_bytes_count;
             if buf.len() > remained_space {
@@ -156,9 +157,9 @@ impl Write for BufferedWriteFileStream {
 
     fn flush(&mut self) -> std::io::Result<()> {
         if self.written_bytes_count != 0 {
-            self.written_bytes_count = 0;
             self.stream
-                .write(&self.buffer[0..self.written_bytes_count])?;
+                .write_all(&self.buffer[0..self.written_bytes_count])?;
+            self.written_bytes_count = 0;
         }
 
         self.stream.flush()
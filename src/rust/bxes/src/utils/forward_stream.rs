@@ -0,0 +1,67 @@
+use std::io::{self, Error, ErrorKind, Read};
+
+use crate::binary_rw::{
+    core::{ReadStream, SeekStream},
+    error::BinaryError,
+};
+
+/// Read stream over a non-seekable reader of known length, e.g. a decompressor,
+/// seeking is supported only forward by skipping bytes.
+pub struct ForwardReadStream {
+    reader: Box<dyn Read + Send>,
+    position: usize,
+    length: usize,
+}
+
+impl ForwardReadStream {
+    pub fn new(reader: Box<dyn Read + Send>, length: usize) -> Self {
+        Self {
+            reader,
+            position: 0,
+            length,
+        }
+    }
+}
+
+impl ReadStream for ForwardReadStream {}
+
+impl Read for ForwardReadStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.position + buf.len() > self.length {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                BinaryError::ReadPastEof,
+            ));
+        }
+
+        self.reader.read_exact(buf)?;
+        self.position += buf.len();
+
+        Ok(buf.len())
+    }
+}
+
+impl SeekStream for ForwardReadStream {
+    fn seek(&mut self, to: usize) -> crate::binary_rw::core::Result<usize> {
+        if to < self.position {
+            return Err(BinaryError::Io(Error::new(
+                ErrorKind::Unsupported,
+                "The stream can not be sought backward",
+            )));
+        }
+
+        let to_skip = (to - self.position) as u64;
+        let skipped = io::copy(&mut (&mut self.reader).take(to_skip), &mut io::sink())?;
+        self.position += skipped as usize;
+
+        Ok(self.position)
+    }
+
+    fn tell(&mut self) -> crate::binary_rw::core::Result<usize> {
+        Ok(self.position)
+    }
+
+    fn len(&self) -> crate::binary_rw::core::Result<usize> {
+        Ok(self.length)
+    }
+}
@@ -0,0 +1,62 @@
+use std::io::{Error, ErrorKind, Read};
+
+use crate::binary_rw::{
+    core::{ReadStream, SeekStream},
+    error::BinaryError,
+};
+
+pub struct MemoryReadStream<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    base_offset: usize,
+}
+
+impl<'a> MemoryReadStream<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_base_offset(bytes, 0)
+    }
+
+    /// Positions of the stream are offset by `base_offset`, so that reading a part of a log
+    /// reports positions in the whole log.
+    pub fn with_base_offset(bytes: &'a [u8], base_offset: usize) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            base_offset,
+        }
+    }
+}
+
+impl<'a> ReadStream for MemoryReadStream<'a> {}
+
+impl<'a> Read for MemoryReadStream<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let end = self.position + buf.len();
+        if end > self.bytes.len() {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                BinaryError::ReadPastEof,
+            ));
+        }
+
+        buf.copy_from_slice(&self.bytes[self.position..end]);
+        self.position = end;
+
+        Ok(buf.len())
+    }
+}
+
+impl<'a> SeekStream for MemoryReadStream<'a> {
+    fn seek(&mut self, to: usize) -> crate::binary_rw::core::Result<usize> {
+        self.position = to - self.base_offset;
+        Ok(to)
+    }
+
+    fn tell(&mut self) -> crate::binary_rw::core::Result<usize> {
+        Ok(self.base_offset + self.position)
+    }
+
+    fn len(&self) -> crate::binary_rw::core::Result<usize> {
+        Ok(self.base_offset + self.bytes.len())
+    }
+}
@@ -1 +1,4 @@
 pub mod buffered_stream;
+pub mod forward_stream;
+pub mod memory_stream;
+pub mod std_stream;
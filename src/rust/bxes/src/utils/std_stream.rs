@@ -0,0 +1,64 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::binary_rw::core::{ReadStream, SeekStream, WriteStream};
+
+/// Adapts any std stream to `ReadStream`/`WriteStream`, so binary readers and writers
+/// can work over in-memory buffers, network bodies or custom containers.
+pub struct StdStream<S: Seek> {
+    stream: S,
+    length: usize,
+}
+
+impl<S: Seek> StdStream<S> {
+    pub fn new(mut stream: S) -> std::io::Result<Self> {
+        let position = stream.stream_position()?;
+        let length = stream.seek(SeekFrom::End(0))? as usize;
+        stream.seek(SeekFrom::Start(position))?;
+
+        Ok(Self { stream, length })
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Seek + Read> ReadStream for StdStream<S> {}
+
+impl<S: Seek + Read> Read for StdStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.stream.read_exact(buf)?;
+        Ok(buf.len())
+    }
+}
+
+impl<S: Seek + Write> WriteStream for StdStream<S> {}
+
+impl<S: Seek + Write> Write for StdStream<S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.stream.write_all(buf)?;
+
+        let position = self.stream.stream_position()? as usize;
+        self.length = self.length.max(position);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+impl<S: Seek> SeekStream for StdStream<S> {
+    fn seek(&mut self, to: usize) -> crate::binary_rw::core::Result<usize> {
+        Ok(self.stream.seek(SeekFrom::Start(to as u64))? as usize)
+    }
+
+    fn tell(&mut self) -> crate::binary_rw::core::Result<usize> {
+        Ok(self.stream.stream_position()? as usize)
+    }
+
+    fn len(&self) -> crate::binary_rw::core::Result<usize> {
+        Ok(self.length)
+    }
+}
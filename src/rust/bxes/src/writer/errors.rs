@@ -1,32 +1,79 @@
-use std::{rc::Rc, fmt::Display};
+use std::{io, sync::Arc};
 
-use crate::{binary_rw::error::BinaryError, models::BxesValue};
+use thiserror::Error;
+use zip::result::ZipError;
 
-#[derive(Debug)]
+use crate::{
+    binary_rw::error::BinaryError,
+    constants::SUPPORTED_VERSIONS,
+    models::BxesValue,
+    read::errors::{BxesErrorLocation, BxesReadError},
+};
+
+#[derive(Debug, Error)]
 pub enum BxesWriteError {
-    FailedToOpenFileForWriting(String),
-    WriteError(BinaryError),
-    FailedToGetWriterPosition(String),
-    FailedToSeek(String),
-    FailedToFindKeyValueIndex((Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)),
-    FailedToFindValueIndex(Rc<Box<BxesValue>>),
-    FailedToCreateTempFile,
-    FailedToCreateArchive,
-    LebWriteError(String),
+    #[error("Failed to open file {path} for writing")]
+    FailedToOpenFileForWriting { path: String, source: BinaryError },
+    #[error("Failed to write")]
+    WriteError(#[from] BinaryError),
+    #[error("Failed to get writer position")]
+    FailedToGetWriterPosition(#[source] BinaryError),
+    #[error("Failed to seek writer")]
+    FailedToSeek(#[source] BinaryError),
+    #[error("Failed to find index of key-value pair {0:?}")]
+    FailedToFindKeyValueIndex((Arc<BxesValue>, Arc<BxesValue>)),
+    #[error("Failed to find index of value {0:?}")]
+    FailedToFindValueIndex(Arc<BxesValue>),
+    #[error("Failed to create temp file")]
+    FailedToCreateTempFile(#[source] io::Error),
+    #[error("Failed to create temp directory")]
+    FailedToCreateTempDir(#[source] io::Error),
+    #[error("Failed to create archive")]
+    FailedToCreateArchive(#[from] ZipError),
+    #[error("Failed to write LEB128 encoded value")]
+    LebWriteError(#[source] io::Error),
+    #[error("Event must be preceded by a trace variant start")]
+    EventOutsideOfTraceVariant,
+    #[error("Unsupported bxes version: {0}, supported versions: {SUPPORTED_VERSIONS:?}")]
+    UnsupportedVersion(u32),
+    #[error("Failed to compress log")]
+    FailedToCompress(#[source] io::Error),
+    #[error("Failed to read existing log")]
+    FailedToReadLog(#[from] BxesReadError),
+    #[error("Stream writers do not support {0}")]
+    UnsupportedStreamOption(&'static str),
+    #[error("Count at offset {offset} of {path} is not padded to be updated in place")]
+    UnpaddedCount { path: String, offset: usize },
+    #[error("Error {location}")]
+    Located {
+        location: BxesErrorLocation,
+        source: Box<BxesWriteError>,
+    },
 }
 
-impl ToString for BxesWriteError {
-    fn to_string(&self) -> String {
+impl BxesWriteError {
+    /// Attaches the location to the error, see `BxesReadError::located`.
+    pub(crate) fn located(self, location: BxesErrorLocation) -> Self {
+        match self {
+            BxesWriteError::Located {
+                location: existing_location,
+                source,
+            } => BxesWriteError::Located {
+                location: existing_location.or(location),
+                source,
+            },
+            err => BxesWriteError::Located {
+                location,
+                source: Box::new(err),
+            },
+        }
+    }
+
+    /// Returns the location at which the error occurred, if it is known.
+    pub fn location(&self) -> Option<&BxesErrorLocation> {
         match self {
-            BxesWriteError::FailedToOpenFileForWriting(err) => err.to_owned(),
-            BxesWriteError::WriteError(err) => err.to_string(),
-            BxesWriteError::FailedToGetWriterPosition(err) => err.to_owned(),
-            BxesWriteError::FailedToSeek(err) => err.to_owned(),
-            BxesWriteError::FailedToFindKeyValueIndex(value) => format!("{:?}", value),
-            BxesWriteError::FailedToFindValueIndex(value) => format!("{:?}", value),
-            BxesWriteError::FailedToCreateTempFile => "FailedToCreateTempFile".to_string(),
-            BxesWriteError::FailedToCreateArchive => "FailedToCreateArchive".to_string(),
-            BxesWriteError::LebWriteError(err) => err.to_string(),
+            BxesWriteError::Located { location, .. } => Some(location),
+            _ => None,
         }
     }
-}
\ No newline at end of file
+}
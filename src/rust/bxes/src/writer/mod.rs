@@ -1,5 +1,8 @@
 pub mod errors;
+pub mod multiple_file_bxes_appender;
 pub mod multiple_file_bxes_writer;
 pub mod single_file_bxes_writer;
+pub mod stream;
+pub mod write_options;
 mod write_context;
 mod writer_utils;
@@ -0,0 +1,577 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs::File,
+    hash::{Hash, Hasher},
+    io::{Read, Seek, SeekFrom, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    binary_rw::{
+        core::{BinaryReader, BinaryWriter, Endian, SeekStream},
+        file_stream::FileStream,
+    },
+    constants,
+    models::{BxesChecksum, BxesEvent, BxesTraceVariant, BxesValue},
+    read::{
+        multiple_files_bxes_reader::read_bxes_multiple_files_lazy,
+        read_utils::{try_read_trace_variant, try_read_u32},
+    },
+    utils::{buffered_stream::BufferedWriteFileStream, std_stream::StdStream},
+};
+
+use super::{
+    errors::BxesWriteError,
+    stream::multiple_file_bxes_stream_writer::{
+        execute_with_checksummed_writer, write_checksum, write_count, write_section_count,
+        write_variants_count,
+    },
+    write_context::BxesWriteContext,
+    writer_utils::{
+        header_size, try_seek, try_tell_pos, try_write_event, try_write_kv_pair,
+        try_write_padded_count, try_write_trace_variant_start, try_write_u32_no_type_id,
+        try_write_value, update_count_checksum,
+    },
+};
+
+/// Appends trace variants to an existing log written in the multiple files layout.
+///
+/// New values, key-value pairs and variants are written to the end of the corresponding files,
+/// a variant which is already present in the log gets its traces count incremented instead.
+/// The log metadata is left unchanged.
+/// Checksums of values and key-value pairs are continued from the stored ones,
+/// appended variants get their own checksums. Checksums cover counts, so they are updated
+/// along with the counts.
+///
+/// Count headers and checksums of sections are written by `finish`, which is also called
+/// when the appender is dropped, ignoring errors. Until then the log on disk is inconsistent:
+/// if the process terminates before `finish`, the log can not be read anymore.
+///
+/// Only hashes of variants are kept in memory, along with values and key-value pairs of the log,
+/// a variant with a matching hash is read back from the variants file to be compared.
+pub struct MultipleFilesBxesAppender {
+    values_stream: BufferedWriteFileStream,
+    kv_pairs_stream: BufferedWriteFileStream,
+    variants_stream: BufferedWriteFileStream,
+    variants_reader: StdStream<File>,
+    context: BxesWriteContext<'static>,
+    values: Vec<Arc<BxesValue>>,
+    kv_pairs: Vec<(u32, u32)>,
+    /// Offsets and traces counts of variants by hashes of their metadata and events.
+    variants: HashMap<u64, Vec<(usize, u32)>>,
+    variants_count: u32,
+    /// Counts of values and key-value pairs stored in the log, which are covered by stored checksums.
+    stored_values_count: u32,
+    stored_kv_pairs_count: u32,
+    values_checksum: u32,
+    kv_pairs_checksum: u32,
+    finished: bool,
+}
+
+impl MultipleFilesBxesAppender {
+    pub fn open(directory_path: &str) -> Result<Self, BxesWriteError> {
+        let mut log = match read_bxes_multiple_files_lazy(directory_path) {
+            Ok(log) => log,
+            Err(err) => return Err(BxesWriteError::FailedToReadLog(err)),
+        };
+
+        let mut variants_count = 0;
+        let mut variants: HashMap<u64, Vec<(usize, u32)>> = HashMap::new();
+        let read_result = log.try_for_each_variant_with_offset(|offset, variant| {
+            variants
+                .entry(hash_variant(&variant.metadata, &variant.events))
+                .or_default()
+                .push((offset, variant.traces_count));
+
+            variants_count += 1;
+        });
+
+        if let Err(err) = read_result {
+            return Err(BxesWriteError::FailedToReadLog(err));
+        }
+
+        let mut context = BxesWriteContext::empty(log.version);
+        context.string_encoding = log.string_encoding;
+        context.checksum = log.checksum;
+        context.padded_counts = true;
+        for (index, value) in log.values().iter().enumerate() {
+            context
+                .values_indices
+                .borrow_mut()
+                .entry(value.clone())
+                .or_insert(index);
+        }
+
+        let last_string = log
+            .values()
+            .iter()
+            .rev()
+            .find_map(|value| match value.as_ref() {
+                BxesValue::String(string) => Some(string.clone()),
+                _ => None,
+            });
+
+        context.last_string.replace(last_string);
+
+        for (index, (key, value)) in log.kv_pairs().iter().enumerate() {
+            let key = log.values()[*key as usize].clone();
+            let value = log.values()[*value as usize].clone();
+            context
+                .kv_indices
+                .borrow_mut()
+                .entry((key, value))
+                .or_insert(index);
+        }
+
+        // Counts are updated in place, so all of them must be padded.
+        let count_position = header_size(log.version);
+        for file_name in [constants::VALUES_FILE_NAME, constants::KEY_VALUES_FILE_NAME] {
+            let path = Path::new(directory_path).join(file_name);
+            try_check_padded_counts(&path, log.version, [count_position])?;
+        }
+
+        let variants_offsets = variants
+            .values()
+            .flat_map(|variants| variants.iter().map(|(offset, _)| *offset));
+
+        try_check_padded_counts(
+            &Path::new(directory_path).join(constants::VARIANTS_FILE_NAME),
+            log.version,
+            std::iter::once(count_position).chain(variants_offsets),
+        )?;
+
+        let checksum_size = log.checksum.size();
+
+        // The checksum of the section is overwritten by appended items and written again on `finish`.
+        let open_append = |file_name: &str,
+                           checksum_size: usize|
+         -> Result<BufferedWriteFileStream, BxesWriteError> {
+            let file_path = Path::new(directory_path).join(file_name);
+            let mut stream = match FileStream::write(&file_path) {
+                Ok(stream) => stream,
+                Err(err) => {
+                    return Err(BxesWriteError::FailedToOpenFileForWriting {
+                        path: file_path.to_string_lossy().into_owned(),
+                        source: err,
+                    })
+                }
+            };
+
+            stream
+                .len()
+                .and_then(|length| stream.seek(length - checksum_size))
+                .map_err(BxesWriteError::FailedToSeek)?;
+
+            Ok(BufferedWriteFileStream::new(stream, 1024 * 8))
+        };
+
+        let read_checksum = |file_name: &str| -> Result<u32, BxesWriteError> {
+            match log.checksum {
+                BxesChecksum::None => Ok(0),
+                BxesChecksum::Crc32c => {
+                    try_read_trailing_checksum(&Path::new(directory_path).join(file_name))
+                }
+            }
+        };
+
+        let variants_path = Path::new(directory_path).join(constants::VARIANTS_FILE_NAME);
+        let variants_reader = match File::open(&variants_path).and_then(StdStream::new) {
+            Ok(stream) => stream,
+            Err(err) => {
+                return Err(BxesWriteError::FailedToOpenFileForWriting {
+                    path: variants_path.to_string_lossy().into_owned(),
+                    source: err.into(),
+                })
+            }
+        };
+
+        Ok(Self {
+            values_stream: open_append(constants::VALUES_FILE_NAME, checksum_size)?,
+            kv_pairs_stream: open_append(constants::KEY_VALUES_FILE_NAME, checksum_size)?,
+            variants_stream: open_append(constants::VARIANTS_FILE_NAME, 0)?,
+            values_checksum: read_checksum(constants::VALUES_FILE_NAME)?,
+            kv_pairs_checksum: read_checksum(constants::KEY_VALUES_FILE_NAME)?,
+            variants_reader,
+            context,
+            stored_values_count: log.values().len() as u32,
+            stored_kv_pairs_count: log.kv_pairs().len() as u32,
+            values: log.values().clone(),
+            kv_pairs: log.kv_pairs().clone(),
+            variants,
+            variants_count,
+            finished: false,
+        })
+    }
+
+    /// Appends `variant.traces_count` traces of the variant to the log.
+    pub fn append_variant(&mut self, variant: &BxesTraceVariant) -> Result<(), BxesWriteError> {
+        let hash = hash_variant(&variant.metadata, &variant.events);
+        if let Some((index, end)) = self.find_variant(hash, variant)? {
+            let (offset, traces_count) = &mut self.variants.get_mut(&hash).unwrap()[index];
+            let old_traces_count = *traces_count;
+            *traces_count = traces_count.saturating_add(variant.traces_count);
+
+            let (offset, traces_count) = (*offset, *traces_count);
+            return self.write_traces_count(offset, end, old_traces_count, traces_count);
+        }
+
+        self.write_attributes(&variant.metadata)?;
+        for event in &variant.events {
+            self.write_value(&event.name)?;
+            if let Some(attributes) = event.attributes.as_ref() {
+                self.write_attributes(attributes)?;
+            }
+        }
+
+        let mut checksum = 0;
+        let stream = &mut self.variants_stream;
+        let offset =
+            execute_with_checksummed_writer(stream, &self.context, &mut checksum, |context| {
+                let offset = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+
+                try_write_trace_variant_start(
+                    context.clone(),
+                    variant.traces_count,
+                    &variant.metadata,
+                )?;
+
+                let version = context.borrow().version;
+                try_write_padded_count(
+                    context.borrow_mut().writer.as_mut().unwrap(),
+                    version,
+                    variant.events.len() as u32,
+                )?;
+
+                let mut previous_timestamp = None;
+                for event in &variant.events {
+                    try_write_event(event, previous_timestamp, context.clone())?;
+                    previous_timestamp = Some(event.timestamp);
+                }
+
+                Ok(offset)
+            })?;
+
+        write_checksum(&mut self.variants_stream, &self.context, checksum)?;
+
+        self.variants
+            .entry(hash)
+            .or_default()
+            .push((offset, variant.traces_count));
+
+        self.variants_count += 1;
+
+        Ok(())
+    }
+
+    /// Appends a single trace with the given metadata and events to the log.
+    pub fn append_trace(
+        &mut self,
+        metadata: Vec<(Arc<BxesValue>, Arc<BxesValue>)>,
+        events: Vec<BxesEvent>,
+    ) -> Result<(), BxesWriteError> {
+        self.append_variant(&BxesTraceVariant {
+            traces_count: 1,
+            metadata,
+            events,
+        })
+    }
+
+    /// Writes count headers and checksums of sections, after which the log can be read again.
+    pub fn finish(mut self) -> Result<(), BxesWriteError> {
+        self.try_finish()
+    }
+
+    fn try_finish(&mut self) -> Result<(), BxesWriteError> {
+        self.finished = true;
+        let values_count = self.context.values_indices.borrow().len() as u32;
+        let kv_pairs_count = self.context.kv_indices.borrow().len() as u32;
+
+        let context = &self.context;
+        write_section_count(
+            &mut self.values_stream,
+            context,
+            self.stored_values_count,
+            values_count,
+            self.values_checksum,
+        )?;
+        write_section_count(
+            &mut self.kv_pairs_stream,
+            context,
+            self.stored_kv_pairs_count,
+            kv_pairs_count,
+            self.kv_pairs_checksum,
+        )?;
+        write_variants_count(&mut self.variants_stream, context, self.variants_count)?;
+
+        for stream in [
+            &mut self.values_stream,
+            &mut self.kv_pairs_stream,
+            &mut self.variants_stream,
+        ] {
+            stream
+                .flush()
+                .map_err(|err| BxesWriteError::WriteError(err.into()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Updates the traces count of the variant at `offset` which ends at `end`,
+    /// the checksum of the variant is updated along with it.
+    fn write_traces_count(
+        &mut self,
+        offset: usize,
+        end: usize,
+        old_traces_count: u32,
+        traces_count: u32,
+    ) -> Result<(), BxesWriteError> {
+        let context = &self.context;
+        write_count(&mut self.variants_stream, context, offset, traces_count)?;
+        if context.checksum == BxesChecksum::None {
+            return Ok(());
+        }
+
+        let checksum_position = end - context.checksum.size();
+        self.variants_reader
+            .seek(checksum_position)
+            .map_err(BxesWriteError::FailedToSeek)?;
+
+        let mut reader = BinaryReader::new(&mut self.variants_reader, Endian::Little);
+        let checksum = match try_read_u32(&mut reader) {
+            Ok(checksum) => checksum,
+            Err(err) => return Err(BxesWriteError::FailedToReadLog(err)),
+        };
+
+        let version = context.version;
+        let checksum = update_count_checksum(
+            checksum,
+            version,
+            old_traces_count,
+            traces_count,
+            offset,
+            checksum_position,
+        );
+
+        let mut writer = BinaryWriter::new(&mut self.variants_stream, Endian::Little);
+        let current_position = try_tell_pos(&mut writer)?;
+        try_seek(&mut writer, checksum_position)?;
+        try_write_u32_no_type_id(&mut writer, checksum)?;
+        try_seek(&mut writer, current_position)
+    }
+
+    /// Returns the index of the variant with the given hash which is equal to the given variant
+    /// along with the position at which the variant ends.
+    fn find_variant(
+        &mut self,
+        hash: u64,
+        variant: &BxesTraceVariant,
+    ) -> Result<Option<(usize, usize)>, BxesWriteError> {
+        let offsets = match self.variants.get(&hash) {
+            Some(candidates) => candidates
+                .iter()
+                .map(|(offset, _)| *offset)
+                .collect::<Vec<_>>(),
+            None => return Ok(None),
+        };
+
+        for (index, offset) in offsets.into_iter().enumerate() {
+            let (existing_variant, end) = self.try_read_variant(offset)?;
+            if existing_variant.metadata == variant.metadata
+                && events_equal(&existing_variant.events, &variant.events)
+            {
+                return Ok(Some((index, end)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the variant at the given offset along with the position at which it ends.
+    fn try_read_variant(
+        &mut self,
+        offset: usize,
+    ) -> Result<(BxesTraceVariant, usize), BxesWriteError> {
+        self.variants_stream
+            .flush()
+            .map_err(|err| BxesWriteError::WriteError(err.into()))?;
+
+        self.variants_reader
+            .seek(offset)
+            .map_err(BxesWriteError::FailedToSeek)?;
+
+        let mut reader = BinaryReader::new(&mut self.variants_reader, Endian::Little);
+        let variant = match try_read_trace_variant(
+            &mut reader,
+            self.context.version,
+            self.context.checksum,
+            &self.values,
+            &self.kv_pairs,
+        ) {
+            Ok(variant) => variant,
+            Err(err) => return Err(BxesWriteError::FailedToReadLog(err)),
+        };
+
+        match reader.tell() {
+            Ok(end) => Ok((variant, end)),
+            Err(err) => Err(BxesWriteError::FailedToGetWriterPosition(err)),
+        }
+    }
+
+    fn write_value(&mut self, value: &Arc<BxesValue>) -> Result<(), BxesWriteError> {
+        // Nested values are written beforehand, so that each written value is added to `values`.
+        match value.as_ref() {
+            BxesValue::Artifact(artifact) => {
+                for item in &artifact.items {
+                    self.write_value(&item.model)?;
+                    self.write_value(&item.instance)?;
+                    self.write_value(&item.transition)?;
+                }
+            }
+            BxesValue::Drivers(drivers) => {
+                for driver in &drivers.drivers {
+                    self.write_value(&driver.name)?;
+                    self.write_value(&driver.driver_type)?;
+                }
+            }
+            _ => {}
+        }
+
+        let checksum = &mut self.values_checksum;
+        let written = execute_with_checksummed_writer(
+            &mut self.values_stream,
+            &self.context,
+            checksum,
+            |context| try_write_value(value, &mut context.borrow_mut()),
+        )?;
+
+        if written {
+            self.values.push(value.clone());
+        }
+
+        Ok(())
+    }
+
+    fn write_attributes(
+        &mut self,
+        attributes: &[(Arc<BxesValue>, Arc<BxesValue>)],
+    ) -> Result<(), BxesWriteError> {
+        for (key, value) in attributes {
+            self.write_value(key)?;
+            self.write_value(value)?;
+
+            let stream = &mut self.kv_pairs_stream;
+            let checksum = &mut self.kv_pairs_checksum;
+            let written =
+                execute_with_checksummed_writer(stream, &self.context, checksum, |context| {
+                    try_write_kv_pair(key, value, &mut context.borrow_mut())
+                })?;
+
+            if written {
+                let values_indices = self.context.values_indices.borrow();
+                self.kv_pairs
+                    .push((values_indices[key] as u32, values_indices[value] as u32));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the checksum which is written at the end of the section file.
+fn try_read_trailing_checksum(path: &Path) -> Result<u32, BxesWriteError> {
+    let read_checksum = || -> std::io::Result<u32> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::End(-(std::mem::size_of::<u32>() as i64)))?;
+
+        let mut bytes = [0; 4];
+        file.read_exact(&mut bytes)?;
+
+        Ok(u32::from_le_bytes(bytes))
+    };
+
+    match read_checksum() {
+        Ok(checksum) => Ok(checksum),
+        Err(err) => Err(BxesWriteError::FailedToOpenFileForWriting {
+            path: path.to_string_lossy().into_owned(),
+            source: err.into(),
+        }),
+    }
+}
+
+/// Checks that counts at `positions` of the section file take `PADDED_COUNT_SIZE` bytes,
+/// as readers also accept counts with minimal LEB128 encoding.
+fn try_check_padded_counts(
+    path: &Path,
+    version: u32,
+    positions: impl IntoIterator<Item = usize>,
+) -> Result<(), BxesWriteError> {
+    if version < constants::COMPACT_LENGTHS_VERSION {
+        return Ok(());
+    }
+
+    let read_count_bytes = |file: &mut File, position: usize| -> std::io::Result<Vec<u8>> {
+        file.seek(SeekFrom::Start(position as u64))?;
+
+        let mut bytes = vec![];
+        file.take(constants::PADDED_COUNT_SIZE as u64)
+            .read_to_end(&mut bytes)?;
+
+        Ok(bytes)
+    };
+
+    let to_error = |err: std::io::Error| BxesWriteError::FailedToOpenFileForWriting {
+        path: path.to_string_lossy().into_owned(),
+        source: err.into(),
+    };
+
+    let mut file = File::open(path).map_err(to_error)?;
+    for position in positions {
+        let bytes = read_count_bytes(&mut file, position).map_err(to_error)?;
+        let width = bytes
+            .iter()
+            .position(|byte| byte & 0x80 == 0)
+            .map(|index| index + 1);
+
+        if width != Some(constants::PADDED_COUNT_SIZE) {
+            return Err(BxesWriteError::UnpaddedCount {
+                path: path.to_string_lossy().into_owned(),
+                offset: position,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+impl Drop for MultipleFilesBxesAppender {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.try_finish();
+        }
+    }
+}
+
+/// Hashes of events do not distinguish empty attributes from `None`, as the former are read back as the latter.
+fn hash_variant(metadata: &[(Arc<BxesValue>, Arc<BxesValue>)], events: &[BxesEvent]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    metadata.hash(&mut hasher);
+    events.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+/// Empty attributes are read back as `None`, so they are considered equal.
+fn events_equal(first_events: &[BxesEvent], second_events: &[BxesEvent]) -> bool {
+    first_events.len() == second_events.len()
+        && first_events
+            .iter()
+            .zip(second_events)
+            .all(|(first, second)| {
+                first.name == second.name
+                    && first.timestamp == second.timestamp
+                    && first.lifecycle == second.lifecycle
+                    && first.attributes.as_deref().unwrap_or_default()
+                        == second.attributes.as_deref().unwrap_or_default()
+            })
+}
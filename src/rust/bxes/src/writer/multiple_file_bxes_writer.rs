@@ -9,9 +9,11 @@ use crate::{
 use super::{
     errors::BxesWriteError,
     write_context::BxesWriteContext,
+    write_options::BxesWriteOptions,
     writer_utils::{
-        try_open_write, try_write_key_values, try_write_log_metadata, try_write_u32_no_type_id,
-        try_write_values, try_write_variants,
+        checksum_for_version, string_encoding_for_version, try_check_version, try_open_write,
+        try_write_header, try_write_key_values, try_write_log_metadata, try_write_values,
+        try_write_variants,
     },
 };
 
@@ -22,7 +24,32 @@ pub fn write_bxes_multiple_files(
     log: &BxesEventLog,
     directory_path: &str,
 ) -> Result<(), BxesWriteError> {
-    let context = BxesWriteContext::empty();
+    write_bxes_multiple_files_with_options(log, directory_path, &BxesWriteOptions::default())
+}
+
+/// Writes the log with the given version, index order and checksums, the compression is not used
+/// as files of a multiple-files log are not compressed.
+pub fn write_bxes_multiple_files_with_options(
+    log: &BxesEventLog,
+    directory_path: &str,
+    options: &BxesWriteOptions,
+) -> Result<(), BxesWriteError> {
+    try_write_bxes_multiple_files(log, directory_path, options)
+}
+
+fn try_write_bxes_multiple_files(
+    log: &BxesEventLog,
+    directory_path: &str,
+    options: &BxesWriteOptions,
+) -> Result<(), BxesWriteError> {
+    let version = options.version.unwrap_or(log.version);
+    try_check_version(version)?;
+    let mut context = BxesWriteContext::empty(version);
+    context.index_order = options.index_order;
+    context.sort_strings = options.sort_strings;
+    context.string_encoding = string_encoding_for_version(version, options.string_encoding);
+    context.checksum = checksum_for_version(version, options.checksum);
+    context.padded_counts = true;
 
     let writer = |file_path: &'static str, action: Box<WriterFunc>| {
         execute_with_writer(log, directory_path, file_path, &context, action)
@@ -45,7 +72,7 @@ pub fn write_bxes_multiple_files(
 
     writer(
         constants::VARIANTS_FILE_NAME,
-        Box::new(|log, context| try_write_variants(log, context)),
+        Box::new(|log, context| try_write_variants(log, context).map(|_| ())),
     )
 }
 
@@ -66,6 +93,11 @@ where
     let mut file_stream = try_open_write(file_path)?;
     let mut writer = BinaryWriter::new(&mut file_stream, Endian::Little);
 
-    try_write_u32_no_type_id(&mut writer, log.version)?;
+    try_write_header(
+        &mut writer,
+        context.version,
+        context.string_encoding,
+        context.checksum,
+    )?;
     action(log, Rc::new(RefCell::new(context.with_writer(&mut writer))))
 }
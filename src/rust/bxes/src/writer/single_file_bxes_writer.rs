@@ -1,40 +1,158 @@
-use std::{cell::RefCell, rc::Rc};
-
-use tempfile::NamedTempFile;
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+    path::Path,
+    rc::Rc,
+};
 
 use crate::{
     binary_rw::core::{BinaryWriter, Endian},
-    models::BxesEventLog,
+    constants::ARCHIVE_LOG_FILE_NAME,
+    models::{BxesEventLog, BxesVariantIndexEntry},
+    utils::std_stream::StdStream,
 };
 
+use super::write_options::BxesCompression;
+
 use super::{
     errors::BxesWriteError,
     write_context::BxesWriteContext,
+    write_options::BxesWriteOptions,
     writer_utils::{
-        compress_to_archive, try_open_write, try_write_key_values, try_write_log_metadata,
-        try_write_values, try_write_variants, try_write_version,
+        checksum_for_version, string_encoding_for_version, try_check_version, try_write_compressed,
+        try_write_header, try_write_key_values, try_write_log_metadata, try_write_values,
+        try_write_variants, try_write_variants_index,
     },
 };
 
 pub fn write_bxes(path: &str, log: &BxesEventLog) -> Result<(), BxesWriteError> {
-    let raw_log_path = match NamedTempFile::new() {
+    write_bxes_with_options(path, log, &BxesWriteOptions::default())
+}
+
+pub fn write_bxes_with_options(
+    path: &str,
+    log: &BxesEventLog,
+    options: &BxesWriteOptions,
+) -> Result<(), BxesWriteError> {
+    try_write_bxes_file(path, log, options)
+}
+
+/// Writes the single-file log into the given stream and returns the stream back.
+pub fn write_bxes_to<W: Write + Seek>(writer: W, log: &BxesEventLog) -> Result<W, BxesWriteError> {
+    write_bxes_to_with_options(writer, log, &BxesWriteOptions::default())
+}
+
+pub fn write_bxes_to_with_options<W: Write + Seek>(
+    writer: W,
+    log: &BxesEventLog,
+    options: &BxesWriteOptions,
+) -> Result<W, BxesWriteError> {
+    let raw_log = try_write_raw_log_to_temp_file(log, options)?;
+    try_write_compressed(writer, ARCHIVE_LOG_FILE_NAME, raw_log, &options.compression)
+}
+
+pub fn write_bxes_to_bytes(log: &BxesEventLog) -> Result<Vec<u8>, BxesWriteError> {
+    Ok(write_bxes_to(Cursor::new(vec![]), log)?.into_inner())
+}
+
+fn try_write_bxes_file(
+    path: &str,
+    log: &BxesEventLog,
+    options: &BxesWriteOptions,
+) -> Result<(), BxesWriteError> {
+    let file = match File::create(path) {
         Ok(file) => file,
-        Err(_) => return Err(BxesWriteError::FailedToCreateTempFile),
+        Err(err) => {
+            return Err(BxesWriteError::FailedToOpenFileForWriting {
+                path: path.to_owned(),
+                source: err.into(),
+            })
+        }
     };
 
-    let raw_log_path = raw_log_path.path().to_str().unwrap();
-    let mut stream = try_open_write(raw_log_path)?;
-    let mut writer = BinaryWriter::new(&mut stream, Endian::Little);
+    if let BxesCompression::None = options.compression {
+        try_write_raw_log(file, log, options)?;
+        return Ok(());
+    }
 
-    let context = Rc::new(RefCell::new(BxesWriteContext::new(&mut writer)));
+    let raw_log = try_write_raw_log_to_temp_file(log, options)?;
+    let archive_log_name = Path::new(path).file_name().unwrap().to_str().unwrap();
+    try_write_compressed(file, archive_log_name, raw_log, &options.compression)?;
 
-    try_write_version(context.borrow_mut().writer.as_mut().unwrap(), log.version)?;
-    try_write_values(log, context.clone())?;
-    try_write_key_values(log, context.clone())?;
-    try_write_log_metadata(log, context.clone())?;
-    try_write_variants(log, context.clone())?;
+    Ok(())
+}
 
-    compress_to_archive(raw_log_path, path)?;
+/// Writes the raw log into a temporary file, so that it is not kept in memory
+/// before compression, and returns the file rewound to its start.
+fn try_write_raw_log_to_temp_file(
+    log: &BxesEventLog,
+    options: &BxesWriteOptions,
+) -> Result<impl Read, BxesWriteError> {
+    let temp_file = match tempfile::tempfile() {
+        Ok(file) => file,
+        Err(err) => return Err(BxesWriteError::FailedToCreateTempFile(err)),
+    };
 
-    Ok(())
+    let mut raw_log = try_write_raw_log(temp_file, log, options)?;
+    match raw_log.seek(SeekFrom::Start(0)) {
+        Ok(_) => Ok(raw_log),
+        Err(err) => Err(BxesWriteError::WriteError(err.into())),
+    }
+}
+
+/// Writes the raw log into the stream, which must be positioned at its start,
+/// and returns the stream back.
+fn try_write_raw_log<S: Write + Seek>(
+    raw_log: S,
+    log: &BxesEventLog,
+    options: &BxesWriteOptions,
+) -> Result<S, BxesWriteError> {
+    let version = options.version.unwrap_or(log.version);
+    try_check_version(version)?;
+
+    let mut stream = match StdStream::new(raw_log) {
+        Ok(stream) => stream,
+        Err(err) => return Err(BxesWriteError::WriteError(err.into())),
+    };
+
+    {
+        let mut writer = BinaryWriter::new(&mut stream, Endian::Little);
+        let mut context = BxesWriteContext::new(&mut writer, version);
+        context.index_order = options.index_order;
+        context.sort_strings = options.sort_strings;
+        context.string_encoding = string_encoding_for_version(version, options.string_encoding);
+        context.checksum = checksum_for_version(version, options.checksum);
+        let string_encoding = context.string_encoding;
+        let checksum = context.checksum;
+        let context = Rc::new(RefCell::new(context));
+
+        try_write_header(
+            context.borrow_mut().writer.as_mut().unwrap(),
+            version,
+            string_encoding,
+            checksum,
+        )?;
+        try_write_values(log, context.clone())?;
+        try_write_key_values(log, context.clone())?;
+        try_write_log_metadata(log, context.clone())?;
+        let variants_offsets = try_write_variants(log, context.clone())?;
+
+        if options.variants_index {
+            let entries = log
+                .variants
+                .iter()
+                .zip(variants_offsets)
+                .map(|(variant, offset)| BxesVariantIndexEntry {
+                    offset: offset as u64,
+                    events_count: variant.events.len() as u32,
+                })
+                .collect::<Vec<_>>();
+
+            let mut context = context.borrow_mut();
+            try_write_variants_index(context.writer.as_mut().unwrap(), checksum, &entries)?;
+        }
+    }
+
+    Ok(stream.into_inner())
 }
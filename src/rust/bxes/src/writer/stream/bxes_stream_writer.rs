@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use crate::{
+    models::{
+        BxesClassifier, BxesEvent, BxesExtension, BxesGlobal, BxesTraceVariantStart, BxesValue,
+    },
+    writer::errors::BxesWriteError,
+};
+
+pub enum BxesStreamEvent {
+    Value(Arc<BxesValue>),
+    KeyValue((Arc<BxesValue>, Arc<BxesValue>)),
+    TraceVariantStart(BxesTraceVariantStart),
+    Event(BxesEvent),
+    LogMetadataProperty((Arc<BxesValue>, Arc<BxesValue>)),
+    LogMetadataExtension(BxesExtension),
+    LogMetadataGlobal(BxesGlobal),
+    LogMetadataClassifier(BxesClassifier),
+}
+
+/// Writes a log incrementally from a sequence of stream events.
+///
+/// Values and key-value pairs are written as soon as they are first seen, events are written
+/// right after the last `TraceVariantStart`, log metadata is written when the writer is finished.
+/// The log is not complete (counts are not patched) until `finish` is called.
+pub trait BxesStreamWriter {
+    fn handle_event(&mut self, event: BxesStreamEvent) -> Result<(), BxesWriteError>;
+
+    fn finish(self) -> Result<(), BxesWriteError>
+    where
+        Self: Sized;
+}
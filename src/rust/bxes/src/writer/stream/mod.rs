@@ -0,0 +1,3 @@
+pub mod bxes_stream_writer;
+pub mod multiple_file_bxes_stream_writer;
+pub mod single_file_bxes_stream_writer;
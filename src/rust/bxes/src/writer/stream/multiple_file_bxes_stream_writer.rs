@@ -0,0 +1,503 @@
+use std::{cell::RefCell, io::Write, path::Path, rc::Rc, sync::Arc};
+
+use crate::{
+    binary_rw::core::{BinaryWriter, Endian},
+    constants,
+    models::{
+        BxesChecksum, BxesEvent, BxesEventLogMetadata, BxesTraceVariantStart, BxesValue,
+        BxesVariantIndexEntry,
+    },
+    utils::buffered_stream::BufferedWriteFileStream,
+    writer::{
+        errors::BxesWriteError,
+        write_context::BxesWriteContext,
+        write_options::{BxesIndexOrder, BxesWriteOptions},
+        writer_utils::{
+            checksum_for_version, header_size, string_encoding_for_version, try_check_version,
+            try_open_write, try_seek, try_tell_pos, try_write_event, try_write_event_log_metadata,
+            try_write_header, try_write_kv_pair, try_write_padded_count,
+            try_write_trace_variant_start, try_write_u32_no_type_id, try_write_value,
+            try_write_variants_count, update_count_checksum,
+        },
+    },
+};
+
+use super::bxes_stream_writer::{BxesStreamEvent, BxesStreamWriter};
+
+pub struct MultipleFilesBxesStreamWriter {
+    values_stream: BufferedWriteFileStream,
+    kv_pairs_stream: BufferedWriteFileStream,
+    metadata_stream: BufferedWriteFileStream,
+    variants_stream: BufferedWriteFileStream,
+    context: BxesWriteContext<'static>,
+    metadata: BxesEventLogMetadata,
+    variants_count: u32,
+    last_variant_events_count: Option<(usize, u32)>,
+    last_variant_offset: usize,
+    last_event_timestamp: Option<i64>,
+    /// Offsets of variants in the variants file along with their events counts,
+    /// they are recorded only for the variants index of single-file logs.
+    variants_index: Option<Vec<BxesVariantIndexEntry>>,
+    values_checksum: u32,
+    kv_pairs_checksum: u32,
+    variant_checksum: u32,
+}
+
+impl MultipleFilesBxesStreamWriter {
+    pub fn new(directory_path: &str, version: u32) -> Result<Self, BxesWriteError> {
+        let options = BxesWriteOptions {
+            version: Some(version),
+            ..BxesWriteOptions::default()
+        };
+
+        Self::with_options(directory_path, &options)
+    }
+
+    /// Writes the version of the options or the latest one, values are written as they come,
+    /// so options which reorder values are rejected.
+    pub fn with_options(
+        directory_path: &str,
+        options: &BxesWriteOptions,
+    ) -> Result<Self, BxesWriteError> {
+        try_check_stream_options(options)?;
+
+        let version = options.version.unwrap_or(constants::BXES_VERSION);
+        try_check_version(version)?;
+
+        let open_write = |file_name: &str| -> Result<BufferedWriteFileStream, BxesWriteError> {
+            let file_path = Path::new(directory_path).join(file_name);
+            let stream = try_open_write(file_path.to_str().unwrap())?;
+
+            Ok(BufferedWriteFileStream::new(stream, 1024 * 8))
+        };
+
+        let mut writer = Self {
+            values_stream: open_write(constants::VALUES_FILE_NAME)?,
+            kv_pairs_stream: open_write(constants::KEY_VALUES_FILE_NAME)?,
+            metadata_stream: open_write(constants::METADATA_FILE_NAME)?,
+            variants_stream: open_write(constants::VARIANTS_FILE_NAME)?,
+            context: BxesWriteContext::empty(version),
+            metadata: BxesEventLogMetadata {
+                extensions: None,
+                classifiers: None,
+                properties: None,
+                globals: None,
+            },
+            variants_count: 0,
+            last_variant_events_count: None,
+            last_variant_offset: 0,
+            last_event_timestamp: None,
+            variants_index: None,
+            values_checksum: 0,
+            kv_pairs_checksum: 0,
+            variant_checksum: 0,
+        };
+
+        writer.context.string_encoding =
+            string_encoding_for_version(version, options.string_encoding);
+        writer.context.checksum = checksum_for_version(version, options.checksum);
+        writer.context.padded_counts = true;
+        writer.write_initial_info(version)?;
+
+        Ok(writer)
+    }
+
+    fn write_initial_info(&mut self, version: u32) -> Result<(), BxesWriteError> {
+        for stream in [
+            &mut self.values_stream,
+            &mut self.kv_pairs_stream,
+            &mut self.metadata_stream,
+            &mut self.variants_stream,
+        ] {
+            let mut writer = BinaryWriter::new(stream, Endian::Little);
+            let string_encoding = self.context.string_encoding;
+            try_write_header(&mut writer, version, string_encoding, self.context.checksum)?;
+        }
+
+        // Counts are covered by checksums of sections, so checksums are updated when counts are written.
+        for (stream, checksum) in [
+            (&mut self.values_stream, &mut self.values_checksum),
+            (&mut self.kv_pairs_stream, &mut self.kv_pairs_checksum),
+        ] {
+            execute_with_checksummed_writer(stream, &self.context, checksum, |context| {
+                let writer = &mut context.borrow_mut();
+                try_write_padded_count(writer.writer.as_mut().unwrap(), version, 0)
+            })?;
+        }
+
+        execute_with_writer(&mut self.variants_stream, &self.context, |context| {
+            try_write_variants_count(context, 0)
+        })
+    }
+
+    fn write_value(&mut self, value: &Arc<BxesValue>) -> Result<(), BxesWriteError> {
+        let checksum = &mut self.values_checksum;
+        execute_with_checksummed_writer(
+            &mut self.values_stream,
+            &self.context,
+            checksum,
+            |context| {
+                try_write_value(value, &mut context.borrow_mut())?;
+                Ok(())
+            },
+        )
+    }
+
+    fn write_kv_pair(
+        &mut self,
+        key: &Arc<BxesValue>,
+        value: &Arc<BxesValue>,
+    ) -> Result<(), BxesWriteError> {
+        self.write_value(key)?;
+        self.write_value(value)?;
+
+        let checksum = &mut self.kv_pairs_checksum;
+        execute_with_checksummed_writer(
+            &mut self.kv_pairs_stream,
+            &self.context,
+            checksum,
+            |context| {
+                try_write_kv_pair(key, value, &mut context.borrow_mut())?;
+                Ok(())
+            },
+        )
+    }
+
+    fn write_attributes(
+        &mut self,
+        attributes: &[(Arc<BxesValue>, Arc<BxesValue>)],
+    ) -> Result<(), BxesWriteError> {
+        for (key, value) in attributes {
+            self.write_kv_pair(key, value)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_trace_variant_start(
+        &mut self,
+        variant_start: BxesTraceVariantStart,
+    ) -> Result<(), BxesWriteError> {
+        self.finish_last_variant_if_needed()?;
+        self.write_attributes(&variant_start.metadata)?;
+
+        self.variant_checksum = 0;
+        let checksum = &mut self.variant_checksum;
+        let (offset, events_count_position) = execute_with_checksummed_writer(
+            &mut self.variants_stream,
+            &self.context,
+            checksum,
+            |context| {
+                let offset = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+                try_write_trace_variant_start(
+                    context.clone(),
+                    variant_start.traces_count,
+                    &variant_start.metadata,
+                )?;
+
+                let mut context = context.borrow_mut();
+                let version = context.version;
+                let writer = context.writer.as_mut().unwrap();
+
+                let position = try_tell_pos(writer)?;
+                try_write_padded_count(writer, version, 0)?;
+
+                Ok((offset, position))
+            },
+        )?;
+
+        self.variants_count += 1;
+        self.last_variant_offset = offset;
+        self.last_variant_events_count = Some((events_count_position, 0));
+        self.last_event_timestamp = None;
+
+        Ok(())
+    }
+
+    fn handle_event_event(&mut self, event: BxesEvent) -> Result<(), BxesWriteError> {
+        if self.last_variant_events_count.is_none() {
+            return Err(BxesWriteError::EventOutsideOfTraceVariant);
+        }
+
+        self.write_value(&event.name)?;
+        if let Some(attributes) = event.attributes.as_ref() {
+            self.write_attributes(attributes)?;
+        }
+
+        let previous_timestamp = self.last_event_timestamp;
+        let checksum = &mut self.variant_checksum;
+        execute_with_checksummed_writer(
+            &mut self.variants_stream,
+            &self.context,
+            checksum,
+            |context| try_write_event(&event, previous_timestamp, context),
+        )?;
+
+        self.last_event_timestamp = Some(event.timestamp);
+
+        if let Some((_, events_count)) = self.last_variant_events_count.as_mut() {
+            *events_count += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the events count and the checksum of the last variant.
+    fn finish_last_variant_if_needed(&mut self) -> Result<(), BxesWriteError> {
+        if let Some((position, events_count)) = self.last_variant_events_count.take() {
+            if let Some(variants_index) = self.variants_index.as_mut() {
+                variants_index.push(BxesVariantIndexEntry {
+                    offset: self.last_variant_offset as u64,
+                    events_count,
+                });
+            }
+
+            let stream = &mut self.variants_stream;
+            let version = self.context.version;
+            let end = try_tell_pos(&mut BinaryWriter::new(stream, Endian::Little))?;
+            let checksum = update_count_checksum(
+                self.variant_checksum,
+                version,
+                0,
+                events_count,
+                position,
+                end,
+            );
+
+            write_count(stream, &self.context, position, events_count)?;
+            write_checksum(stream, &self.context, checksum)?;
+        }
+
+        Ok(())
+    }
+
+    /// Makes the writer record offsets and events counts of variants, which are returned
+    /// by `finish_with_variants_index`.
+    pub(crate) fn record_variants_index(&mut self) {
+        self.variants_index = Some(vec![]);
+    }
+
+    /// Same as `finish`, returns entries of the variants index with offsets in the variants file.
+    pub(crate) fn finish_with_variants_index(
+        mut self,
+    ) -> Result<Vec<BxesVariantIndexEntry>, BxesWriteError> {
+        self.flush_information()?;
+        Ok(self.variants_index.take().unwrap_or_default())
+    }
+
+    fn flush_information(&mut self) -> Result<(), BxesWriteError> {
+        execute_with_writer(&mut self.metadata_stream, &self.context, |context| {
+            try_write_event_log_metadata(&self.metadata, context)
+        })?;
+
+        self.finish_last_variant_if_needed()?;
+
+        let values_count = self.context.values_indices.borrow().len() as u32;
+        let kv_pairs_count = self.context.kv_indices.borrow().len() as u32;
+
+        let context = &self.context;
+        write_variants_count(&mut self.variants_stream, context, self.variants_count)?;
+        write_section_count(
+            &mut self.values_stream,
+            context,
+            0,
+            values_count,
+            self.values_checksum,
+        )?;
+        write_section_count(
+            &mut self.kv_pairs_stream,
+            context,
+            0,
+            kv_pairs_count,
+            self.kv_pairs_checksum,
+        )?;
+
+        for stream in [
+            &mut self.values_stream,
+            &mut self.kv_pairs_stream,
+            &mut self.metadata_stream,
+            &mut self.variants_stream,
+        ] {
+            stream
+                .flush()
+                .map_err(|err| BxesWriteError::WriteError(err.into()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl BxesStreamWriter for MultipleFilesBxesStreamWriter {
+    fn handle_event(&mut self, event: BxesStreamEvent) -> Result<(), BxesWriteError> {
+        match event {
+            BxesStreamEvent::Value(value) => self.write_value(&value),
+            BxesStreamEvent::KeyValue((key, value)) => self.write_kv_pair(&key, &value),
+            BxesStreamEvent::TraceVariantStart(variant_start) => {
+                self.handle_trace_variant_start(variant_start)
+            }
+            BxesStreamEvent::Event(event) => self.handle_event_event(event),
+            BxesStreamEvent::LogMetadataProperty((key, value)) => {
+                self.write_kv_pair(&key, &value)?;
+                push(&mut self.metadata.properties, (key, value));
+                Ok(())
+            }
+            BxesStreamEvent::LogMetadataExtension(extension) => {
+                self.write_value(&extension.name)?;
+                self.write_value(&extension.prefix)?;
+                self.write_value(&extension.uri)?;
+                push(&mut self.metadata.extensions, extension);
+                Ok(())
+            }
+            BxesStreamEvent::LogMetadataGlobal(global) => {
+                self.write_attributes(&global.globals)?;
+                push(&mut self.metadata.globals, global);
+                Ok(())
+            }
+            BxesStreamEvent::LogMetadataClassifier(classifier) => {
+                self.write_value(&classifier.name)?;
+                for key in &classifier.keys {
+                    self.write_value(key)?;
+                }
+
+                push(&mut self.metadata.classifiers, classifier);
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(mut self) -> Result<(), BxesWriteError> {
+        self.flush_information()
+    }
+}
+
+fn push<T>(vec: &mut Option<Vec<T>>, item: T) {
+    vec.get_or_insert_with(Vec::new).push(item);
+}
+
+pub(crate) fn execute_with_writer<T>(
+    stream: &mut BufferedWriteFileStream,
+    context: &BxesWriteContext,
+    action: impl FnOnce(Rc<RefCell<BxesWriteContext>>) -> Result<T, BxesWriteError>,
+) -> Result<T, BxesWriteError> {
+    let mut writer = BinaryWriter::new(stream, Endian::Little);
+    action(Rc::new(RefCell::new(context.with_writer(&mut writer))))
+}
+
+fn try_check_stream_options(options: &BxesWriteOptions) -> Result<(), BxesWriteError> {
+    if options.index_order != BxesIndexOrder::FirstSeen {
+        return Err(BxesWriteError::UnsupportedStreamOption(
+            "index order by frequency",
+        ));
+    }
+
+    if options.sort_strings {
+        return Err(BxesWriteError::UnsupportedStreamOption(
+            "sorting of strings",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Same as `execute_with_writer`, but the written bytes continue the given checksum
+/// when the log is written with checksums.
+pub(crate) fn execute_with_checksummed_writer<T>(
+    stream: &mut BufferedWriteFileStream,
+    context: &BxesWriteContext,
+    checksum: &mut u32,
+    action: impl FnOnce(Rc<RefCell<BxesWriteContext>>) -> Result<T, BxesWriteError>,
+) -> Result<T, BxesWriteError> {
+    let checksummed = context.checksum != BxesChecksum::None;
+    execute_with_writer(stream, context, |context| {
+        if checksummed {
+            context
+                .borrow_mut()
+                .writer
+                .as_mut()
+                .unwrap()
+                .start_checksum(*checksum);
+        }
+
+        let result = action(context.clone());
+        if let Some(updated_checksum) = context
+            .borrow_mut()
+            .writer
+            .as_mut()
+            .unwrap()
+            .stop_checksum()
+        {
+            *checksum = updated_checksum;
+        }
+
+        result
+    })
+}
+
+/// Writes the checksum at the current position when the log is written with checksums.
+pub(crate) fn write_checksum(
+    stream: &mut BufferedWriteFileStream,
+    context: &BxesWriteContext,
+    checksum: u32,
+) -> Result<(), BxesWriteError> {
+    if context.checksum == BxesChecksum::None {
+        return Ok(());
+    }
+
+    try_write_u32_no_type_id(&mut BinaryWriter::new(stream, Endian::Little), checksum)
+}
+
+/// Updates the padded count at the given position.
+pub(crate) fn write_count(
+    stream: &mut BufferedWriteFileStream,
+    context: &BxesWriteContext,
+    position: usize,
+    count: u32,
+) -> Result<(), BxesWriteError> {
+    let mut writer = BinaryWriter::new(stream, Endian::Little);
+
+    let current_position = try_tell_pos(&mut writer)?;
+    try_seek(&mut writer, position)?;
+    try_write_padded_count(&mut writer, context.version, count)?;
+    try_seek(&mut writer, current_position)
+}
+
+/// Updates the count of values or key-value pairs which follows the header of the file from
+/// `old_count` to `count` and writes the checksum of the section, which covers the count,
+/// at the current position.
+pub(crate) fn write_section_count(
+    stream: &mut BufferedWriteFileStream,
+    context: &BxesWriteContext,
+    old_count: u32,
+    count: u32,
+    checksum: u32,
+) -> Result<(), BxesWriteError> {
+    let version = context.version;
+    let position = header_size(version);
+    let end = try_tell_pos(&mut BinaryWriter::new(stream, Endian::Little))?;
+    let checksum = update_count_checksum(checksum, version, old_count, count, position, end);
+
+    write_checksum(stream, context, checksum)?;
+    write_count(stream, context, position, count)
+}
+
+/// Updates the number of variants which follows the header of the variants file along with its checksum.
+pub(crate) fn write_variants_count(
+    stream: &mut BufferedWriteFileStream,
+    context: &BxesWriteContext,
+    count: u32,
+) -> Result<(), BxesWriteError> {
+    execute_with_writer(stream, context, |context| {
+        let version = context.borrow().version;
+        let current_position = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+
+        try_seek(
+            context.borrow_mut().writer.as_mut().unwrap(),
+            header_size(version),
+        )?;
+        try_write_variants_count(context.clone(), count)?;
+        try_seek(
+            context.borrow_mut().writer.as_mut().unwrap(),
+            current_position,
+        )
+    })
+}
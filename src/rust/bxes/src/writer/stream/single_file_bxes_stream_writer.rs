@@ -0,0 +1,164 @@
+use std::{
+    fs::File,
+    io::{self, Seek, SeekFrom},
+    path::Path,
+};
+
+use tempfile::{NamedTempFile, TempDir};
+
+use crate::{
+    binary_rw::{
+        core::{BinaryWriter, Endian},
+        error::BinaryError,
+        file_stream::FileStream,
+    },
+    constants,
+    models::BxesVariantIndexEntry,
+    writer::{
+        errors::BxesWriteError,
+        write_options::BxesWriteOptions,
+        writer_utils::{
+            checksum_for_version, compress_to_archive, header_size, string_encoding_for_version,
+            try_open_write, try_tell_pos, try_write_header, try_write_variants_index,
+        },
+    },
+};
+
+use super::{
+    bxes_stream_writer::{BxesStreamEvent, BxesStreamWriter},
+    multiple_file_bxes_stream_writer::MultipleFilesBxesStreamWriter,
+};
+
+pub struct SingleFileBxesStreamWriter {
+    save_path: String,
+    version: u32,
+    options: BxesWriteOptions,
+    temp_dir: TempDir,
+    multiple_files_writer: MultipleFilesBxesStreamWriter,
+}
+
+impl SingleFileBxesStreamWriter {
+    pub fn new(save_path: &str, version: u32) -> Result<Self, BxesWriteError> {
+        let options = BxesWriteOptions {
+            version: Some(version),
+            ..BxesWriteOptions::default()
+        };
+
+        Self::with_options(save_path, options)
+    }
+
+    /// See `MultipleFilesBxesStreamWriter::with_options`.
+    pub fn with_options(
+        save_path: &str,
+        options: BxesWriteOptions,
+    ) -> Result<Self, BxesWriteError> {
+        let temp_dir = match TempDir::new() {
+            Ok(temp_dir) => temp_dir,
+            Err(err) => return Err(BxesWriteError::FailedToCreateTempDir(err)),
+        };
+
+        let mut multiple_files_writer = MultipleFilesBxesStreamWriter::with_options(
+            temp_dir.path().to_str().unwrap(),
+            &options,
+        )?;
+
+        let version = options.version.unwrap_or(constants::BXES_VERSION);
+
+        if options.variants_index {
+            multiple_files_writer.record_variants_index();
+        }
+
+        Ok(Self {
+            save_path: save_path.to_owned(),
+            version,
+            options,
+            temp_dir,
+            multiple_files_writer,
+        })
+    }
+}
+
+impl BxesStreamWriter for SingleFileBxesStreamWriter {
+    fn handle_event(&mut self, event: BxesStreamEvent) -> Result<(), BxesWriteError> {
+        self.multiple_files_writer.handle_event(event)
+    }
+
+    fn finish(self) -> Result<(), BxesWriteError> {
+        let variants_index = self.multiple_files_writer.finish_with_variants_index()?;
+        merge_files_into_one(
+            self.temp_dir.path(),
+            self.save_path.as_str(),
+            self.version,
+            &self.options,
+            variants_index,
+        )
+    }
+}
+
+/// Offsets of variants index entries are relative to the variants file,
+/// they are shifted to the position at which the variants file is copied.
+fn merge_files_into_one(
+    directory_path: &Path,
+    save_path: &str,
+    version: u32,
+    options: &BxesWriteOptions,
+    mut variants_index: Vec<BxesVariantIndexEntry>,
+) -> Result<(), BxesWriteError> {
+    let raw_log_path = match NamedTempFile::new() {
+        Ok(file) => file,
+        Err(err) => return Err(BxesWriteError::FailedToCreateTempFile(err)),
+    };
+
+    let raw_log_path = raw_log_path.path().to_str().unwrap();
+    let mut stream = try_open_write(raw_log_path)?;
+
+    let mut writer = BinaryWriter::new(&mut stream, Endian::Little);
+    try_write_header(
+        &mut writer,
+        version,
+        string_encoding_for_version(version, options.string_encoding),
+        checksum_for_version(version, options.checksum),
+    )?;
+
+    let copy_contents = |file_name: &str, stream: &mut FileStream| {
+        skip_header_and_copy_contents(&directory_path.join(file_name), version, stream)
+            .map_err(|err| BxesWriteError::WriteError(BinaryError::Io(err)))
+    };
+
+    for file_name in [
+        constants::VALUES_FILE_NAME,
+        constants::KEY_VALUES_FILE_NAME,
+        constants::METADATA_FILE_NAME,
+    ] {
+        copy_contents(file_name, &mut stream)?;
+    }
+
+    let variants_position = try_tell_pos(&mut BinaryWriter::new(&mut stream, Endian::Little))?;
+    copy_contents(constants::VARIANTS_FILE_NAME, &mut stream)?;
+
+    if options.variants_index {
+        let shift = (variants_position - header_size(version)) as u64;
+        for entry in variants_index.iter_mut() {
+            entry.offset += shift;
+        }
+
+        let mut writer = BinaryWriter::new(&mut stream, Endian::Little);
+        let checksum = checksum_for_version(version, options.checksum);
+        try_write_variants_index(&mut writer, checksum, &variants_index)?;
+    }
+
+    compress_to_archive(raw_log_path, save_path, &options.compression)
+}
+
+fn skip_header_and_copy_contents(
+    file_path: &Path,
+    version: u32,
+    writer: &mut impl io::Write,
+) -> io::Result<()> {
+    let mut file = File::open(file_path)?;
+    file.seek(SeekFrom::Start(header_size(version) as u64))?;
+
+    io::copy(&mut file, writer)?;
+
+    Ok(())
+}
@@ -1,27 +1,55 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 
-use crate::{binary_rw::core::BinaryWriter, models::BxesValue};
+use crate::{
+    binary_rw::core::BinaryWriter,
+    models::{BxesChecksum, BxesStringEncoding, BxesValue},
+};
+
+use super::write_options::BxesIndexOrder;
 
 pub struct BxesWriteContext<'b> {
-    pub values_indices: Rc<RefCell<HashMap<Rc<Box<BxesValue>>, usize>>>,
-    pub kv_indices: Rc<RefCell<HashMap<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>), usize>>>,
+    pub values_indices: Rc<RefCell<HashMap<Arc<BxesValue>, usize>>>,
+    pub kv_indices: Rc<RefCell<HashMap<(Arc<BxesValue>, Arc<BxesValue>), usize>>>,
+    /// The last written string value, the next string value is front-coded against it.
+    pub last_string: Rc<RefCell<Option<Arc<str>>>>,
     pub writer: Option<&'b mut BinaryWriter<'b>>,
+    pub version: u32,
+    pub index_order: BxesIndexOrder,
+    pub sort_strings: bool,
+    pub string_encoding: BxesStringEncoding,
+    pub checksum: BxesChecksum,
+    /// Counts of variants, traces and events are padded to the fixed width, so that they can be updated in place.
+    pub padded_counts: bool,
 }
 
 impl<'b> BxesWriteContext<'b> {
-    pub fn empty() -> Self {
+    pub fn empty(version: u32) -> Self {
         Self {
             values_indices: Rc::new(RefCell::new(HashMap::new())),
             kv_indices: Rc::new(RefCell::new(HashMap::new())),
+            last_string: Rc::new(RefCell::new(None)),
             writer: None,
+            version,
+            index_order: BxesIndexOrder::FirstSeen,
+            sort_strings: false,
+            string_encoding: BxesStringEncoding::Plain,
+            checksum: BxesChecksum::None,
+            padded_counts: false,
         }
     }
 
-    pub fn new(writer: &'b mut BinaryWriter<'b>) -> Self {
+    pub fn new(writer: &'b mut BinaryWriter<'b>, version: u32) -> Self {
         Self {
             values_indices: Rc::new(RefCell::new(HashMap::new())),
             kv_indices: Rc::new(RefCell::new(HashMap::new())),
+            last_string: Rc::new(RefCell::new(None)),
             writer: Some(writer),
+            version,
+            index_order: BxesIndexOrder::FirstSeen,
+            sort_strings: false,
+            string_encoding: BxesStringEncoding::Plain,
+            checksum: BxesChecksum::None,
+            padded_counts: false,
         }
     }
 
@@ -29,7 +57,14 @@ impl<'b> BxesWriteContext<'b> {
         BxesWriteContext {
             values_indices: self.values_indices.clone(),
             kv_indices: self.kv_indices.clone(),
+            last_string: self.last_string.clone(),
             writer: Some(writer),
+            version: self.version,
+            index_order: self.index_order,
+            sort_strings: self.sort_strings,
+            string_encoding: self.string_encoding,
+            checksum: self.checksum,
+            padded_counts: self.padded_counts,
         }
     }
 }
@@ -0,0 +1,59 @@
+use crate::models::{BxesChecksum, BxesStringEncoding};
+
+/// How the raw single-file log is stored, readers detect the container by its magic bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BxesCompression {
+    /// The raw log is stored as is, such logs can be memory-mapped.
+    None,
+    /// ZIP archive with one deflated entry, the level is in range 0..=9.
+    Deflate { level: i32 },
+    /// Zstandard frame, the level is in range 1..=22.
+    Zstd { level: i32 },
+    /// LZ4 frame.
+    Lz4,
+}
+
+/// How indices are assigned to values and key-value pairs, the format of the log is the same.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BxesIndexOrder {
+    /// Indices are assigned in the order in which values are first met in the log.
+    #[default]
+    FirstSeen,
+    /// The smallest indices are assigned to the most frequently referenced values,
+    /// so that their LEB128 encoded indices take less space.
+    Frequency,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BxesWriteOptions {
+    pub compression: BxesCompression,
+    pub index_order: BxesIndexOrder,
+    /// Whether strings are sorted among values whose LEB128 encoded indices have the same width,
+    /// so that front-coded strings share longer prefixes while indices keep their width.
+    pub sort_strings: bool,
+    /// The encoding of string values, strings are plain for versions before `FRONT_CODED_STRINGS_VERSION`.
+    pub string_encoding: BxesStringEncoding,
+    /// Checksums written after each section, they are omitted for versions before `CHECKSUMS_VERSION`.
+    pub checksum: BxesChecksum,
+    /// Whether the variants index is written after trace variants of single-file logs,
+    /// it is required by `BxesRandomAccessReader`.
+    pub variants_index: bool,
+    /// The version of the format to write instead of the version of the log, data which is
+    /// not supported by the target version (i.e. events lifecycle) is omitted.
+    /// Stream writers write the latest version when it is not set.
+    pub version: Option<u32>,
+}
+
+impl Default for BxesWriteOptions {
+    fn default() -> Self {
+        Self {
+            compression: BxesCompression::Deflate { level: 8 },
+            index_order: BxesIndexOrder::FirstSeen,
+            sort_strings: false,
+            string_encoding: BxesStringEncoding::Plain,
+            checksum: BxesChecksum::None,
+            variants_index: true,
+            version: None,
+        }
+    }
+}
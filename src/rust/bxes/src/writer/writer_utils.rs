@@ -1,10 +1,14 @@
 use num_traits::ToPrimitive;
 use std::{
     cell::RefCell,
-    fs::{self, File},
-    io::Write,
+    cmp::{Ordering, Reverse},
+    collections::{HashMap, HashSet},
+    fs::File,
+    hash::Hash,
+    io::{self, Read, Seek, Write},
     path::Path,
     rc::Rc,
+    sync::Arc,
 };
 use zip::{write::FileOptions, ZipWriter};
 
@@ -13,48 +17,256 @@ use crate::{
         core::{BinaryWriter, SeekStream},
         file_stream::FileStream,
     },
+    constants::{
+        CHECKSUMS_VERSION, COMPACT_LENGTHS_VERSION, DELTA_TIMESTAMPS_VERSION,
+        EVENT_LIFECYCLE_VERSION, FRONT_CODED_STRINGS_VERSION, PADDED_COUNT_SIZE,
+        SUPPORTED_VERSIONS, VARIANTS_INDEX_MARKER,
+    },
     models::{
-        BrafLifecycle, BxesArtifact, BxesClassifier, BxesDrivers, BxesEvent, BxesEventLog,
-        BxesExtension, BxesGlobal, BxesValue, Lifecycle, SoftwareEventType, StandardLifecycle,
+        BrafLifecycle, BxesArtifact, BxesChecksum, BxesClassifier, BxesDrivers, BxesEvent,
+        BxesEventLog, BxesEventLogMetadata, BxesExtension, BxesGlobal, BxesStringEncoding,
+        BxesTraceVariant, BxesValue, BxesVariantIndexEntry, Lifecycle, SoftwareEventType,
+        StandardLifecycle,
     },
+    read::errors::{BxesErrorLocation, BxesSection},
     type_ids::TypeIds,
 };
 
-use super::{errors::BxesWriteError, write_context::BxesWriteContext};
+use super::{
+    errors::BxesWriteError,
+    write_context::BxesWriteContext,
+    write_options::{BxesCompression, BxesIndexOrder},
+};
 
+/// Writes trace variants and returns offsets of the written variants.
 pub fn try_write_variants(
     log: &BxesEventLog,
     context: Rc<RefCell<BxesWriteContext>>,
+) -> Result<Vec<usize>, BxesWriteError> {
+    let mut offsets = vec![];
+
+    try_write_variants_count(context.clone(), log.variants.len() as u32)?;
+
+    for (variant_index, variant) in log.variants.iter().enumerate() {
+        let offset = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+        offsets.push(offset);
+
+        let location = BxesErrorLocation {
+            offset: Some(offset),
+            ..BxesErrorLocation::variant(variant_index)
+        };
+
+        try_write_checksummed(context.clone(), || {
+            try_write_variant(variant, context.clone())
+        })
+        .map_err(|err| err.located(location))?;
+    }
+
+    Ok(offsets)
+}
+
+fn try_write_variant(
+    variant: &BxesTraceVariant,
+    context: Rc<RefCell<BxesWriteContext>>,
 ) -> Result<(), BxesWriteError> {
-    write_collection_and_count(context.clone(), false, log.variants.len() as u32, || {
-        for variant in &log.variants {
-            try_write_u32_no_type_id(
-                context.borrow_mut().writer.as_mut().unwrap(),
-                variant.traces_count,
-            )?;
-
-            try_write_attributes(context.clone(), Some(&variant.metadata), false)?;
-
-            write_collection_and_count(
-                context.clone(),
-                false,
-                variant.events.len() as u32,
-                || {
-                    for event in &variant.events {
-                        try_write_event(event, context.clone())?;
-                    }
+    try_write_trace_variant_start(context.clone(), variant.traces_count, &variant.metadata)?;
 
-                    Ok(())
-                },
-            )?;
-        }
+    try_write_updatable_count(&context, variant.events.len() as u32)?;
 
-        Ok(())
+    let mut previous_timestamp = None;
+    for (event_index, event) in variant.events.iter().enumerate() {
+        try_write_event(event, previous_timestamp, context.clone()).map_err(|err| {
+            err.located(BxesErrorLocation {
+                event_index: Some(event_index),
+                ..BxesErrorLocation::default()
+            })
+        })?;
+
+        previous_timestamp = Some(event.timestamp);
+    }
+
+    Ok(())
+}
+
+/// The number of variants does not belong to any section, so it is followed by its own checksum
+/// when the log is written with checksums.
+pub fn try_write_variants_count(
+    context: Rc<RefCell<BxesWriteContext>>,
+    count: u32,
+) -> Result<(), BxesWriteError> {
+    try_write_checksummed(context.clone(), || {
+        try_write_updatable_count(&context, count)
     })
 }
 
+/// Writes the traces count and the metadata of the variant which precede the events count.
+pub fn try_write_trace_variant_start(
+    context: Rc<RefCell<BxesWriteContext>>,
+    traces_count: u32,
+    metadata: &Vec<(Arc<BxesValue>, Arc<BxesValue>)>,
+) -> Result<(), BxesWriteError> {
+    try_write_updatable_count(&context, traces_count)?;
+
+    let compact = uses_compact_lengths(&context);
+    try_write_attributes(context, Some(metadata), compact)
+}
+
+fn uses_compact_lengths(context: &Rc<RefCell<BxesWriteContext>>) -> bool {
+    context.borrow().version >= COMPACT_LENGTHS_VERSION
+}
+
+/// Writes a count or an index which is LEB128 encoded since `COMPACT_LENGTHS_VERSION`
+/// and is a fixed-width `u32` in previous versions.
+pub fn try_write_compact_u32(
+    writer: &mut BinaryWriter,
+    version: u32,
+    value: u32,
+) -> Result<(), BxesWriteError> {
+    if version >= COMPACT_LENGTHS_VERSION {
+        try_write_leb_128(writer, value)
+    } else {
+        try_write_u32_no_type_id(writer, value)
+    }
+}
+
+/// Writes a count of values, key-value pairs, variants, traces or events.
+pub fn try_write_count(
+    writer: &mut BinaryWriter,
+    version: u32,
+    count: u32,
+) -> Result<(), BxesWriteError> {
+    try_write_compact_u32(writer, version, count)
+}
+
+/// Same as `try_write_count`, but LEB128 encoded counts are padded to `PADDED_COUNT_SIZE` bytes,
+/// so that the count can be updated in place.
+pub fn try_write_padded_count(
+    writer: &mut BinaryWriter,
+    version: u32,
+    count: u32,
+) -> Result<(), BxesWriteError> {
+    let bytes = padded_count_bytes(version, count);
+    try_write(|| writer.write_bytes(&bytes))
+}
+
+/// Returns bytes of the count written by `try_write_padded_count`.
+pub fn padded_count_bytes(version: u32, count: u32) -> Vec<u8> {
+    if version < COMPACT_LENGTHS_VERSION {
+        return count.to_le_bytes().to_vec();
+    }
+
+    let mut bytes = vec![0; PADDED_COUNT_SIZE];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = ((count >> (7 * index)) & 0x7f) as u8;
+        if index < PADDED_COUNT_SIZE - 1 {
+            *byte |= 0x80;
+        }
+    }
+
+    bytes
+}
+
+/// Returns the checksum of the data in which the padded count at `position` is updated from
+/// `old_count` to `count`, `end` is the position at which the data ends.
+/// As CRC32C is linear, the checksum is updated without reading the data again.
+pub fn update_count_checksum(
+    checksum: u32,
+    version: u32,
+    old_count: u32,
+    count: u32,
+    position: usize,
+    end: usize,
+) -> u32 {
+    let old_bytes = padded_count_bytes(version, old_count);
+    let new_bytes = padded_count_bytes(version, count);
+    let length_after = end - position - new_bytes.len();
+
+    let difference = crc32c::crc32c(&old_bytes) ^ crc32c::crc32c(&new_bytes);
+    checksum ^ crc32c::crc32c_combine(difference, 0, length_after)
+}
+
+/// Counts of variants, traces and events are padded when the context requires them to be updatable in place.
+fn try_write_updatable_count(
+    context: &Rc<RefCell<BxesWriteContext>>,
+    count: u32,
+) -> Result<(), BxesWriteError> {
+    let mut context = context.borrow_mut();
+    let (version, padded) = (context.version, context.padded_counts);
+    let writer = context.writer.as_mut().unwrap();
+
+    match padded {
+        true => try_write_padded_count(writer, version, count),
+        false => try_write_count(writer, version, count),
+    }
+}
+
+/// Writes the section followed by its checksum when the log is written with checksums.
+fn try_write_checksummed<T>(
+    context: Rc<RefCell<BxesWriteContext>>,
+    write_func: impl FnOnce() -> Result<T, BxesWriteError>,
+) -> Result<T, BxesWriteError> {
+    if context.borrow().checksum == BxesChecksum::None {
+        return write_func();
+    }
+
+    context
+        .borrow_mut()
+        .writer
+        .as_mut()
+        .unwrap()
+        .start_checksum(0);
+    let result = write_func()?;
+
+    let mut context = context.borrow_mut();
+    let writer = context.writer.as_mut().unwrap();
+    let checksum = writer.stop_checksum().unwrap_or_default();
+    try_write_u32_no_type_id(writer, checksum)?;
+
+    Ok(result)
+}
+
+/// Locates errors which occur while writing the section at the start of the section.
+fn try_write_section<T>(
+    context: Rc<RefCell<BxesWriteContext>>,
+    section: BxesSection,
+    write_func: impl FnOnce() -> Result<T, BxesWriteError>,
+) -> Result<T, BxesWriteError> {
+    let offset = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+    write_func().map_err(|err| err.located(BxesErrorLocation::section(section, offset)))
+}
+
+/// The variants index is followed by its checksum when the log is written with checksums,
+/// the checksum covers entries of the index and its trailer.
+pub fn try_write_variants_index(
+    writer: &mut BinaryWriter,
+    checksum: BxesChecksum,
+    entries: &[BxesVariantIndexEntry],
+) -> Result<(), BxesWriteError> {
+    let index_offset = try_tell_pos(writer)?;
+    if checksum != BxesChecksum::None {
+        writer.start_checksum(0);
+    }
+
+    for entry in entries {
+        try_write_u64_no_type_id(writer, entry.offset)?;
+        try_write_u32_no_type_id(writer, entry.events_count)?;
+    }
+
+    try_write_u32_no_type_id(writer, entries.len() as u32)?;
+    try_write_u64_no_type_id(writer, index_offset as u64)?;
+    try_write_u32_no_type_id(writer, VARIANTS_INDEX_MARKER)?;
+
+    match writer.stop_checksum() {
+        Some(checksum) => try_write_u32_no_type_id(writer, checksum),
+        None => Ok(()),
+    }
+}
+
+/// `previous_timestamp` is the timestamp of the previous event of the trace variant,
+/// it is `None` for the first event.
 pub fn try_write_event(
     event: &BxesEvent,
+    previous_timestamp: Option<i64>,
     context: Rc<RefCell<BxesWriteContext>>,
 ) -> Result<(), BxesWriteError> {
     {
@@ -77,22 +289,63 @@ pub fn try_write_event(
         };
     }
 
-    try_write_i64_no_type_id(
+    let version = context.borrow().version;
+    try_write_event_timestamp(
         context.borrow_mut().writer.as_mut().unwrap(),
+        version,
         event.timestamp,
+        previous_timestamp,
     )?;
 
+    if version >= EVENT_LIFECYCLE_VERSION {
+        try_write_lifecycle(
+            context.borrow_mut().writer.as_mut().unwrap(),
+            &event.lifecycle,
+        )?;
+    }
+
     try_write_attributes(context, event.attributes.as_ref(), true)
 }
 
+fn try_write_event_timestamp(
+    writer: &mut BinaryWriter,
+    version: u32,
+    timestamp: i64,
+    previous_timestamp: Option<i64>,
+) -> Result<(), BxesWriteError> {
+    match previous_timestamp {
+        Some(previous_timestamp) if version >= DELTA_TIMESTAMPS_VERSION => {
+            let delta = timestamp.wrapping_sub(previous_timestamp);
+            try_write_leb_128_u64(writer, zigzag_encode(delta))
+        }
+        _ => try_write_i64_no_type_id(writer, timestamp),
+    }
+}
+
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
 pub fn try_write_log_metadata(
     log: &BxesEventLog,
     context: Rc<RefCell<BxesWriteContext>>,
 ) -> Result<(), BxesWriteError> {
-    try_write_properties(context.clone(), log.metadata.properties.as_ref())?;
-    try_write_extensions(context.clone(), log.metadata.extensions.as_ref())?;
-    try_write_globals(context.clone(), log.metadata.globals.as_ref())?;
-    try_write_classifiers(context.clone(), log.metadata.classifiers.as_ref())
+    try_write_section(context.clone(), BxesSection::Metadata, || {
+        try_write_event_log_metadata(&log.metadata, context)
+    })
+}
+
+/// Writes the metadata followed by its checksum when the log is written with checksums.
+pub fn try_write_event_log_metadata(
+    metadata: &BxesEventLogMetadata,
+    context: Rc<RefCell<BxesWriteContext>>,
+) -> Result<(), BxesWriteError> {
+    try_write_checksummed(context.clone(), || {
+        try_write_properties(context.clone(), metadata.properties.as_ref())?;
+        try_write_extensions(context.clone(), metadata.extensions.as_ref())?;
+        try_write_globals(context.clone(), metadata.globals.as_ref())?;
+        try_write_classifiers(context.clone(), metadata.classifiers.as_ref())
+    })
 }
 
 struct BinaryWriterWrapper<'a, 'b> {
@@ -119,25 +372,30 @@ impl<'a, 'b> BinaryWriterWrapper<'a, 'b> {
 }
 
 pub fn try_write_leb_128<'a>(writer: &mut BinaryWriter, value: u32) -> Result<(), BxesWriteError> {
+    try_write_leb_128_u64(writer, value as u64)
+}
+
+pub fn try_write_leb_128_u64(writer: &mut BinaryWriter, value: u64) -> Result<(), BxesWriteError> {
     let mut wrapper = BinaryWriterWrapper::new(writer);
 
-    match leb128::write::unsigned(&mut wrapper, value as u64) {
+    match leb128::write::unsigned(&mut wrapper, value) {
         Ok(_) => Ok(()),
-        Err(err) => Err(BxesWriteError::LebWriteError(err.to_string())),
+        Err(err) => Err(BxesWriteError::LebWriteError(err)),
     }
 }
 
 pub fn try_write_properties(
     context: Rc<RefCell<BxesWriteContext>>,
-    properties: Option<&Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>>,
+    properties: Option<&Vec<(Arc<BxesValue>, Arc<BxesValue>)>>,
 ) -> Result<(), BxesWriteError> {
-    write_collection_and_count(context.clone(), false, count(properties), || {
+    let compact = uses_compact_lengths(&context);
+    write_collection_and_count(context.clone(), compact, count(properties), || {
         if let Some(properties) = properties {
             for property in properties {
                 try_write_kv_index(
                     context.clone(),
                     &(property.0.clone(), property.1.clone()),
-                    false,
+                    compact,
                 )?;
             }
         }
@@ -158,7 +416,8 @@ pub fn try_write_globals(
     context: Rc<RefCell<BxesWriteContext>>,
     globals: Option<&Vec<BxesGlobal>>,
 ) -> Result<(), BxesWriteError> {
-    write_collection_and_count(context.clone(), false, count(globals), || {
+    let compact = uses_compact_lengths(&context);
+    write_collection_and_count(context.clone(), compact, count(globals), || {
         if let Some(globals) = globals {
             for global in globals {
                 try_write_enum_value_no_type_index(
@@ -168,14 +427,14 @@ pub fn try_write_globals(
 
                 write_collection_and_count(
                     context.clone(),
-                    false,
+                    compact,
                     global.globals.len() as u32,
                     || {
                         for global in &global.globals {
                             try_write_kv_index(
                                 context.clone(),
                                 &(global.0.clone(), global.1.clone()),
-                                false,
+                                compact,
                             )?;
                         }
 
@@ -191,7 +450,7 @@ pub fn try_write_globals(
 
 pub fn try_write_kv_index(
     context: Rc<RefCell<BxesWriteContext>>,
-    kv: &(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>),
+    kv: &(Arc<BxesValue>, Arc<BxesValue>),
     write_leb_128: bool,
 ) -> Result<(), BxesWriteError> {
     if !context.borrow().kv_indices.borrow().contains_key(kv) {
@@ -214,7 +473,8 @@ pub fn try_write_extensions(
     context: Rc<RefCell<BxesWriteContext>>,
     extensions: Option<&Vec<BxesExtension>>,
 ) -> Result<(), BxesWriteError> {
-    write_collection_and_count(context.clone(), false, count(extensions), || {
+    let compact = uses_compact_lengths(&context);
+    write_collection_and_count(context.clone(), compact, count(extensions), || {
         if let Some(extensions) = extensions {
             for extension in extensions {
                 try_write_value_index(context.clone(), extension.name.clone())?;
@@ -231,13 +491,14 @@ pub fn try_write_classifiers(
     context: Rc<RefCell<BxesWriteContext>>,
     classifiers: Option<&Vec<BxesClassifier>>,
 ) -> Result<(), BxesWriteError> {
-    write_collection_and_count(context.clone(), false, count(classifiers), || {
+    let compact = uses_compact_lengths(&context);
+    write_collection_and_count(context.clone(), compact, count(classifiers), || {
         if let Some(classifiers) = classifiers {
             for classifier in classifiers {
                 try_write_value_index(context.clone(), classifier.name.clone())?;
                 write_collection_and_count(
                     context.clone(),
-                    false,
+                    compact,
                     classifier.keys.len() as u32,
                     || {
                         for key in &classifier.keys {
@@ -256,7 +517,7 @@ pub fn try_write_classifiers(
 
 fn try_write_value_index(
     context: Rc<RefCell<BxesWriteContext>>,
-    value: Rc<Box<BxesValue>>,
+    value: Arc<BxesValue>,
 ) -> Result<(), BxesWriteError> {
     if !context
         .borrow()
@@ -272,13 +533,15 @@ fn try_write_value_index(
             .borrow()
             .get(&value)
             .unwrap() as u32;
-        try_write_u32_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), index)
+
+        let version = context.borrow().version;
+        try_write_compact_u32(context.borrow_mut().writer.as_mut().unwrap(), version, index)
     }
 }
 
 pub fn try_write_attributes(
     context: Rc<RefCell<BxesWriteContext>>,
-    attributes: Option<&Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>>,
+    attributes: Option<&Vec<(Arc<BxesValue>, Arc<BxesValue>)>>,
     write_leb_128_count: bool,
 ) -> Result<(), BxesWriteError> {
     write_collection_and_count(
@@ -304,52 +567,76 @@ pub fn try_write_attributes(
 pub fn try_write_key_values(
     log: &BxesEventLog,
     context: Rc<RefCell<BxesWriteContext>>,
+) -> Result<(), BxesWriteError> {
+    try_write_section(context.clone(), BxesSection::KeyValues, || {
+        try_write_checksummed(context.clone(), || {
+            write_kv_pairs_and_count(log, context.clone())
+        })
+    })
+}
+
+fn write_kv_pairs_and_count(
+    log: &BxesEventLog,
+    context: Rc<RefCell<BxesWriteContext>>,
 ) -> Result<(), BxesWriteError> {
     write_collection_and_count_after(context.clone(), || {
-        execute_with_kv_pairs(log, |value| {
-            match value {
-                ValueOrKeyValue::Value(_) => {}
-                ValueOrKeyValue::KeyValue((key, value)) => {
-                    if !context
-                        .borrow()
-                        .kv_indices
-                        .borrow()
-                        .contains_key(&(key.clone(), value.clone()))
-                    {
-                        let count = context.borrow().kv_indices.borrow().len();
-                        let key_index = *context.borrow().values_indices.borrow().get(key).unwrap();
-                        let value_index =
-                            *context.borrow().values_indices.borrow().get(value).unwrap();
-
-                        try_write_leb_128(
-                            context.borrow_mut().writer.as_mut().unwrap(),
-                            key_index as u32,
-                        )?;
-
-                        try_write_leb_128(
-                            context.borrow_mut().writer.as_mut().unwrap(),
-                            value_index as u32,
-                        )?;
-
-                        context
-                            .borrow_mut()
-                            .kv_indices
-                            .borrow_mut()
-                            .insert((key.clone(), value.clone()), count);
+        let index_order = context.borrow().index_order;
+        match index_order {
+            BxesIndexOrder::FirstSeen => execute_with_kv_pairs(log, |value| {
+                match value {
+                    ValueOrKeyValue::Value(_) => {}
+                    ValueOrKeyValue::KeyValue((key, value)) => {
+                        try_write_kv_pair(key, value, &mut context.borrow_mut())?;
                     }
                 }
-            }
 
-            Ok(())
-        })?;
+                Ok(())
+            })?,
+            BxesIndexOrder::Frequency => {
+                let mut kv_pairs = vec![];
+                execute_with_kv_pairs(log, |value| {
+                    if let ValueOrKeyValue::KeyValue(kv_pair) = value {
+                        kv_pairs.push(kv_pair);
+                    }
+
+                    Ok(())
+                })?;
+
+                for (key, value) in order_by_frequency(kv_pairs) {
+                    try_write_kv_pair(key, value, &mut context.borrow_mut())?;
+                }
+            }
+        }
 
         Ok(context.borrow().kv_indices.borrow().len() as u32)
     })
 }
 
+pub fn try_write_kv_pair(
+    key: &Arc<BxesValue>,
+    value: &Arc<BxesValue>,
+    context: &mut BxesWriteContext,
+) -> Result<bool, BxesWriteError> {
+    let kv = (key.clone(), value.clone());
+    if context.kv_indices.borrow().contains_key(&kv) {
+        return Ok(false);
+    }
+
+    let key_index = get_index(key, context)?;
+    let value_index = get_index(value, context)?;
+
+    try_write_leb_128(context.writer.as_mut().unwrap(), key_index)?;
+    try_write_leb_128(context.writer.as_mut().unwrap(), value_index)?;
+
+    let len = context.kv_indices.borrow().len();
+    context.kv_indices.borrow_mut().insert(kv, len);
+
+    Ok(true)
+}
+
 pub enum ValueOrKeyValue<'a> {
-    Value(&'a Rc<Box<BxesValue>>),
-    KeyValue((&'a Rc<Box<BxesValue>>, &'a Rc<Box<BxesValue>>)),
+    Value(&'a Arc<BxesValue>),
+    KeyValue((&'a Arc<BxesValue>, &'a Arc<BxesValue>)),
 }
 
 fn execute_with_kv_pairs<'a>(
@@ -399,7 +686,7 @@ fn execute_with_kv_pairs<'a>(
 }
 
 fn execute_with_attributes_kv_pairs<'a>(
-    attributes: &'a Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>,
+    attributes: &'a Vec<(Arc<BxesValue>, Arc<BxesValue>)>,
     action: &mut impl FnMut(ValueOrKeyValue<'a>) -> Result<(), BxesWriteError>,
 ) -> Result<(), BxesWriteError> {
     for (key, value) in attributes {
@@ -413,29 +700,182 @@ fn execute_with_attributes_kv_pairs<'a>(
 }
 
 pub fn try_write_version(writer: &mut BinaryWriter, version: u32) -> Result<(), BxesWriteError> {
+    try_check_version(version)?;
     try_write_u32_no_type_id(writer, version)
 }
 
+/// Writes the version followed by the encoding of strings since `FRONT_CODED_STRINGS_VERSION`
+/// and the kind of checksums since `CHECKSUMS_VERSION`.
+pub fn try_write_header(
+    writer: &mut BinaryWriter,
+    version: u32,
+    string_encoding: BxesStringEncoding,
+    checksum: BxesChecksum,
+) -> Result<(), BxesWriteError> {
+    try_write_version(writer, version)?;
+    if version >= FRONT_CODED_STRINGS_VERSION {
+        try_write_u8_no_type_id(writer, string_encoding.to_u8().unwrap())?;
+    }
+
+    if version >= CHECKSUMS_VERSION {
+        try_write_u8_no_type_id(writer, checksum.to_u8().unwrap())?;
+    }
+
+    Ok(())
+}
+
+/// Returns the size of the header which precedes the contents of each file of the log.
+pub fn header_size(version: u32) -> usize {
+    let mut size = std::mem::size_of::<u32>();
+    if version >= FRONT_CODED_STRINGS_VERSION {
+        size += std::mem::size_of::<u8>();
+    }
+
+    if version >= CHECKSUMS_VERSION {
+        size += std::mem::size_of::<u8>();
+    }
+
+    size
+}
+
+/// Strings are plain for versions of the format which do not support front coding.
+pub fn string_encoding_for_version(
+    version: u32,
+    string_encoding: BxesStringEncoding,
+) -> BxesStringEncoding {
+    if version >= FRONT_CODED_STRINGS_VERSION {
+        string_encoding
+    } else {
+        BxesStringEncoding::Plain
+    }
+}
+
+/// Checksums are omitted for versions of the format which do not support them.
+pub fn checksum_for_version(version: u32, checksum: BxesChecksum) -> BxesChecksum {
+    if version >= CHECKSUMS_VERSION {
+        checksum
+    } else {
+        BxesChecksum::None
+    }
+}
+
+pub fn try_check_version(version: u32) -> Result<(), BxesWriteError> {
+    match SUPPORTED_VERSIONS.contains(&version) {
+        true => Ok(()),
+        false => Err(BxesWriteError::UnsupportedVersion(version)),
+    }
+}
+
 pub fn try_write_values(
     log: &BxesEventLog,
     context: Rc<RefCell<BxesWriteContext>>,
+) -> Result<(), BxesWriteError> {
+    try_write_section(context.clone(), BxesSection::Values, || {
+        try_write_checksummed(context.clone(), || {
+            write_values_and_count(log, context.clone())
+        })
+    })
+}
+
+fn write_values_and_count(
+    log: &BxesEventLog,
+    context: Rc<RefCell<BxesWriteContext>>,
 ) -> Result<(), BxesWriteError> {
     write_collection_and_count_after(context.clone(), || {
-        execute_with_kv_pairs(log, |value| {
-            match value {
-                ValueOrKeyValue::Value(value) => {
+        let index_order = context.borrow().index_order;
+        let sort_strings = context.borrow().sort_strings;
+        match (index_order, sort_strings) {
+            (BxesIndexOrder::FirstSeen, false) => execute_with_kv_pairs(log, |value| {
+                match value {
+                    ValueOrKeyValue::Value(value) => {
+                        try_write_value(value, &mut context.borrow_mut())?;
+                    }
+                    ValueOrKeyValue::KeyValue(_) => {}
+                }
+
+                Ok(())
+            })?,
+            _ => {
+                let mut values = vec![];
+                execute_with_kv_pairs(log, |value| {
+                    if let ValueOrKeyValue::Value(value) = value {
+                        values.push(value);
+                    }
+
+                    Ok(())
+                })?;
+
+                let mut values = match index_order {
+                    BxesIndexOrder::Frequency => order_by_frequency(values),
+                    BxesIndexOrder::FirstSeen => distinct(values),
+                };
+
+                if sort_strings {
+                    sort_strings_by_index_width(&mut values);
+                }
+
+                for value in values {
                     try_write_value(value, &mut context.borrow_mut())?;
                 }
-                ValueOrKeyValue::KeyValue(_) => {}
             }
-
-            Ok(())
-        })?;
+        }
 
         Ok(context.borrow().values_indices.borrow().len() as u32)
     })
 }
 
+/// Returns distinct items, more frequent items go first,
+/// items with the same number of occurrences keep the order in which they were first met.
+fn order_by_frequency<T: Copy + Eq + Hash>(items: Vec<T>) -> Vec<T> {
+    let mut positions: HashMap<T, usize> = HashMap::new();
+    let mut counts: Vec<(T, usize)> = vec![];
+
+    for item in items {
+        match positions.get(&item) {
+            Some(position) => counts[*position].1 += 1,
+            None => {
+                positions.insert(item, counts.len());
+                counts.push((item, 1));
+            }
+        }
+    }
+
+    counts.sort_by_key(|(_, count)| Reverse(*count));
+    counts.into_iter().map(|(item, _)| item).collect()
+}
+
+/// Returns distinct items in the order in which they were first met.
+fn distinct<T: Copy + Eq + Hash>(items: Vec<T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(*item))
+        .collect()
+}
+
+/// Sorts string values and places them before other values whose LEB128 encoded indices
+/// have the same width, so that front-coded strings share longer prefixes while indices
+/// keep their width. Other values keep the order, so artifacts and drivers still follow
+/// the strings they reference.
+fn sort_strings_by_index_width(values: &mut [&Arc<BxesValue>]) {
+    let length = values.len();
+    let mut start = 0;
+    let mut end = 1 << 7;
+
+    while start < length {
+        let group = &mut values[start..end.min(length)];
+        group.sort_by(|first, second| match (first.as_ref(), second.as_ref()) {
+            (BxesValue::String(first), BxesValue::String(second)) => first.cmp(second),
+            (BxesValue::String(_), _) => Ordering::Less,
+            (_, BxesValue::String(_)) => Ordering::Greater,
+            _ => Ordering::Equal,
+        });
+
+        start = end;
+        end <<= 7;
+    }
+}
+
 fn write_collection_and_count(
     context: Rc<RefCell<BxesWriteContext>>,
     write_leb_128_count: bool,
@@ -456,42 +896,53 @@ fn write_collection_and_count_after(
     mut writer_action: impl FnMut() -> Result<u32, BxesWriteError>,
 ) -> Result<(), BxesWriteError> {
     let pos = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
+    let version = context.borrow().version;
 
-    try_write_u32_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), 0)?;
+    try_write_padded_count(context.borrow_mut().writer.as_mut().unwrap(), version, 0)?;
 
     let count = writer_action()?;
 
-    let current_pos = try_tell_pos(context.borrow_mut().writer.as_mut().unwrap())?;
-    try_seek(context.borrow_mut().writer.as_mut().unwrap(), pos)?;
+    let mut context = context.borrow_mut();
+    let writer = context.writer.as_mut().unwrap();
+    let current_pos = try_tell_pos(writer)?;
 
-    try_write_u32_no_type_id(context.borrow_mut().writer.as_mut().unwrap(), count)?;
+    // The count is covered by the checksum of the section, which is updated with the written count.
+    let checksum = writer.stop_checksum();
+    try_seek(writer, pos)?;
+    try_write_padded_count(writer, version, count)?;
+    try_seek(writer, current_pos)?;
 
-    try_seek(context.borrow_mut().writer.as_mut().unwrap(), current_pos)
+    if let Some(checksum) = checksum {
+        let checksum = update_count_checksum(checksum, version, 0, count, pos, current_pos);
+        writer.start_checksum(checksum);
+    }
+
+    Ok(())
 }
 
-fn try_seek(writer: &mut BinaryWriter, pos: usize) -> Result<(), BxesWriteError> {
+pub fn try_seek(writer: &mut BinaryWriter, pos: usize) -> Result<(), BxesWriteError> {
     match writer.seek(pos) {
         Ok(_) => Ok(()),
-        Err(err) => Err(BxesWriteError::FailedToSeek(err.to_string())),
+        Err(err) => Err(BxesWriteError::FailedToSeek(err)),
     }
 }
 
-fn try_tell_pos(writer: &mut BinaryWriter) -> Result<usize, BxesWriteError> {
+pub fn try_tell_pos(writer: &mut BinaryWriter) -> Result<usize, BxesWriteError> {
     match writer.tell() {
         Ok(pos) => Ok(pos),
-        Err(err) => Err(BxesWriteError::FailedToGetWriterPosition(err.to_string())),
+        Err(err) => Err(BxesWriteError::FailedToGetWriterPosition(err)),
     }
 }
 
 pub fn try_write_value(
-    value: &Rc<Box<BxesValue>>,
+    value: &Arc<BxesValue>,
     context: &mut BxesWriteContext,
 ) -> Result<bool, BxesWriteError> {
     if context.values_indices.borrow().contains_key(value) {
         return Ok(false);
     }
 
-    let value_ref = value.as_ref().as_ref();
+    let value_ref = value.as_ref();
     match value_ref {
         BxesValue::Int32(value) => try_write_i32(context.writer.as_mut().unwrap(), *value),
         BxesValue::Int64(value) => try_write_i64(context.writer.as_mut().unwrap(), *value),
@@ -500,7 +951,14 @@ pub fn try_write_value(
         BxesValue::Float32(value) => try_write_f32(context.writer.as_mut().unwrap(), *value),
         BxesValue::Float64(value) => try_write_f64(context.writer.as_mut().unwrap(), *value),
         BxesValue::String(value) => {
-            try_write_string(context.writer.as_mut().unwrap(), value.as_str())
+            let previous_string = context.last_string.replace(Some(value.clone()));
+            try_write_string(
+                context.writer.as_mut().unwrap(),
+                context.version,
+                context.string_encoding,
+                previous_string.as_deref(),
+                value,
+            )
         }
         BxesValue::Bool(value) => try_write_bool(context.writer.as_mut().unwrap(), *value),
         BxesValue::Timestamp(value) => {
@@ -562,27 +1020,29 @@ pub fn try_write_artifact(
         get_type_id_byte(TypeIds::Artifact),
     )?;
 
-    try_write_u32_no_type_id(
+    let version = context.version;
+    try_write_compact_u32(
         context.writer.as_mut().unwrap(),
+        version,
         artifact.items.len() as u32,
     )?;
 
     for artifact in &artifact.items {
         let index = get_index(&artifact.model, context)?;
-        try_write_u32_no_type_id(context.writer.as_mut().unwrap(), index as u32)?;
+        try_write_compact_u32(context.writer.as_mut().unwrap(), version, index)?;
 
         let index = get_index(&artifact.instance, context)?;
-        try_write_u32_no_type_id(context.writer.as_mut().unwrap(), index as u32)?;
+        try_write_compact_u32(context.writer.as_mut().unwrap(), version, index)?;
 
         let index = get_index(&artifact.transition, context)?;
-        try_write_u32_no_type_id(context.writer.as_mut().unwrap(), index as u32)?;
+        try_write_compact_u32(context.writer.as_mut().unwrap(), version, index)?;
     }
 
     Ok(())
 }
 
 fn get_index(
-    value: &Rc<Box<BxesValue>>,
+    value: &Arc<BxesValue>,
     context: &mut BxesWriteContext,
 ) -> Result<u32, BxesWriteError> {
     if let Some(index) = context.values_indices.borrow().get(value) {
@@ -593,7 +1053,7 @@ fn get_index(
 }
 
 fn get_or_write_value_index(
-    value: &Rc<Box<BxesValue>>,
+    value: &Arc<BxesValue>,
     context: &mut BxesWriteContext,
 ) -> Result<u32, BxesWriteError> {
     try_write_value(value, context)?;
@@ -616,8 +1076,10 @@ pub fn try_write_drivers(
         get_type_id_byte(TypeIds::Drivers),
     )?;
 
-    try_write_u32_no_type_id(
+    let version = context.version;
+    try_write_compact_u32(
         context.writer.as_mut().unwrap(),
+        version,
         drivers.drivers.len() as u32,
     )?;
 
@@ -625,10 +1087,10 @@ pub fn try_write_drivers(
         try_write_f64_no_type_id(context.writer.as_mut().unwrap(), driver.amount())?;
 
         let index = get_index(&driver.name, context)?;
-        try_write_u32_no_type_id(context.writer.as_mut().unwrap(), index)?;
+        try_write_compact_u32(context.writer.as_mut().unwrap(), version, index)?;
 
         let index = get_index(&driver.driver_type, context)?;
-        try_write_u32_no_type_id(context.writer.as_mut().unwrap(), index)?;
+        try_write_compact_u32(context.writer.as_mut().unwrap(), version, index)?;
     }
 
     Ok(())
@@ -662,6 +1124,13 @@ pub fn try_write_u32_no_type_id(
     try_write(|| writer.write_u32(value))
 }
 
+pub fn try_write_u64_no_type_id(
+    writer: &mut BinaryWriter,
+    value: u64,
+) -> Result<(), BxesWriteError> {
+    try_write(|| writer.write_u64(value))
+}
+
 pub fn try_write_u32(writer: &mut BinaryWriter, value: u32) -> Result<(), BxesWriteError> {
     try_write(|| {
         writer.write_u8(get_type_id_byte(TypeIds::U32))?;
@@ -708,12 +1177,37 @@ pub fn try_write_bool(writer: &mut BinaryWriter, value: bool) -> Result<(), Bxes
     })
 }
 
-pub fn try_write_string(writer: &mut BinaryWriter, value: &str) -> Result<(), BxesWriteError> {
-    try_write(|| {
-        writer.write_u8(get_type_id_byte(TypeIds::String))?;
-        writer.write_u64(value.len() as u64)?;
-        writer.write_bytes(value.as_bytes())
-    })
+/// `previous_string` is the last string value written to the values table,
+/// only the part of front-coded strings after the shared prefix is written.
+pub fn try_write_string(
+    writer: &mut BinaryWriter,
+    version: u32,
+    string_encoding: BxesStringEncoding,
+    previous_string: Option<&str>,
+    value: &str,
+) -> Result<(), BxesWriteError> {
+    try_write_u8_no_type_id(writer, get_type_id_byte(TypeIds::String))?;
+
+    let mut suffix = value.as_bytes();
+    if string_encoding == BxesStringEncoding::FrontCoded {
+        let previous_string = previous_string.unwrap_or_default().as_bytes();
+        let prefix_length = previous_string
+            .iter()
+            .zip(suffix)
+            .take_while(|(previous, current)| previous == current)
+            .count();
+
+        try_write_leb_128_u64(writer, prefix_length as u64)?;
+        suffix = &suffix[prefix_length..];
+    }
+
+    if version >= COMPACT_LENGTHS_VERSION {
+        try_write_leb_128_u64(writer, suffix.len() as u64)?;
+    } else {
+        try_write_u64_no_type_id(writer, suffix.len() as u64)?;
+    }
+
+    try_write(|| writer.write_bytes(suffix))
 }
 
 pub fn try_write_lifecycle(
@@ -779,35 +1273,86 @@ fn try_write(
 pub fn try_open_write(path: &str) -> Result<FileStream, BxesWriteError> {
     match FileStream::create(path) {
         Ok(stream) => Ok(stream),
-        Err(err) => Err(BxesWriteError::FailedToOpenFileForWriting(err.to_string())),
+        Err(err) => Err(BxesWriteError::FailedToOpenFileForWriting {
+            path: path.to_owned(),
+            source: err,
+        }),
     }
 }
 
-pub fn compress_to_archive(log_path: &str, save_path: &str) -> Result<(), BxesWriteError> {
-    let file = File::create(save_path).or_else(|_| Err(BxesWriteError::FailedToCreateArchive))?;
-    let mut zip_writer = ZipWriter::new(file);
-
+pub fn compress_to_archive(
+    log_path: &str,
+    save_path: &str,
+    compression: &BxesCompression,
+) -> Result<(), BxesWriteError> {
+    let file = File::create(save_path).map_err(|err| BxesWriteError::FailedToOpenFileForWriting {
+        path: save_path.to_owned(),
+        source: err.into(),
+    })?;
     let archive_log_name = Path::new(save_path).file_name().unwrap().to_str().unwrap();
+    let raw_log = File::open(log_path).map_err(|err| BxesWriteError::WriteError(err.into()))?;
+
+    try_write_compressed(file, archive_log_name, raw_log, compression)?;
+
+    Ok(())
+}
+
+/// Writes the raw log into the container of the given compression,
+/// the entry name is used only by ZIP archives.
+pub fn try_write_compressed<W: Write + Seek>(
+    mut writer: W,
+    archive_log_name: &str,
+    mut raw_log: impl Read,
+    compression: &BxesCompression,
+) -> Result<W, BxesWriteError> {
+    match compression {
+        BxesCompression::None => {
+            io::copy(&mut raw_log, &mut writer)
+                .map_err(|err| BxesWriteError::WriteError(err.into()))?;
+
+            Ok(writer)
+        }
+        BxesCompression::Deflate { level } => {
+            try_write_archive(writer, archive_log_name, raw_log, *level)
+        }
+        BxesCompression::Zstd { level } => {
+            zstd::stream::copy_encode(raw_log, &mut writer, *level)
+                .map_err(BxesWriteError::FailedToCompress)?;
+
+            Ok(writer)
+        }
+        BxesCompression::Lz4 => {
+            let mut encoder = lz4_flex::frame::FrameEncoder::new(writer);
+            io::copy(&mut raw_log, &mut encoder).map_err(BxesWriteError::FailedToCompress)?;
+
+            encoder
+                .finish()
+                .map_err(|err| BxesWriteError::FailedToCompress(err.into()))
+        }
+    }
+}
+
+/// Writes a zip archive with a single entry which contains the raw log.
+pub fn try_write_archive<W: Write + Seek>(
+    writer: W,
+    archive_log_name: &str,
+    mut raw_log: impl Read,
+    level: i32,
+) -> Result<W, BxesWriteError> {
+    let mut zip_writer = ZipWriter::new(writer);
+
     let options = FileOptions::default()
         .compression_method(zip::CompressionMethod::Deflated)
-        .compression_level(Some(8));
+        .compression_level(Some(level));
 
-    zip_writer
-        .start_file(archive_log_name, options)
-        .or_else(|_| Err(BxesWriteError::FailedToCreateArchive))?;
+    zip_writer.start_file(archive_log_name, options)?;
 
-    let bytes = fs::read(log_path).unwrap();
-    zip_writer
-        .write_all(&bytes)
-        .or_else(|_| Err(BxesWriteError::FailedToCreateArchive))?;
+    io::copy(&mut raw_log, &mut zip_writer)
+        .map_err(|err| BxesWriteError::FailedToCreateArchive(err.into()))?;
 
     zip_writer
         .flush()
-        .or_else(|_| Err(BxesWriteError::FailedToCreateArchive))?;
+        .map_err(|err| BxesWriteError::FailedToCreateArchive(err.into()))?;
 
-    zip_writer
-        .finish()
-        .or_else(|_| Err(BxesWriteError::FailedToCreateArchive))?;
-
-    Ok(())
+    Ok(zip_writer.finish()?)
 }
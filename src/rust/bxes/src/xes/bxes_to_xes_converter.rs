@@ -0,0 +1,284 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::Arc,
+};
+
+use crate::{models::*, read::single_file_bxes_reader::read_bxes};
+
+use super::{
+    errors::{BxesToXesConversionError, XesWriteError},
+    xes_constants::*,
+    xes_write_utils::*,
+};
+
+pub fn convert_bxes_to_xes(
+    bxes_path: &str,
+    xes_path: &str,
+) -> Result<(), BxesToXesConversionError> {
+    let log = read_bxes(bxes_path).map_err(BxesToXesConversionError::BxesRead)?;
+    write_xes(&log, xes_path).map_err(BxesToXesConversionError::XesWrite)
+}
+
+pub fn write_xes(log: &BxesEventLog, path: &str) -> Result<(), XesWriteError> {
+    let file = match File::create(path) {
+        Ok(file) => file,
+        Err(err) => return Err(XesWriteError::FailedToCreateFile(err)),
+    };
+
+    let mut writer = write_xes_to(log, BufWriter::new(file))?;
+    match writer.flush() {
+        Ok(()) => Ok(()),
+        Err(err) => Err(XesWriteError::IOError(err)),
+    }
+}
+
+/// Writes the log as XES, each trace variant is expanded into `traces_count` traces.
+pub fn write_xes_to<W: Write>(log: &BxesEventLog, writer: W) -> Result<W, XesWriteError> {
+    let mut writer = XesWriter::new(writer);
+
+    writer.write_declaration()?;
+    writer.start_element(
+        LOG_TAG_NAME,
+        &[
+            (XES_VERSION_ATTRIBUTE, XES_VERSION),
+            (XES_FEATURES_ATTRIBUTE, XES_NESTED_ATTRIBUTES_FEATURE),
+        ],
+    )?;
+
+    write_metadata(&mut writer, &log.metadata)?;
+
+    for variant in &log.variants {
+        for _ in 0..variant.traces_count {
+            write_trace(&mut writer, variant)?;
+        }
+    }
+
+    writer.end_element(LOG_TAG_NAME)?;
+
+    Ok(writer.into_inner())
+}
+
+fn write_metadata<W: Write>(
+    writer: &mut XesWriter<W>,
+    metadata: &BxesEventLogMetadata,
+) -> Result<(), XesWriteError> {
+    if let Some(extensions) = metadata.extensions.as_ref() {
+        for extension in extensions {
+            writer.empty_element(
+                EXTENSION_TAG_NAME,
+                &[
+                    (EXTENSION_NAME_ATTRIBUTE, string_value(&extension.name)?),
+                    (EXTENSION_PREFIX_ATTRIBUTE, string_value(&extension.prefix)?),
+                    (EXTENSION_URI_ATTRIBUTE, string_value(&extension.uri)?),
+                ],
+            )?;
+        }
+    }
+
+    if let Some(globals) = metadata.globals.as_ref() {
+        for global in globals {
+            write_global(writer, global)?;
+        }
+    }
+
+    if let Some(classifiers) = metadata.classifiers.as_ref() {
+        for classifier in classifiers {
+            let keys = classifier
+                .keys
+                .iter()
+                .map(|key| string_value(key))
+                .collect::<Result<Vec<&str>, XesWriteError>>()?
+                .join(" ");
+
+            writer.empty_element(
+                CLASSIFIER_TAG_NAME,
+                &[
+                    (CLASSIFIER_NAME_ATTRIBUTE, string_value(&classifier.name)?),
+                    (CLASSIFIER_KEYS_ATTRIBUTE, keys.as_str()),
+                ],
+            )?;
+        }
+    }
+
+    if let Some(properties) = metadata.properties.as_ref() {
+        for (key, value) in properties {
+            write_key_value(writer, key, value)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_global<W: Write>(
+    writer: &mut XesWriter<W>,
+    global: &BxesGlobal,
+) -> Result<(), XesWriteError> {
+    let scope = match global.entity_kind {
+        BxesGlobalKind::Event => "event",
+        BxesGlobalKind::Trace => "trace",
+        BxesGlobalKind::Log => "log",
+    };
+
+    writer.start_element(GLOBAL_TAG_NAME, &[(GLOBAL_SCOPE_ATTRIBUTE, scope)])?;
+    for (key, value) in &global.globals {
+        write_key_value(writer, key, value)?;
+    }
+
+    writer.end_element(GLOBAL_TAG_NAME)
+}
+
+fn write_trace<W: Write>(
+    writer: &mut XesWriter<W>,
+    variant: &BxesTraceVariant,
+) -> Result<(), XesWriteError> {
+    writer.start_element(TRACE_TAG_NAME, &[])?;
+
+    for (key, value) in &variant.metadata {
+        write_key_value(writer, key, value)?;
+    }
+
+    for event in &variant.events {
+        write_event(writer, event)?;
+    }
+
+    writer.end_element(TRACE_TAG_NAME)
+}
+
+fn write_event<W: Write>(
+    writer: &mut XesWriter<W>,
+    event: &BxesEvent,
+) -> Result<(), XesWriteError> {
+    writer.start_element(EVENT_TAG_NAME, &[])?;
+
+    writer.value_element(STRING_TAG_NAME, CONCEPT_NAME, string_value(&event.name)?)?;
+    writer.value_element(DATE_TAG_NAME, TIME_TIMESTAMP, &format_date(event.timestamp))?;
+
+    if event.lifecycle != Lifecycle::default() && !has_lifecycle_attribute(event) {
+        let name = match &event.lifecycle {
+            Lifecycle::Braf(lifecycle) => braf_lifecycle_name(lifecycle),
+            Lifecycle::Standard(lifecycle) => standard_lifecycle_name(lifecycle),
+        };
+
+        writer.value_element(STRING_TAG_NAME, LIFECYCLE_TRANSITION, name)?;
+    }
+
+    if let Some(attributes) = event.attributes.as_ref() {
+        for (key, value) in attributes {
+            write_key_value(writer, key, value)?;
+        }
+    }
+
+    writer.end_element(EVENT_TAG_NAME)
+}
+
+fn has_lifecycle_attribute(event: &BxesEvent) -> bool {
+    match event.attributes.as_ref() {
+        Some(attributes) => attributes.iter().any(|(key, _)| match key.as_ref() {
+            BxesValue::String(key) => key.as_ref() == LIFECYCLE_TRANSITION,
+            _ => false,
+        }),
+        None => false,
+    }
+}
+
+fn write_key_value<W: Write>(
+    writer: &mut XesWriter<W>,
+    key: &Arc<BxesValue>,
+    value: &Arc<BxesValue>,
+) -> Result<(), XesWriteError> {
+    let key = string_value(key)?;
+
+    match value.as_ref() {
+        BxesValue::Int32(value) => writer.value_element(INT_TAG_NAME, key, &value.to_string()),
+        BxesValue::Int64(value) => writer.value_element(INT_TAG_NAME, key, &value.to_string()),
+        BxesValue::Uint32(value) => writer.value_element(INT_TAG_NAME, key, &value.to_string()),
+        BxesValue::Uint64(value) => writer.value_element(INT_TAG_NAME, key, &value.to_string()),
+        BxesValue::Float32(value) => writer.value_element(FLOAT_TAG_NAME, key, &value.to_string()),
+        BxesValue::Float64(value) => writer.value_element(FLOAT_TAG_NAME, key, &value.to_string()),
+        BxesValue::String(value) => writer.value_element(STRING_TAG_NAME, key, value),
+        BxesValue::Bool(value) => writer.value_element(BOOL_TAG_NAME, key, &value.to_string()),
+        BxesValue::Timestamp(value) => {
+            writer.value_element(DATE_TAG_NAME, key, &format_date(*value))
+        }
+        BxesValue::BrafLifecycle(lifecycle) => {
+            writer.value_element(STRING_TAG_NAME, key, braf_lifecycle_name(lifecycle))
+        }
+        BxesValue::StandardLifecycle(lifecycle) => {
+            writer.value_element(STRING_TAG_NAME, key, standard_lifecycle_name(lifecycle))
+        }
+        BxesValue::Artifact(artifact) => write_artifact(writer, key, artifact),
+        BxesValue::Drivers(drivers) => write_drivers(writer, key, drivers),
+        BxesValue::Guid(guid) => writer.value_element(ID_TAG_NAME, key, &guid.to_string()),
+        BxesValue::SoftwareEventType(event_type) => {
+            writer.value_element(STRING_TAG_NAME, key, software_event_type_name(event_type))
+        }
+    }
+}
+
+fn write_artifact<W: Write>(
+    writer: &mut XesWriter<W>,
+    key: &str,
+    artifact: &BxesArtifact,
+) -> Result<(), XesWriteError> {
+    writer.start_element(LIST_TAG_NAME, &[(KEY_ATTRIBUTE_NAME, key)])?;
+    writer.start_element(VALUES_TAG_NAME, &[])?;
+
+    for item in &artifact.items {
+        writer.start_element(
+            STRING_TAG_NAME,
+            &[
+                (KEY_ATTRIBUTE_NAME, ARTIFACT_ITEM_MODEL),
+                (VALUE_ATTRIBUTE_NAME, string_value(&item.model)?),
+            ],
+        )?;
+
+        writer.value_element(
+            STRING_TAG_NAME,
+            ARTIFACT_ITEM_INSTANCE,
+            string_value(&item.instance)?,
+        )?;
+
+        writer.value_element(
+            STRING_TAG_NAME,
+            ARTIFACT_ITEM_TRANSITION,
+            string_value(&item.transition)?,
+        )?;
+
+        writer.end_element(STRING_TAG_NAME)?;
+    }
+
+    writer.end_element(VALUES_TAG_NAME)?;
+    writer.end_element(LIST_TAG_NAME)
+}
+
+fn write_drivers<W: Write>(
+    writer: &mut XesWriter<W>,
+    key: &str,
+    drivers: &BxesDrivers,
+) -> Result<(), XesWriteError> {
+    writer.start_element(LIST_TAG_NAME, &[(KEY_ATTRIBUTE_NAME, key)])?;
+    writer.start_element(VALUES_TAG_NAME, &[])?;
+
+    for driver in &drivers.drivers {
+        writer.start_element(
+            STRING_TAG_NAME,
+            &[
+                (KEY_ATTRIBUTE_NAME, COST_DRIVER),
+                (VALUE_ATTRIBUTE_NAME, string_value(&driver.name)?),
+            ],
+        )?;
+
+        writer.value_element(FLOAT_TAG_NAME, COST_AMOUNT, &driver.amount().to_string())?;
+        writer.value_element(
+            STRING_TAG_NAME,
+            COST_TYPE,
+            string_value(&driver.driver_type)?,
+        )?;
+
+        writer.end_element(STRING_TAG_NAME)?;
+    }
+
+    writer.end_element(VALUES_TAG_NAME)?;
+    writer.end_element(LIST_TAG_NAME)
+}
@@ -0,0 +1,54 @@
+use std::io;
+
+use quick_xml::events::attributes::AttrError;
+use thiserror::Error;
+
+use crate::{models::BxesValue, read::errors::BxesReadError, writer::errors::BxesWriteError};
+
+#[derive(Debug, Error)]
+pub enum XesReadError {
+    #[error("Failed to open file")]
+    FailedToOpenFile(#[source] io::Error),
+    #[error("Failed to read xml")]
+    XmlError(#[from] quick_xml::Error),
+    #[error("Failed to read xml attribute")]
+    XmlAttributeError(#[from] AttrError),
+    #[error("Failed to find attribute {1} in tag {0}")]
+    MissingAttribute(String, String),
+    #[error("Attribute {0} contains key and no value")]
+    AttributeWithoutValue(String),
+    #[error("Failed to parse value {1} for type {0}")]
+    FailedToParseValue(String, String),
+    #[error("Unknown scope attribute value {0}")]
+    UnknownGlobalScope(String),
+    #[error("Failed to find name of an event")]
+    MissingEventName,
+}
+
+#[derive(Debug, Error)]
+pub enum XesToBxesConversionError {
+    #[error("Failed to read xes log")]
+    XesRead(#[from] XesReadError),
+    #[error("Failed to write bxes log")]
+    BxesWrite(#[from] BxesWriteError),
+}
+
+#[derive(Debug, Error)]
+pub enum XesWriteError {
+    #[error("Failed to create file")]
+    FailedToCreateFile(#[source] io::Error),
+    #[error("Failed to write xes")]
+    IOError(#[source] io::Error),
+    #[error("Failed to write xml")]
+    XmlError(#[from] quick_xml::Error),
+    #[error("Expected string value, got {0:?}")]
+    ExpectedString(BxesValue),
+}
+
+#[derive(Debug, Error)]
+pub enum BxesToXesConversionError {
+    #[error("Failed to read bxes log")]
+    BxesRead(#[from] BxesReadError),
+    #[error("Failed to write xes log")]
+    XesWrite(#[from] XesWriteError),
+}
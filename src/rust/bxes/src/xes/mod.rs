@@ -0,0 +1,7 @@
+pub mod bxes_to_xes_converter;
+pub mod errors;
+pub mod xes_constants;
+pub mod xes_read_context;
+pub mod xes_read_utils;
+pub mod xes_to_bxes_converter;
+pub mod xes_write_utils;
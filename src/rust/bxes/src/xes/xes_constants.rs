@@ -0,0 +1,123 @@
+use crate::models::{BrafLifecycle, StandardLifecycle};
+
+pub const DEFAULT_NAME: &str = "name";
+
+pub const LOG_TAG_NAME: &str = "log";
+pub const TRACE_TAG_NAME: &str = "trace";
+pub const EVENT_TAG_NAME: &str = "event";
+pub const EXTENSION_TAG_NAME: &str = "extension";
+pub const CLASSIFIER_TAG_NAME: &str = "classifier";
+pub const GLOBAL_TAG_NAME: &str = "global";
+pub const LIST_TAG_NAME: &str = "list";
+pub const VALUES_TAG_NAME: &str = "values";
+
+pub const CLASSIFIER_NAME_ATTRIBUTE: &str = DEFAULT_NAME;
+pub const CLASSIFIER_KEYS_ATTRIBUTE: &str = "keys";
+
+pub const EXTENSION_NAME_ATTRIBUTE: &str = DEFAULT_NAME;
+pub const EXTENSION_PREFIX_ATTRIBUTE: &str = "prefix";
+pub const EXTENSION_URI_ATTRIBUTE: &str = "uri";
+
+pub const GLOBAL_SCOPE_ATTRIBUTE: &str = "scope";
+
+pub const XES_VERSION_ATTRIBUTE: &str = "xes.version";
+pub const XES_FEATURES_ATTRIBUTE: &str = "xes.features";
+pub const XES_VERSION: &str = "1.0";
+pub const XES_NESTED_ATTRIBUTES_FEATURE: &str = "nested-attributes";
+
+pub const STRING_TAG_NAME: &str = "string";
+pub const DATE_TAG_NAME: &str = "date";
+pub const INT_TAG_NAME: &str = "int";
+pub const FLOAT_TAG_NAME: &str = "float";
+pub const BOOL_TAG_NAME: &str = "boolean";
+pub const ID_TAG_NAME: &str = "id";
+
+pub const KEY_ATTRIBUTE_NAME: &str = "key";
+pub const VALUE_ATTRIBUTE_NAME: &str = "value";
+
+pub const CONCEPT_NAME: &str = "concept:name";
+pub const TIME_TIMESTAMP: &str = "time:timestamp";
+pub const LIFECYCLE_TRANSITION: &str = "lifecycle:transition";
+
+pub const ARTIFACT_MOVES: &str = "artifactlifecycle:moves";
+pub const ARTIFACT_ITEM_MODEL: &str = "artifactlifecycle:model";
+pub const ARTIFACT_ITEM_INSTANCE: &str = "artifactlifecycle:instance";
+pub const ARTIFACT_ITEM_TRANSITION: &str = "artifactlifecycle:transition";
+
+pub const COST_DRIVERS: &str = "cost:drivers";
+pub const COST_DRIVER: &str = "cost:driver";
+pub const COST_AMOUNT: &str = "cost:amount";
+pub const COST_TYPE: &str = "cost:type";
+
+pub const STANDARD_LIFECYCLE_NAMES: &[(&str, StandardLifecycle)] = &[
+    ("unspecified", StandardLifecycle::Unspecified),
+    ("assign", StandardLifecycle::Assign),
+    ("ate_abort", StandardLifecycle::AteAbort),
+    ("autoskip", StandardLifecycle::Autoskip),
+    ("complete", StandardLifecycle::Complete),
+    ("manualskip", StandardLifecycle::ManualSkip),
+    ("pi_abort", StandardLifecycle::PiAbort),
+    ("reassign", StandardLifecycle::ReAssign),
+    ("resume", StandardLifecycle::Resume),
+    ("schedule", StandardLifecycle::Schedule),
+    ("start", StandardLifecycle::Start),
+    ("suspend", StandardLifecycle::Suspend),
+    ("unknown", StandardLifecycle::Unknown),
+    ("withdraw", StandardLifecycle::Withdraw),
+];
+
+pub const BRAF_LIFECYCLE_NAMES: &[(&str, BrafLifecycle)] = &[
+    ("Unspecified", BrafLifecycle::Unspecified),
+    ("Closed", BrafLifecycle::Closed),
+    ("Closed.Cancelled", BrafLifecycle::ClosedCancelled),
+    (
+        "Closed.Cancelled.Aborted",
+        BrafLifecycle::ClosedCancelledAborted,
+    ),
+    (
+        "Closed.Cancelled.Error",
+        BrafLifecycle::ClosedCancelledError,
+    ),
+    (
+        "Closed.Cancelled.Exited",
+        BrafLifecycle::ClosedCancelledExited,
+    ),
+    (
+        "Closed.Cancelled.Obsolete",
+        BrafLifecycle::ClosedCancelledObsolete,
+    ),
+    (
+        "Closed.Cancelled.Terminated",
+        BrafLifecycle::ClosedCancelledTerminated,
+    ),
+    ("Completed", BrafLifecycle::Completed),
+    ("Completed.Failed", BrafLifecycle::CompletedFailed),
+    ("Completed.Success", BrafLifecycle::CompletedSuccess),
+    ("Open", BrafLifecycle::Open),
+    ("Open.NotRunning", BrafLifecycle::OpenNotRunning),
+    (
+        "Open.NotRunning.Assigned",
+        BrafLifecycle::OpenNotRunningAssigned,
+    ),
+    (
+        "Open.NotRunning.Reserved",
+        BrafLifecycle::OpenNotRunningReserved,
+    ),
+    (
+        "Open.NotRunning.Suspended.Assigned",
+        BrafLifecycle::OpenNotRunningSuspendedAssigned,
+    ),
+    (
+        "Open.NotRunning.Suspended.Reserved",
+        BrafLifecycle::OpenNotRunningSuspendedReserved,
+    ),
+    ("Open.Running", BrafLifecycle::OpenRunning),
+    (
+        "Open.Running.InProgress",
+        BrafLifecycle::OpenRunningInProgress,
+    ),
+    (
+        "Open.Running.Suspended",
+        BrafLifecycle::OpenRunningSuspended,
+    ),
+];
@@ -0,0 +1,26 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::models::BxesValue;
+
+#[derive(Default)]
+pub struct XesReadContext {
+    values: HashMap<BxesValue, Arc<BxesValue>>,
+    pub event_defaults: HashMap<String, BxesValue>,
+}
+
+impl XesReadContext {
+    pub fn get_or_create_value(&mut self, value: BxesValue) -> Arc<BxesValue> {
+        if let Some(existing_value) = self.values.get(&value) {
+            return existing_value.clone();
+        }
+
+        let new_value = Arc::new(value.clone());
+        self.values.insert(value, new_value.clone());
+
+        new_value
+    }
+
+    pub fn get_or_create_string_value(&mut self, value: &str) -> Arc<BxesValue> {
+        self.get_or_create_value(BxesValue::String(Arc::from(value)))
+    }
+}
@@ -0,0 +1,365 @@
+use std::{io::BufRead, sync::Arc};
+
+use chrono::{DateTime, NaiveDateTime};
+use quick_xml::{events::Event, Reader};
+
+use crate::models::{BxesArtifact, BxesArtifactItem, BxesDriver, BxesDrivers, BxesValue};
+
+use super::{errors::XesReadError, xes_constants::*, xes_read_context::XesReadContext};
+
+pub struct XesElement {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub is_empty: bool,
+}
+
+impl XesElement {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute_name, _)| attribute_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn required_attribute(&self, name: &str) -> Result<&str, XesReadError> {
+        match self.attribute(name) {
+            Some(value) => Ok(value),
+            None => Err(XesReadError::MissingAttribute(
+                self.name.clone(),
+                name.to_owned(),
+            )),
+        }
+    }
+}
+
+pub struct XesElementTree {
+    pub element: XesElement,
+    pub children: Vec<XesElementTree>,
+}
+
+pub enum XesNode {
+    Start(XesElement),
+    End,
+    Eof,
+}
+
+pub struct XesReader<R: BufRead> {
+    reader: Reader<R>,
+    buffer: Vec<u8>,
+}
+
+impl<R: BufRead> XesReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: Reader::from_reader(reader),
+            buffer: vec![],
+        }
+    }
+
+    /// Returns the next start (or empty) element or the end of an element,
+    /// all other xml nodes (declarations, text, comments) are skipped.
+    pub fn next_node(&mut self) -> Result<XesNode, XesReadError> {
+        loop {
+            self.buffer.clear();
+            let event = match self.reader.read_event_into(&mut self.buffer) {
+                Ok(event) => event,
+                Err(err) => return Err(XesReadError::XmlError(err)),
+            };
+
+            return match event {
+                Event::Start(start) => Ok(XesNode::Start(create_element(&start, false)?)),
+                Event::Empty(start) => Ok(XesNode::Start(create_element(&start, true)?)),
+                Event::End(_) => Ok(XesNode::End),
+                Event::Eof => Ok(XesNode::Eof),
+                _ => continue,
+            };
+        }
+    }
+
+    pub fn skip_element(&mut self, element: &XesElement) -> Result<(), XesReadError> {
+        if element.is_empty {
+            return Ok(());
+        }
+
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next_node()? {
+                XesNode::Start(child) => {
+                    if !child.is_empty {
+                        depth += 1;
+                    }
+                }
+                XesNode::End => depth -= 1,
+                XesNode::Eof => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn read_element_tree(
+        &mut self,
+        element: XesElement,
+    ) -> Result<XesElementTree, XesReadError> {
+        let mut children = vec![];
+
+        if !element.is_empty {
+            while let XesNode::Start(child) = self.next_node()? {
+                children.push(self.read_element_tree(child)?);
+            }
+        }
+
+        Ok(XesElementTree { element, children })
+    }
+}
+
+fn create_element(
+    start: &quick_xml::events::BytesStart,
+    is_empty: bool,
+) -> Result<XesElement, XesReadError> {
+    let name = String::from_utf8_lossy(start.name().as_ref()).to_string();
+    let mut attributes = vec![];
+
+    for attribute in start.attributes() {
+        let attribute = match attribute {
+            Ok(attribute) => attribute,
+            Err(err) => return Err(XesReadError::XmlAttributeError(err)),
+        };
+
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).to_string();
+        let value = match attribute.unescape_value() {
+            Ok(value) => value.to_string(),
+            Err(err) => return Err(XesReadError::XmlError(err)),
+        };
+
+        attributes.push((key, value));
+    }
+
+    Ok(XesElement {
+        name,
+        attributes,
+        is_empty,
+    })
+}
+
+pub fn is_attribute_tag(tag_name: &str) -> bool {
+    matches!(
+        tag_name,
+        STRING_TAG_NAME
+            | DATE_TAG_NAME
+            | INT_TAG_NAME
+            | FLOAT_TAG_NAME
+            | BOOL_TAG_NAME
+            | ID_TAG_NAME
+            | LIST_TAG_NAME
+    )
+}
+
+/// Parses an attribute tag (the element is consumed including its children).
+/// Returns `None` for tags without key and value and for unsupported lists.
+pub fn parse_attribute<R: BufRead>(
+    reader: &mut XesReader<R>,
+    element: XesElement,
+    context: &mut XesReadContext,
+) -> Result<Option<(String, BxesValue)>, XesReadError> {
+    if element.name == LIST_TAG_NAME {
+        let key = element.required_attribute(KEY_ATTRIBUTE_NAME)?.to_owned();
+        let tree = reader.read_element_tree(element)?;
+
+        return Ok(match key.as_str() {
+            ARTIFACT_MOVES => Some((key, BxesValue::Artifact(read_artifact(&tree, context)?))),
+            COST_DRIVERS => Some((key, BxesValue::Drivers(read_drivers(&tree, context)?))),
+            _ => None,
+        });
+    }
+
+    reader.skip_element(&element)?;
+
+    let key = element.attribute(KEY_ATTRIBUTE_NAME);
+    let value = element.attribute(VALUE_ATTRIBUTE_NAME);
+
+    match (key, value) {
+        (None, None) => Ok(None),
+        (Some(key), None) => Err(XesReadError::AttributeWithoutValue(key.to_owned())),
+        (None, Some(_)) => Ok(None),
+        (Some(key), Some(value)) => Ok(Some((
+            key.to_owned(),
+            parse_value(&element.name, key, value)?,
+        ))),
+    }
+}
+
+pub fn parse_value(tag_name: &str, key: &str, value: &str) -> Result<BxesValue, XesReadError> {
+    if key == LIFECYCLE_TRANSITION {
+        return Ok(parse_lifecycle(value));
+    }
+
+    let parsed_value = match tag_name {
+        STRING_TAG_NAME => Some(BxesValue::String(Arc::from(value))),
+        DATE_TAG_NAME => parse_date(value).map(BxesValue::Timestamp),
+        INT_TAG_NAME => value.parse::<i64>().ok().map(BxesValue::Int64),
+        FLOAT_TAG_NAME => value.parse::<f64>().ok().map(BxesValue::Float64),
+        BOOL_TAG_NAME => parse_bool(value).map(BxesValue::Bool),
+        ID_TAG_NAME => uuid::Uuid::parse_str(value).ok().map(BxesValue::Guid),
+        _ => None,
+    };
+
+    match parsed_value {
+        Some(parsed_value) => Ok(parsed_value),
+        None => Err(XesReadError::FailedToParseValue(
+            tag_name.to_owned(),
+            value.to_owned(),
+        )),
+    }
+}
+
+/// Standard lifecycle names take precedence over BRAF ones,
+/// unknown transitions are mapped to unspecified standard lifecycle.
+pub fn parse_lifecycle(value: &str) -> BxesValue {
+    if let Some((_, lifecycle)) = STANDARD_LIFECYCLE_NAMES
+        .iter()
+        .find(|(name, _)| *name == value)
+    {
+        return BxesValue::StandardLifecycle(lifecycle.clone());
+    }
+
+    if let Some((_, lifecycle)) = BRAF_LIFECYCLE_NAMES.iter().find(|(name, _)| *name == value) {
+        return BxesValue::BrafLifecycle(lifecycle.clone());
+    }
+
+    BxesValue::StandardLifecycle(crate::models::StandardLifecycle::Unspecified)
+}
+
+/// Returns the number of nanoseconds since Unix epoch with the precision of 100 nanoseconds.
+pub fn parse_date(value: &str) -> Option<i64> {
+    let nanoseconds = match DateTime::parse_from_rfc3339(value) {
+        Ok(date) => date.timestamp_nanos_opt()?,
+        Err(_) => NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()?
+            .and_utc()
+            .timestamp_nanos_opt()?,
+    };
+
+    Some(nanoseconds / 100 * 100)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn list_items(list: &XesElementTree) -> Vec<&XesElementTree> {
+    let mut items = vec![];
+    for child in &list.children {
+        if child.element.name == VALUES_TAG_NAME {
+            items.extend(child.children.iter());
+        } else {
+            items.push(child);
+        }
+    }
+
+    items
+}
+
+fn find_child_value<'a>(item: &'a XesElementTree, keys: &[&str]) -> Option<&'a str> {
+    item.children
+        .iter()
+        .find(|child| match child.element.attribute(KEY_ATTRIBUTE_NAME) {
+            Some(key) => keys.contains(&key),
+            None => false,
+        })
+        .and_then(|child| child.element.attribute(VALUE_ATTRIBUTE_NAME))
+}
+
+fn required_child_value<'a>(
+    item: &'a XesElementTree,
+    keys: &[&str],
+) -> Result<&'a str, XesReadError> {
+    match find_child_value(item, keys) {
+        Some(value) => Ok(value),
+        None => Err(XesReadError::MissingAttribute(
+            item.element.name.clone(),
+            keys[0].to_owned(),
+        )),
+    }
+}
+
+fn value_of_key<'a>(element: &'a XesElement, key: &str) -> Option<&'a str> {
+    match element.attribute(KEY_ATTRIBUTE_NAME) {
+        Some(element_key) if element_key == key => element.attribute(VALUE_ATTRIBUTE_NAME),
+        _ => None,
+    }
+}
+
+fn read_artifact(
+    list: &XesElementTree,
+    context: &mut XesReadContext,
+) -> Result<BxesArtifact, XesReadError> {
+    let mut items = vec![];
+
+    for item in list_items(list) {
+        let model = match item.element.attribute(ARTIFACT_ITEM_MODEL) {
+            Some(model) => model,
+            None => match value_of_key(&item.element, ARTIFACT_ITEM_MODEL) {
+                Some(model) => model,
+                None => {
+                    return Err(XesReadError::MissingAttribute(
+                        item.element.name.clone(),
+                        ARTIFACT_ITEM_MODEL.to_owned(),
+                    ))
+                }
+            },
+        };
+
+        let instance = required_child_value(item, &[ARTIFACT_ITEM_INSTANCE])?;
+        let transition = required_child_value(item, &[ARTIFACT_ITEM_TRANSITION])?;
+
+        items.push(BxesArtifactItem {
+            model: context.get_or_create_string_value(model),
+            instance: context.get_or_create_string_value(instance),
+            transition: context.get_or_create_string_value(transition),
+        });
+    }
+
+    Ok(BxesArtifact { items })
+}
+
+fn read_drivers(
+    list: &XesElementTree,
+    context: &mut XesReadContext,
+) -> Result<BxesDrivers, XesReadError> {
+    let mut drivers = vec![];
+
+    for item in list_items(list) {
+        let name = match value_of_key(&item.element, COST_DRIVER) {
+            Some(name) => name,
+            None => required_child_value(item, &[COST_DRIVER])?,
+        };
+
+        let amount = required_child_value(item, &[COST_AMOUNT])?;
+        let amount = match amount.parse::<f64>() {
+            Ok(amount) => amount,
+            Err(_) => {
+                return Err(XesReadError::FailedToParseValue(
+                    FLOAT_TAG_NAME.to_owned(),
+                    amount.to_owned(),
+                ))
+            }
+        };
+
+        let driver_type = required_child_value(item, &[COST_TYPE, ARTIFACT_ITEM_TRANSITION])?;
+
+        drivers.push(BxesDriver {
+            amount: BxesValue::Float64(amount),
+            name: context.get_or_create_string_value(name),
+            driver_type: context.get_or_create_string_value(driver_type),
+        });
+    }
+
+    Ok(BxesDrivers { drivers })
+}
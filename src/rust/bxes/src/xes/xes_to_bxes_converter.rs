@@ -0,0 +1,267 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    sync::Arc,
+};
+
+use crate::{
+    builder::bxes_event_log_builder::BxesEventLogBuilder, models::*,
+    writer::single_file_bxes_writer::write_bxes,
+};
+
+use super::{
+    errors::{XesReadError, XesToBxesConversionError},
+    xes_constants::*,
+    xes_read_context::XesReadContext,
+    xes_read_utils::*,
+};
+
+pub fn convert_xes_to_bxes(
+    xes_path: &str,
+    bxes_path: &str,
+) -> Result<(), XesToBxesConversionError> {
+    let log = read_xes(xes_path).map_err(XesToBxesConversionError::XesRead)?;
+    write_bxes(bxes_path, &log).map_err(XesToBxesConversionError::BxesWrite)
+}
+
+/// Reads XES log into bxes model, identical traces are grouped into one trace variant.
+pub fn read_xes(path: &str) -> Result<BxesEventLog, XesReadError> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) => return Err(XesReadError::FailedToOpenFile(err)),
+    };
+
+    read_xes_from(BufReader::new(file))
+}
+
+pub fn read_xes_from<R: BufRead>(reader: R) -> Result<BxesEventLog, XesReadError> {
+    let mut reader = XesReader::new(reader);
+    let mut context = XesReadContext::default();
+    let mut log_builder = BxesEventLogBuilder::new();
+
+    loop {
+        let element = match reader.next_node()? {
+            XesNode::Start(element) => element,
+            XesNode::End => continue,
+            XesNode::Eof => break,
+        };
+
+        match element.name.as_str() {
+            LOG_TAG_NAME => continue,
+            TRACE_TAG_NAME => {
+                let trace = read_trace(&mut reader, element, &mut context)?;
+                log_builder.add_trace(vec![], trace);
+            }
+            EXTENSION_TAG_NAME => {
+                let extension = read_extension(&mut reader, element, &mut context)?;
+                push(&mut log_builder.metadata.extensions, extension);
+            }
+            CLASSIFIER_TAG_NAME => {
+                let classifier = read_classifier(&mut reader, element, &mut context)?;
+                push(&mut log_builder.metadata.classifiers, classifier);
+            }
+            GLOBAL_TAG_NAME => {
+                let global = read_global(&mut reader, element, &mut context)?;
+                push(&mut log_builder.metadata.globals, global);
+            }
+            tag_name if is_attribute_tag(tag_name) => {
+                if let Some(property) = read_key_value(&mut reader, element, &mut context)? {
+                    push(&mut log_builder.metadata.properties, property);
+                }
+            }
+            _ => reader.skip_element(&element)?,
+        }
+    }
+
+    Ok(log_builder.build())
+}
+
+fn push<T>(vec: &mut Option<Vec<T>>, item: T) {
+    vec.get_or_insert_with(Vec::new).push(item);
+}
+
+fn read_key_value<R: BufRead>(
+    reader: &mut XesReader<R>,
+    element: XesElement,
+    context: &mut XesReadContext,
+) -> Result<Option<(Arc<BxesValue>, Arc<BxesValue>)>, XesReadError> {
+    Ok(
+        parse_attribute(reader, element, context)?.map(|(key, value)| {
+            (
+                context.get_or_create_string_value(&key),
+                context.get_or_create_value(value),
+            )
+        }),
+    )
+}
+
+fn read_extension<R: BufRead>(
+    reader: &mut XesReader<R>,
+    element: XesElement,
+    context: &mut XesReadContext,
+) -> Result<BxesExtension, XesReadError> {
+    reader.skip_element(&element)?;
+
+    Ok(BxesExtension {
+        name: context
+            .get_or_create_string_value(element.required_attribute(EXTENSION_NAME_ATTRIBUTE)?),
+        prefix: context
+            .get_or_create_string_value(element.required_attribute(EXTENSION_PREFIX_ATTRIBUTE)?),
+        uri: context
+            .get_or_create_string_value(element.required_attribute(EXTENSION_URI_ATTRIBUTE)?),
+    })
+}
+
+fn read_classifier<R: BufRead>(
+    reader: &mut XesReader<R>,
+    element: XesElement,
+    context: &mut XesReadContext,
+) -> Result<BxesClassifier, XesReadError> {
+    reader.skip_element(&element)?;
+
+    let name = element.required_attribute(CLASSIFIER_NAME_ATTRIBUTE)?;
+    let keys = element.required_attribute(CLASSIFIER_KEYS_ATTRIBUTE)?;
+
+    Ok(BxesClassifier {
+        name: context.get_or_create_string_value(name),
+        keys: keys
+            .split_whitespace()
+            .map(|key| context.get_or_create_string_value(key))
+            .collect(),
+    })
+}
+
+fn read_global<R: BufRead>(
+    reader: &mut XesReader<R>,
+    element: XesElement,
+    context: &mut XesReadContext,
+) -> Result<BxesGlobal, XesReadError> {
+    let entity_kind = match element.required_attribute(GLOBAL_SCOPE_ATTRIBUTE)? {
+        "event" => BxesGlobalKind::Event,
+        "trace" => BxesGlobalKind::Trace,
+        "log" => BxesGlobalKind::Log,
+        scope => return Err(XesReadError::UnknownGlobalScope(scope.to_owned())),
+    };
+
+    let mut globals = vec![];
+    if !element.is_empty {
+        while let XesNode::Start(child) = reader.next_node()? {
+            if !is_attribute_tag(&child.name) {
+                reader.skip_element(&child)?;
+                continue;
+            }
+
+            if let Some((key, value)) = parse_attribute(reader, child, context)? {
+                if entity_kind == BxesGlobalKind::Event {
+                    context.event_defaults.insert(key.clone(), value.clone());
+                }
+
+                globals.push((
+                    context.get_or_create_string_value(&key),
+                    context.get_or_create_value(value),
+                ));
+            }
+        }
+    }
+
+    Ok(BxesGlobal {
+        entity_kind,
+        globals,
+    })
+}
+
+fn read_trace<R: BufRead>(
+    reader: &mut XesReader<R>,
+    element: XesElement,
+    context: &mut XesReadContext,
+) -> Result<Vec<BxesEvent>, XesReadError> {
+    let mut events = vec![];
+    if element.is_empty {
+        return Ok(events);
+    }
+
+    while let XesNode::Start(child) = reader.next_node()? {
+        if child.name == EVENT_TAG_NAME {
+            events.push(read_event(reader, child, context)?);
+        } else {
+            reader.skip_element(&child)?;
+        }
+    }
+
+    Ok(events)
+}
+
+/// The `lifecycle:transition` attribute is kept as an attribute of the event, as the C# converter does,
+/// and is also read into the lifecycle of the event.
+fn read_event<R: BufRead>(
+    reader: &mut XesReader<R>,
+    element: XesElement,
+    context: &mut XesReadContext,
+) -> Result<BxesEvent, XesReadError> {
+    let mut name = None;
+    let mut timestamp = None;
+    let mut lifecycle = Lifecycle::default();
+    let mut attributes = vec![];
+
+    if !element.is_empty {
+        while let XesNode::Start(child) = reader.next_node()? {
+            if !is_attribute_tag(&child.name) {
+                reader.skip_element(&child)?;
+                continue;
+            }
+
+            let (key, value) = match parse_attribute(reader, child, context)? {
+                Some(attribute) => attribute,
+                None => continue,
+            };
+
+            match (key.as_str(), value) {
+                (CONCEPT_NAME, BxesValue::String(value)) => name = Some(value.to_string()),
+                (TIME_TIMESTAMP, BxesValue::Timestamp(value)) => timestamp = Some(value),
+                (key, value) => {
+                    match (key, &value) {
+                        (LIFECYCLE_TRANSITION, BxesValue::StandardLifecycle(value)) => {
+                            lifecycle = Lifecycle::Standard(value.clone())
+                        }
+                        (LIFECYCLE_TRANSITION, BxesValue::BrafLifecycle(value)) => {
+                            lifecycle = Lifecycle::Braf(value.clone())
+                        }
+                        _ => {}
+                    }
+
+                    attributes.push((
+                        context.get_or_create_string_value(key),
+                        context.get_or_create_value(value),
+                    ))
+                }
+            }
+        }
+    }
+
+    let name = match name {
+        Some(name) => name,
+        None => match context.event_defaults.get(CONCEPT_NAME) {
+            Some(BxesValue::String(name)) => name.to_string(),
+            _ => return Err(XesReadError::MissingEventName),
+        },
+    };
+
+    let timestamp = match timestamp {
+        Some(timestamp) => timestamp,
+        None => match context.event_defaults.get(TIME_TIMESTAMP) {
+            Some(BxesValue::Timestamp(timestamp)) => *timestamp,
+            _ => 0,
+        },
+    };
+
+    Ok(BxesEvent {
+        name: context.get_or_create_string_value(&name),
+        timestamp,
+        lifecycle,
+        attributes: if attributes.is_empty() {
+            None
+        } else {
+            Some(attributes)
+        },
+    })
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+
+use chrono::{DateTime, SecondsFormat};
+use quick_xml::{
+    events::{BytesDecl, BytesEnd, BytesStart, Event},
+    Writer,
+};
+
+use crate::models::{BrafLifecycle, BxesValue, SoftwareEventType, StandardLifecycle};
+
+use super::{errors::XesWriteError, xes_constants::*};
+
+pub struct XesWriter<W: Write> {
+    writer: Writer<W>,
+}
+
+impl<W: Write> XesWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Writer::new_with_indent(writer, b' ', 2),
+        }
+    }
+
+    pub fn write_declaration(&mut self) -> Result<(), XesWriteError> {
+        self.write(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+    }
+
+    pub fn start_element(
+        &mut self,
+        name: &str,
+        attributes: &[(&str, &str)],
+    ) -> Result<(), XesWriteError> {
+        self.write(Event::Start(create_start(name, attributes)))
+    }
+
+    pub fn end_element(&mut self, name: &str) -> Result<(), XesWriteError> {
+        self.write(Event::End(BytesEnd::new(name)))
+    }
+
+    pub fn empty_element(
+        &mut self,
+        name: &str,
+        attributes: &[(&str, &str)],
+    ) -> Result<(), XesWriteError> {
+        self.write(Event::Empty(create_start(name, attributes)))
+    }
+
+    pub fn value_element(
+        &mut self,
+        tag_name: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<(), XesWriteError> {
+        self.empty_element(
+            tag_name,
+            &[(KEY_ATTRIBUTE_NAME, key), (VALUE_ATTRIBUTE_NAME, value)],
+        )
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    fn write(&mut self, event: Event) -> Result<(), XesWriteError> {
+        match self.writer.write_event(event) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(XesWriteError::XmlError(err)),
+        }
+    }
+}
+
+fn create_start<'a>(name: &'a str, attributes: &[(&'a str, &'a str)]) -> BytesStart<'a> {
+    let mut start = BytesStart::new(name);
+    for attribute in attributes {
+        start.push_attribute(*attribute);
+    }
+
+    start
+}
+
+pub fn string_value(value: &BxesValue) -> Result<&str, XesWriteError> {
+    match value {
+        BxesValue::String(string) => Ok(string),
+        _ => Err(XesWriteError::ExpectedString(value.clone())),
+    }
+}
+
+/// Formats the number of nanoseconds since Unix epoch as ISO-8601 date in UTC.
+pub fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp_nanos(timestamp).to_rfc3339_opts(SecondsFormat::AutoSi, false)
+}
+
+pub fn standard_lifecycle_name(lifecycle: &StandardLifecycle) -> &'static str {
+    STANDARD_LIFECYCLE_NAMES
+        .iter()
+        .find(|(_, value)| value == lifecycle)
+        .map(|(name, _)| *name)
+        .unwrap_or(STANDARD_LIFECYCLE_NAMES[0].0)
+}
+
+pub fn braf_lifecycle_name(lifecycle: &BrafLifecycle) -> &'static str {
+    BRAF_LIFECYCLE_NAMES
+        .iter()
+        .find(|(_, value)| value == lifecycle)
+        .map(|(name, _)| *name)
+        .unwrap_or(BRAF_LIFECYCLE_NAMES[0].0)
+}
+
+pub fn software_event_type_name(event_type: &SoftwareEventType) -> &'static str {
+    match event_type {
+        SoftwareEventType::Unspecified => "unspecified",
+        SoftwareEventType::Call => "call",
+        SoftwareEventType::Return => "return",
+        SoftwareEventType::Throws => "throws",
+        SoftwareEventType::Handle => "handle",
+        SoftwareEventType::Calling => "calling",
+        SoftwareEventType::Returning => "returning",
+    }
+}
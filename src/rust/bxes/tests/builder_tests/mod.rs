@@ -0,0 +1 @@
+pub mod test_event_log_builder;
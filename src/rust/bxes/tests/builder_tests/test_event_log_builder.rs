@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use bxes::{
+    builder::{
+        bxes_event_log_builder::BxesEventLogBuilder,
+        event_equivalence::BxesIgnoreTimestampsEquivalence,
+    },
+    models::{BxesEvent, BxesValue, Lifecycle},
+};
+
+#[test]
+pub fn test_event_log_builder() {
+    let mut builder = BxesEventLogBuilder::new();
+    let metadata = vec![(create_string("case"), create_string("first"))];
+
+    builder.add_trace(vec![], create_trace(&[("a", 1), ("b", 2)]));
+    builder.add_trace(vec![], create_trace(&[("a", 1), ("b", 2)]));
+    builder.add_trace(vec![], create_trace(&[("a", 1), ("b", 3)]));
+    builder.add_trace(metadata.clone(), create_trace(&[("a", 1), ("b", 2)]));
+    builder.add_trace(metadata.clone(), create_trace(&[("a", 1), ("b", 2)]));
+
+    let log = builder.build();
+
+    let traces_counts = log.variants.iter().map(|variant| variant.traces_count);
+    assert_eq!(traces_counts.collect::<Vec<_>>(), vec![2, 1, 2]);
+    assert_eq!(log.variants[0].events, create_trace(&[("a", 1), ("b", 2)]));
+    assert_eq!(log.variants[2].metadata, metadata);
+}
+
+#[test]
+pub fn test_event_log_builder_ignore_timestamps() {
+    let mut builder = BxesEventLogBuilder::with_equivalence(BxesIgnoreTimestampsEquivalence);
+
+    builder.add_trace(vec![], create_trace(&[("a", 1), ("b", 2)]));
+    builder.add_trace(vec![], create_trace(&[("a", 3), ("b", 4)]));
+    builder.add_trace(vec![], create_trace(&[("b", 1), ("a", 2)]));
+
+    let log = builder.build();
+
+    let traces_counts = log.variants.iter().map(|variant| variant.traces_count);
+    assert_eq!(traces_counts.collect::<Vec<_>>(), vec![2, 1]);
+    assert_eq!(log.variants[0].events, create_trace(&[("a", 1), ("b", 2)]));
+}
+
+fn create_trace(events: &[(&str, i64)]) -> Vec<BxesEvent> {
+    events
+        .iter()
+        .map(|(name, timestamp)| BxesEvent {
+            name: create_string(name),
+            timestamp: *timestamp,
+            lifecycle: Lifecycle::default(),
+            attributes: None,
+        })
+        .collect()
+}
+
+fn create_string(value: &str) -> Arc<BxesValue> {
+    Arc::new(BxesValue::String(value.into()))
+}
@@ -0,0 +1,119 @@
+use std::process::{Command, Output};
+
+use bxes::{
+    read::{
+        multiple_files_bxes_reader::read_bxes_multiple_files, single_file_bxes_reader::read_bxes,
+    },
+    writer::single_file_bxes_writer::write_bxes,
+};
+use tempfile::TempDir;
+
+use crate::test_core::random_log::generate_random_log;
+
+fn run_bxes(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_bxes"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn assert_failure(args: &[&str], expected_message: &str) {
+    let output = run_bxes(args);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(1), "{:?}: {}", args, stderr);
+    assert!(stderr.contains(expected_message), "{:?}: {}", args, stderr);
+}
+
+fn create_log_file(temp_dir: &TempDir, name: &str) -> String {
+    let path = temp_dir.path().join(name);
+    let path = path.to_str().unwrap();
+    write_bxes(path, &generate_random_log()).unwrap();
+
+    path.to_owned()
+}
+
+fn create_corrupt_log_file(temp_dir: &TempDir) -> String {
+    let path = temp_dir.path().join("corrupt.bxes");
+    std::fs::write(&path, b"not a bxes log").unwrap();
+
+    path.to_str().unwrap().to_owned()
+}
+
+#[test]
+pub fn test_convert_failures() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = create_log_file(&temp_dir, "log.bxes");
+    let missing_path = temp_dir.path().join("missing.bxes");
+    let xes_path = temp_dir.path().join("log.xes");
+
+    assert_failure(
+        &[
+            "convert",
+            missing_path.to_str().unwrap(),
+            xes_path.to_str().unwrap(),
+        ],
+        "error: ",
+    );
+
+    assert_failure(
+        &[
+            "convert",
+            &log_path,
+            xes_path.to_str().unwrap(),
+            "--target-version",
+            "1",
+        ],
+        "--target-version can not be used with XES output",
+    );
+
+    assert!(!xes_path.exists());
+}
+
+#[test]
+pub fn test_info_dump_and_validate_failures() {
+    let temp_dir = TempDir::new().unwrap();
+    let corrupt_path = create_corrupt_log_file(&temp_dir);
+    let xes_path = temp_dir.path().join("log.xes");
+    let xes_path = xes_path.to_str().unwrap();
+
+    assert_failure(&["info", &corrupt_path], "error: ");
+    assert_failure(&["info", xes_path], "is not a bxes log");
+    assert_failure(&["dump", &corrupt_path], "error: ");
+    assert_failure(&["dump", xes_path], "is not a bxes log");
+    assert_failure(&["validate", &corrupt_path], "error: ");
+}
+
+#[test]
+pub fn test_merge() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = create_log_file(&temp_dir, "log.bxes");
+    let other_log_path = create_log_file(&temp_dir, "other_log.bxes");
+    let corrupt_path = create_corrupt_log_file(&temp_dir);
+
+    let output_path = temp_dir.path().join("merged.bxes");
+    let output_path = output_path.to_str().unwrap();
+    let output_dir_path = temp_dir.path().join("merged");
+    let output_dir_path = output_dir_path.to_str().unwrap();
+
+    assert_failure(
+        &["merge", "-o", output_path, &log_path, &corrupt_path],
+        "error: ",
+    );
+
+    for (output, read) in [
+        (output_path, read_bxes as fn(&str) -> _),
+        (output_dir_path, read_bxes_multiple_files),
+    ] {
+        let status = run_bxes(&["merge", "-o", output, &log_path, &other_log_path]).status;
+        assert!(status.success());
+
+        let expected_variants_count = read_bxes(&log_path).unwrap().variants.len()
+            + read_bxes(&other_log_path).unwrap().variants.len();
+
+        assert_eq!(
+            read(output).unwrap().variants.len(),
+            expected_variants_count
+        );
+    }
+}
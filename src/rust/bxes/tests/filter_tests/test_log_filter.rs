@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use bxes::{
+    builder::bxes_event_log_builder::BxesEventLogBuilder,
+    filter::bxes_log_filter::BxesLogFilter,
+    models::{BxesEvent, BxesEventLog, BxesValue, Lifecycle},
+    read::single_file_bxes_reader::read_bxes_lazy,
+    writer::single_file_bxes_writer::write_bxes,
+};
+use tempfile::TempDir;
+
+#[test]
+pub fn test_filter_log() {
+    let log = create_log();
+
+    let filtered_log = BxesLogFilter::new()
+        .with_min_traces_count(2)
+        .filter_log(&log);
+    assert_eq!(filtered_log.variants.len(), 1);
+    assert_eq!(filtered_log.variants[0].traces_count, 3);
+
+    let filtered_log = BxesLogFilter::new()
+        .with_event_name_predicate(|name| name == "a")
+        .with_timestamp_range(0..5)
+        .filter_log(&log);
+
+    assert_eq!(filtered_log.variants.len(), 2);
+    assert_eq!(
+        filtered_log.variants[0].events,
+        vec![create_event("a", 1, &[("x", 1), ("y", 2)])]
+    );
+
+    let filtered_log = BxesLogFilter::new()
+        .with_event_name_predicate(|name| name != "c")
+        .with_attribute_keys(&[])
+        .filter_log(&log);
+
+    assert_eq!(filtered_log.variants.len(), 1);
+    assert_eq!(filtered_log.variants[0].traces_count, 4);
+}
+
+#[test]
+pub fn test_filter_prunes_values() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("log.bxes");
+    let filtered_log_path = temp_dir.path().join("filtered_log.bxes");
+    let log_path = log_path.to_str().unwrap();
+    let filtered_log_path = filtered_log_path.to_str().unwrap();
+
+    write_bxes(log_path, &create_log()).unwrap();
+
+    let filtered_log = BxesLogFilter::new()
+        .with_event_name_predicate(|name| name != "c")
+        .with_attribute_keys(&["x"])
+        .filter_lazy_log(read_bxes_lazy(log_path).unwrap())
+        .unwrap();
+
+    write_bxes(filtered_log_path, &filtered_log).unwrap();
+
+    let filtered_log = read_bxes_lazy(filtered_log_path).unwrap();
+    for value in ["c", "y"] {
+        assert!(!filtered_log.values().contains(&create_string(value)));
+    }
+
+    assert_eq!(filtered_log.kv_pairs().len(), 1);
+}
+
+fn create_log() -> BxesEventLog {
+    let mut builder = BxesEventLogBuilder::new();
+    let first_trace = vec![
+        create_event("a", 1, &[("x", 1), ("y", 2)]),
+        create_event("b", 5, &[]),
+    ];
+
+    for _ in 0..3 {
+        builder.add_trace(vec![], first_trace.clone());
+    }
+
+    builder.add_trace(
+        vec![],
+        vec![
+            create_event("a", 1, &[("x", 1)]),
+            create_event("b", 5, &[]),
+            create_event("c", 10, &[("y", 3)]),
+        ],
+    );
+
+    builder.build()
+}
+
+fn create_event(name: &str, timestamp: i64, attributes: &[(&str, i64)]) -> BxesEvent {
+    let attributes = attributes
+        .iter()
+        .map(|(key, value)| (create_string(key), Arc::new(BxesValue::Int64(*value))))
+        .collect::<Vec<_>>();
+
+    BxesEvent {
+        name: create_string(name),
+        timestamp,
+        lifecycle: Lifecycle::default(),
+        attributes: match attributes.is_empty() {
+            true => None,
+            false => Some(attributes),
+        },
+    }
+}
+
+fn create_string(value: &str) -> Arc<BxesValue> {
+    Arc::new(BxesValue::String(value.into()))
+}
@@ -0,0 +1,159 @@
+use std::sync::Arc;
+
+use bxes::{
+    constants::BXES_VERSION,
+    merge::{
+        bxes_logs_merger::{
+            merge_log_files, merge_log_files_with_options, merge_logs, merge_logs_with_options,
+        },
+        errors::BxesMergeError,
+        merge_options::{BxesMergeOptions, BxesMetadataConflictPolicy},
+    },
+    models::{BxesChecksum, BxesEventLog, BxesEventLogMetadata, BxesTraceVariant, BxesValue},
+    read::{
+        bxes_validator::validate_bxes_multiple_files,
+        multiple_files_bxes_reader::{read_bxes_multiple_files, read_bxes_multiple_files_lazy},
+    },
+    writer::{
+        multiple_file_bxes_writer::write_bxes_multiple_files, single_file_bxes_writer::write_bxes,
+        write_options::BxesWriteOptions,
+    },
+};
+use tempfile::TempDir;
+
+use crate::test_core::random_log::generate_random_log;
+
+#[test]
+pub fn test_merge_logs() {
+    let (log, other_log) = generate_logs_to_merge();
+
+    let merged_log = merge_logs(&[log.clone(), other_log.clone(), log.clone()]).unwrap();
+
+    assert_eq!(merged_log.version, log.version);
+    assert!(merged_log
+        .variants
+        .eq(&create_expected_variants(&log, &other_log)));
+}
+
+#[test]
+pub fn test_merge_log_files() {
+    let (log, other_log) = generate_logs_to_merge();
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("log.bxes");
+    let other_log_path = temp_dir.path().join("other_log");
+    let output_path = temp_dir.path().join("merged_log");
+    std::fs::create_dir(&other_log_path).unwrap();
+    std::fs::create_dir(&output_path).unwrap();
+
+    let log_path = log_path.to_str().unwrap();
+    let other_log_path = other_log_path.to_str().unwrap();
+    let output_path = output_path.to_str().unwrap();
+
+    write_bxes(log_path, &log).unwrap();
+    write_bxes_multiple_files(&other_log, other_log_path).unwrap();
+
+    merge_log_files(&[log_path, other_log_path, log_path], output_path).unwrap();
+
+    let merged_log = read_bxes_multiple_files(output_path).unwrap();
+    assert!(merged_log
+        .variants
+        .eq(&create_expected_variants(&log, &other_log)));
+
+    let options = BxesMergeOptions {
+        write_options: BxesWriteOptions {
+            checksum: BxesChecksum::Crc32c,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let paths = [log_path, other_log_path];
+    merge_log_files_with_options(&paths, output_path, &options).unwrap();
+
+    let merged_log = read_bxes_multiple_files_lazy(output_path).unwrap();
+    assert_eq!(merged_log.checksum, BxesChecksum::Crc32c);
+    assert_eq!(
+        validate_bxes_multiple_files(output_path).unwrap().issues,
+        vec![]
+    );
+}
+
+#[test]
+pub fn test_merge_metadata_conflicts() {
+    let log = create_log_with_properties(&[("key", "first")]);
+    let other_log = create_log_with_properties(&[("key", "second"), ("other_key", "value")]);
+
+    let merge_properties = |policy: BxesMetadataConflictPolicy| {
+        let options = BxesMergeOptions {
+            metadata_conflict_policy: policy,
+            ..Default::default()
+        };
+
+        merge_logs_with_options(&[log.clone(), other_log.clone()], &options)
+            .map(|log| log.metadata.properties.unwrap())
+    };
+
+    assert_eq!(
+        merge_properties(BxesMetadataConflictPolicy::KeepAll).unwrap(),
+        create_properties(&[("key", "first"), ("key", "second"), ("other_key", "value")])
+    );
+
+    assert_eq!(
+        merge_properties(BxesMetadataConflictPolicy::KeepFirst).unwrap(),
+        create_properties(&[("key", "first"), ("other_key", "value")])
+    );
+
+    assert_eq!(
+        merge_properties(BxesMetadataConflictPolicy::KeepLast).unwrap(),
+        create_properties(&[("key", "second"), ("other_key", "value")])
+    );
+
+    assert!(matches!(
+        merge_properties(BxesMetadataConflictPolicy::Fail),
+        Err(BxesMergeError::MetadataConflict(_))
+    ));
+}
+
+fn generate_logs_to_merge() -> (BxesEventLog, BxesEventLog) {
+    let mut log = generate_random_log();
+    for variant in log.variants.iter_mut() {
+        variant.traces_count %= 1000;
+    }
+
+    (log, generate_random_log())
+}
+
+fn create_expected_variants(log: &BxesEventLog, other_log: &BxesEventLog) -> Vec<BxesTraceVariant> {
+    let mut expected_variants = log.variants.clone();
+    for variant in expected_variants.iter_mut() {
+        variant.traces_count *= 2;
+    }
+
+    expected_variants.extend(other_log.variants.iter().cloned());
+    expected_variants
+}
+
+fn create_log_with_properties(properties: &[(&str, &str)]) -> BxesEventLog {
+    BxesEventLog {
+        version: BXES_VERSION,
+        metadata: BxesEventLogMetadata {
+            extensions: None,
+            classifiers: None,
+            properties: Some(create_properties(properties)),
+            globals: None,
+        },
+        variants: vec![],
+    }
+}
+
+fn create_properties(properties: &[(&str, &str)]) -> Vec<(Arc<BxesValue>, Arc<BxesValue>)> {
+    properties
+        .iter()
+        .map(|(key, value)| {
+            (
+                Arc::new(BxesValue::String((*key).into())),
+                Arc::new(BxesValue::String((*value).into())),
+            )
+        })
+        .collect()
+}
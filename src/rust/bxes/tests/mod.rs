@@ -1,3 +1,10 @@
+pub mod builder_tests;
+pub mod cli_tests;
+pub mod filter_tests;
+pub mod merge_tests;
 pub mod multiple_files_tests;
 pub mod single_file_tests;
+pub mod stream_writer_tests;
 pub mod test_core;
+pub mod validator_tests;
+pub mod xes_tests;
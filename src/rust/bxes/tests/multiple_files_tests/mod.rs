@@ -1 +1,2 @@
+pub mod test_multiple_file_appender;
 pub mod test_multiple_file_reader;
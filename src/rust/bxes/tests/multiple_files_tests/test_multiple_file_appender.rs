@@ -0,0 +1,125 @@
+use std::{fs, path::Path};
+
+use bxes::{
+    constants::{PADDED_COUNT_SIZE, VARIANTS_FILE_NAME},
+    models::BxesChecksum,
+    read::{
+        bxes_validator::validate_bxes_multiple_files,
+        multiple_files_bxes_reader::read_bxes_multiple_files,
+    },
+    writer::{
+        errors::BxesWriteError, multiple_file_bxes_appender::MultipleFilesBxesAppender,
+        multiple_file_bxes_writer::write_bxes_multiple_files_with_options,
+        write_options::BxesWriteOptions,
+    },
+};
+use tempfile::TempDir;
+
+use crate::test_core::{
+    random_log::generate_random_log,
+    raw_log::{create_event, create_log, HEADER_SIZE},
+};
+
+#[test]
+pub fn test_multiple_file_appender() {
+    let mut log = generate_random_log();
+    for variant in log.variants.iter_mut() {
+        variant.traces_count %= 1000;
+    }
+
+    let appended_log = generate_random_log();
+    for checksum in [BxesChecksum::None, BxesChecksum::Crc32c] {
+        let temp_dir = TempDir::new().unwrap();
+        let temp_dir_path = temp_dir.path().to_str().unwrap();
+        let options = BxesWriteOptions {
+            checksum,
+            ..Default::default()
+        };
+
+        write_bxes_multiple_files_with_options(&log, temp_dir_path, &options).unwrap();
+
+        let mut appender = MultipleFilesBxesAppender::open(temp_dir_path).unwrap();
+        for variant in appended_log.variants.iter().chain(&log.variants) {
+            appender.append_variant(variant).unwrap();
+        }
+
+        appender.finish().unwrap();
+
+        let mut expected_variants = log.variants.clone();
+        for variant in expected_variants.iter_mut() {
+            variant.traces_count *= 2;
+        }
+
+        expected_variants.extend(appended_log.variants.iter().cloned());
+
+        let read_log = read_bxes_multiple_files(temp_dir_path).unwrap();
+        assert_eq!(read_log.metadata, log.metadata);
+        assert!(read_log.variants.eq(&expected_variants));
+
+        assert_eq!(
+            validate_bxes_multiple_files(temp_dir_path).unwrap().issues,
+            vec![]
+        );
+    }
+}
+
+#[test]
+pub fn test_multiple_file_appender_finishes_on_drop() {
+    let log = generate_random_log();
+    let appended_log = generate_random_log();
+
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    let options = BxesWriteOptions {
+        checksum: BxesChecksum::Crc32c,
+        ..Default::default()
+    };
+
+    write_bxes_multiple_files_with_options(&log, temp_dir_path, &options).unwrap();
+
+    {
+        let mut appender = MultipleFilesBxesAppender::open(temp_dir_path).unwrap();
+        for variant in &appended_log.variants {
+            appender.append_variant(variant).unwrap();
+        }
+    }
+
+    let mut expected_variants = log.variants.clone();
+    expected_variants.extend(appended_log.variants.iter().cloned());
+
+    let read_log = read_bxes_multiple_files(temp_dir_path).unwrap();
+    assert!(read_log.variants.eq(&expected_variants));
+}
+
+#[test]
+pub fn test_multiple_file_appender_rejects_unpadded_counts() {
+    let log = create_log(vec![vec![create_event("a")]]);
+
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    write_bxes_multiple_files_with_options(&log, temp_dir_path, &BxesWriteOptions::default())
+        .unwrap();
+
+    let variants_path = Path::new(temp_dir_path).join(VARIANTS_FILE_NAME);
+    let bytes = fs::read(&variants_path).unwrap();
+
+    // the number of variants follows the header, the traces count of the first variant follows it
+    let variants_count_offset = HEADER_SIZE;
+    let traces_count_offset = variants_count_offset + PADDED_COUNT_SIZE;
+
+    for offset in [variants_count_offset, traces_count_offset] {
+        let mut count_bytes = &bytes[offset..offset + PADDED_COUNT_SIZE];
+        let count = leb128::read::unsigned(&mut count_bytes).unwrap();
+
+        let mut unpadded_bytes = bytes[..offset].to_vec();
+        leb128::write::unsigned(&mut unpadded_bytes, count).unwrap();
+        unpadded_bytes.extend_from_slice(&bytes[offset + PADDED_COUNT_SIZE..]);
+        fs::write(&variants_path, unpadded_bytes).unwrap();
+
+        assert!(read_bxes_multiple_files(temp_dir_path).is_ok());
+        assert!(matches!(
+            MultipleFilesBxesAppender::open(temp_dir_path),
+            Err(BxesWriteError::UnpaddedCount { offset: count_offset, .. }) if count_offset == offset
+        ));
+    }
+}
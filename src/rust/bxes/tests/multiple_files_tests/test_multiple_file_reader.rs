@@ -1,10 +1,26 @@
+use std::{fs, path::Path};
+
 use bxes::{
-    read::multiple_files_bxes_reader::read_bxes_multiple_files,
-    writer::multiple_file_bxes_writer::write_bxes_multiple_files,
+    constants::{KEY_VALUES_FILE_NAME, PADDED_COUNT_SIZE, VALUES_FILE_NAME, VARIANTS_FILE_NAME},
+    models::BxesChecksum,
+    read::{
+        bxes_validator::{validate_bxes_multiple_files, BxesValidationIssueKind},
+        errors::BxesReadError,
+        mmap_bxes_reader::BxesMappedLogFiles,
+        multiple_files_bxes_reader::{read_bxes_multiple_files, read_bxes_multiple_files_lazy},
+        parallel_bxes_reader::read_bxes_multiple_files_parallel,
+    },
+    writer::{
+        errors::BxesWriteError,
+        multiple_file_bxes_writer::{
+            write_bxes_multiple_files, write_bxes_multiple_files_with_options,
+        },
+        write_options::BxesWriteOptions,
+    },
 };
 use tempfile::TempDir;
 
-use crate::test_core::random_log::generate_random_log;
+use crate::test_core::{mapped_log::assert_mapped_log_eq, random_log::generate_random_log};
 
 #[test]
 pub fn test_multiple_file_reader() {
@@ -17,3 +33,144 @@ pub fn test_multiple_file_reader() {
 
     assert!(log.eq(&read_log));
 }
+
+#[test]
+pub fn test_multiple_file_lazy_reader() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    write_bxes_multiple_files(&log, temp_dir_path).unwrap();
+
+    let lazy_log = read_bxes_multiple_files_lazy(temp_dir_path).unwrap();
+    assert_eq!(lazy_log.metadata, log.metadata);
+
+    let variants = lazy_log.variants().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(variants.eq(&log.variants));
+}
+
+#[test]
+pub fn test_multiple_file_parallel_reader() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    write_bxes_multiple_files(&log, temp_dir_path).unwrap();
+
+    let read_log = read_bxes_multiple_files_parallel(temp_dir_path).unwrap();
+
+    assert_eq!(read_log.metadata, log.metadata);
+    assert!(read_log.variants.eq(&log.variants));
+}
+
+#[test]
+pub fn test_multiple_file_versions() {
+    let mut log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    write_bxes_multiple_files(&log, temp_dir_path).unwrap();
+
+    let overwrite_version = |file_name: &str, version: u32| {
+        let file_path = Path::new(temp_dir_path).join(file_name);
+        let mut bytes = fs::read(&file_path).unwrap();
+        bytes[..4].copy_from_slice(&version.to_le_bytes());
+        fs::write(&file_path, bytes).unwrap();
+    };
+
+    overwrite_version(KEY_VALUES_FILE_NAME, 1);
+    assert!(matches!(
+        read_bxes_multiple_files(temp_dir_path),
+        Err(BxesReadError::VersionsMismatchError(_))
+    ));
+
+    overwrite_version(VALUES_FILE_NAME, 100);
+    assert!(matches!(
+        read_bxes_multiple_files(temp_dir_path),
+        Err(BxesReadError::UnsupportedVersion(100))
+    ));
+
+    log.version = 100;
+    assert!(matches!(
+        write_bxes_multiple_files(&log, temp_dir_path),
+        Err(BxesWriteError::UnsupportedVersion(100))
+    ));
+}
+
+#[test]
+pub fn test_multiple_file_mmap_reader() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    write_bxes_multiple_files(&log, temp_dir_path).unwrap();
+
+    let files = BxesMappedLogFiles::open_multiple_files(temp_dir_path).unwrap();
+    assert_mapped_log_eq(&files.read().unwrap(), &log);
+}
+
+#[test]
+pub fn test_multiple_file_padded_counts() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    write_bxes_multiple_files(&log, temp_dir_path).unwrap();
+
+    // the number of variants follows the version, the string encoding and the kind of checksums
+    let bytes = fs::read(Path::new(temp_dir_path).join(VARIANTS_FILE_NAME)).unwrap();
+    let count_bytes = &bytes[6..6 + PADDED_COUNT_SIZE];
+
+    assert!(count_bytes[..PADDED_COUNT_SIZE - 1]
+        .iter()
+        .all(|byte| byte & 0x80 != 0));
+    assert_eq!(count_bytes[PADDED_COUNT_SIZE - 1] & 0x80, 0);
+
+    let variants_count = leb128::read::unsigned(&mut &count_bytes[..]).unwrap();
+    assert_eq!(variants_count, log.variants.len() as u64);
+}
+
+#[test]
+pub fn test_multiple_file_checksums() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+
+    let options = BxesWriteOptions {
+        checksum: BxesChecksum::Crc32c,
+        ..Default::default()
+    };
+
+    write_bxes_multiple_files_with_options(&log, temp_dir_path, &options).unwrap();
+
+    assert!(read_bxes_multiple_files(temp_dir_path).unwrap().eq(&log));
+    assert!(read_bxes_multiple_files_parallel(temp_dir_path)
+        .unwrap()
+        .variants
+        .eq(&log.variants));
+
+    let files = BxesMappedLogFiles::open_multiple_files(temp_dir_path).unwrap();
+    assert_mapped_log_eq(&files.read().unwrap(), &log);
+
+    assert_eq!(
+        validate_bxes_multiple_files(temp_dir_path).unwrap().issues,
+        vec![]
+    );
+
+    let kv_pairs_path = Path::new(temp_dir_path).join(KEY_VALUES_FILE_NAME);
+    let mut bytes = fs::read(&kv_pairs_path).unwrap();
+    bytes[5] = BxesChecksum::None as u8;
+    fs::write(&kv_pairs_path, bytes).unwrap();
+
+    assert!(matches!(
+        read_bxes_multiple_files(temp_dir_path),
+        Err(BxesReadError::ChecksumKindsMismatch {
+            expected: BxesChecksum::Crc32c,
+            actual: BxesChecksum::None
+        })
+    ));
+
+    let report = validate_bxes_multiple_files(temp_dir_path).unwrap();
+    assert_eq!(
+        report.issues.first().map(|issue| &issue.kind),
+        Some(&BxesValidationIssueKind::ChecksumKindsMismatch {
+            expected: BxesChecksum::Crc32c,
+            actual: BxesChecksum::None
+        })
+    );
+}
@@ -1,9 +1,47 @@
-use std::path::Path;
+use std::{
+    error::Error,
+    fs::File,
+    io::{Cursor, Write},
+    mem::size_of,
+    path::Path,
+    sync::Arc,
+};
 
-use bxes::{read::single_file_bxes_reader::read_bxes, writer::single_file_bxes_writer::write_bxes};
+use bxes::{
+    builder::bxes_event_log_builder::BxesEventLogBuilder,
+    models::{
+        BxesArtifact, BxesArtifactItem, BxesChecksum, BxesEvent, BxesStringEncoding,
+        BxesTraceVariant, BxesValue, Lifecycle,
+    },
+    read::{
+        bxes_validator::{validate_bxes, validate_bxes_raw, BxesValidationIssueKind},
+        errors::{BxesErrorLocation, BxesReadError, BxesSection},
+        lazy_bxes_reader::BxesLazyLogItem,
+        mmap_bxes_reader::BxesMappedLogFiles,
+        parallel_bxes_reader::read_bxes_parallel,
+        random_access_bxes_reader::BxesRandomAccessReader,
+        single_file_bxes_reader::{
+            read_bxes, read_bxes_from, read_bxes_from_bytes, read_bxes_lazy,
+        },
+    },
+    type_ids::TypeIds,
+    writer::{
+        single_file_bxes_writer::{
+            write_bxes, write_bxes_to_bytes, write_bxes_to_with_options, write_bxes_with_options,
+        },
+        write_options::{BxesCompression, BxesIndexOrder, BxesWriteOptions},
+    },
+};
 use tempfile::TempDir;
+use zip::{write::FileOptions, ZipWriter};
 
-use crate::test_core::random_log::generate_random_log;
+use crate::test_core::{
+    mapped_log::assert_mapped_log_eq,
+    random_log::generate_random_log,
+    raw_log::{
+        create_event, create_log, event_offsets, write_raw_log, HEADER_SIZE, SMALL_LEB128_SIZE,
+    },
+};
 
 #[test]
 pub fn test_single_file_read_write() {
@@ -18,3 +56,724 @@ pub fn test_single_file_read_write() {
     let read_log = read_bxes(log_save_path.to_str().unwrap()).unwrap();
     assert!(read_log.eq(&log))
 }
+
+#[test]
+pub fn test_single_file_lazy_read() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    write_bxes(log_save_path, &log).unwrap();
+
+    let lazy_log = read_bxes_lazy(log_save_path).unwrap();
+    assert_eq!(lazy_log.version, log.version);
+    assert_eq!(lazy_log.metadata, log.metadata);
+    assert_eq!(lazy_log.variants_count() as usize, log.variants.len());
+
+    let variants = lazy_log.variants().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(variants.eq(&log.variants));
+
+    let mut variants: Vec<BxesTraceVariant> = vec![];
+    for item in read_bxes_lazy(log_save_path).unwrap().items() {
+        match item.unwrap() {
+            BxesLazyLogItem::TraceVariantStart(variant_start) => {
+                variants.push(BxesTraceVariant {
+                    traces_count: variant_start.traces_count,
+                    metadata: variant_start.metadata,
+                    events: vec![],
+                });
+            }
+            BxesLazyLogItem::Event(event) => variants.last_mut().unwrap().events.push(event),
+        }
+    }
+
+    assert!(variants.eq(&log.variants));
+}
+
+#[test]
+pub fn test_read_log_in_another_thread() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap().to_owned();
+
+    write_bxes(&log_save_path, &log).unwrap();
+
+    let read_log = std::thread::spawn(move || read_bxes(&log_save_path).unwrap())
+        .join()
+        .unwrap();
+
+    let shared_log = Arc::new(read_log);
+    let thread_log = shared_log.clone();
+    let variants_count = std::thread::spawn(move || thread_log.variants.len())
+        .join()
+        .unwrap();
+
+    assert_eq!(variants_count, log.variants.len());
+    assert!(shared_log.as_ref().eq(&log));
+}
+
+#[test]
+pub fn test_single_file_parallel_read() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    write_bxes(log_save_path, &log).unwrap();
+
+    let read_log = read_bxes_parallel(log_save_path).unwrap();
+
+    assert_eq!(read_log.metadata, log.metadata);
+    assert!(read_log.variants.eq(&log.variants));
+}
+
+#[test]
+pub fn test_random_access_read() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    write_bxes(log_save_path, &log).unwrap();
+
+    let mut reader = BxesRandomAccessReader::open(log_save_path).unwrap();
+    assert_eq!(reader.version(), log.version);
+    assert_eq!(reader.variants_count(), log.variants.len());
+
+    for index in (0..log.variants.len()).rev() {
+        let variant = reader.read_variant(index).unwrap();
+        assert!(variant.eq(&log.variants[index]));
+        assert_eq!(
+            reader.events_count(index).unwrap() as usize,
+            log.variants[index].events.len()
+        );
+    }
+
+    assert!(matches!(
+        reader.read_variant(log.variants.len()),
+        Err(BxesReadError::VariantIndexOutOfRange(_))
+    ));
+}
+
+#[test]
+pub fn test_read_corrupted_variants_count() {
+    let log = create_log(vec![vec![create_event("a")]]);
+    let raw_log = write_raw_log(&log, BxesChecksum::None);
+
+    // the number of variants precedes the first variant
+    let variant_offset = raw_log.variants_offsets[0];
+    let variants_count_offset = variant_offset - SMALL_LEB128_SIZE;
+    let raw_log = raw_log.bytes;
+
+    let mut corrupted_log = raw_log[..variants_count_offset].to_vec();
+    leb128::write::unsigned(&mut corrupted_log, u32::MAX as u64).unwrap();
+    corrupted_log.extend_from_slice(&raw_log[variant_offset..]);
+
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    std::fs::write(&log_save_path, corrupted_log).unwrap();
+
+    let log_save_path = log_save_path.to_str().unwrap();
+    assert!(matches!(
+        BxesRandomAccessReader::open(log_save_path),
+        Err(BxesReadError::MissingVariantsIndex)
+    ));
+
+    assert!(read_bxes_parallel(log_save_path).is_err());
+}
+
+#[test]
+pub fn test_read_log_without_events_lifecycle() {
+    let mut log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    let options = BxesWriteOptions {
+        version: Some(1),
+        ..Default::default()
+    };
+
+    write_bxes_with_options(log_save_path, &log, &options).unwrap();
+    let read_log = read_bxes(log_save_path).unwrap();
+
+    for variant in &mut log.variants {
+        for event in &mut variant.events {
+            event.lifecycle = Lifecycle::default();
+        }
+    }
+
+    assert_eq!(read_log.version, 1);
+    assert!(read_log.variants.eq(&log.variants));
+    assert!(read_bxes_parallel(log_save_path).unwrap().variants.eq(&log.variants));
+
+    let bytes = write_bxes_to_with_options(Cursor::new(vec![]), &log, &options)
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(read_bxes_from_bytes(&bytes).unwrap().version, 1);
+}
+
+#[test]
+pub fn test_read_log_with_absolute_timestamps() {
+    let mut log = generate_random_log();
+    log.variants.push(BxesTraceVariant {
+        traces_count: 1,
+        metadata: vec![],
+        events: vec![create_event("a"); 10],
+    });
+
+    for variant in &mut log.variants {
+        for (index, event) in variant.events.iter_mut().enumerate() {
+            event.timestamp = 1_700_000_000_000_000_000 + index as i64 * 1000;
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut read_write = |version: u32| {
+        let log_save_path = temp_dir.path().join(format!("log_{}.bxes", version));
+        let log_save_path = log_save_path.to_str().unwrap();
+        let options = BxesWriteOptions {
+            compression: BxesCompression::None,
+            ..Default::default()
+        };
+
+        log.version = version;
+        write_bxes_with_options(log_save_path, &log, &options).unwrap();
+
+        let read_log = read_bxes(log_save_path).unwrap();
+        assert_eq!(read_log.version, version);
+        assert!(read_log.variants.eq(&log.variants));
+        assert!(read_bxes_parallel(log_save_path).unwrap().variants.eq(&log.variants));
+
+        std::fs::metadata(log_save_path).unwrap().len()
+    };
+
+    let absolute_timestamps_size = read_write(2);
+    let delta_timestamps_size = read_write(3);
+
+    assert!(delta_timestamps_size < absolute_timestamps_size);
+}
+
+#[test]
+pub fn test_read_log_with_fixed_width_lengths() {
+    let mut log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut read_write = |version: u32| {
+        let log_save_path = temp_dir.path().join(format!("log_{}.bxes", version));
+        let log_save_path = log_save_path.to_str().unwrap();
+        let options = BxesWriteOptions {
+            compression: BxesCompression::None,
+            ..Default::default()
+        };
+
+        log.version = version;
+        write_bxes_with_options(log_save_path, &log, &options).unwrap();
+
+        let read_log = read_bxes(log_save_path).unwrap();
+        assert!(read_log.eq(&log));
+        assert!(read_log.variants.eq(&log.variants));
+
+        let files = BxesMappedLogFiles::open(log_save_path).unwrap();
+        assert_mapped_log_eq(&files.read().unwrap(), &log);
+
+        std::fs::metadata(log_save_path).unwrap().len()
+    };
+
+    let fixed_width_size = read_write(3);
+    let compact_size = read_write(4);
+
+    assert!(compact_size < fixed_width_size);
+}
+
+#[test]
+pub fn test_read_log_with_front_coded_strings() {
+    let names = ["Dictionary", "List", "HashSet", "Queue", "Stack", "LinkedList"];
+    let events = names
+        .iter()
+        .map(|name| create_event(&format!("System.Collections.Generic.{}", name)))
+        .collect::<Vec<_>>();
+
+    let mut builder = BxesEventLogBuilder::new();
+    builder.add_trace(vec![], events);
+    let log = builder.build();
+
+    let temp_dir = TempDir::new().unwrap();
+    let read_write = |string_encoding: BxesStringEncoding| {
+        let log_save_path = temp_dir
+            .path()
+            .join(format!("log_{:?}.bxes", string_encoding));
+        let log_save_path = log_save_path.to_str().unwrap();
+        let options = BxesWriteOptions {
+            compression: BxesCompression::None,
+            sort_strings: true,
+            string_encoding,
+            ..Default::default()
+        };
+
+        write_bxes_with_options(log_save_path, &log, &options).unwrap();
+
+        let read_log = read_bxes(log_save_path).unwrap();
+        assert!(read_log.eq(&log));
+        assert!(read_log.variants.eq(&log.variants));
+
+        let files = BxesMappedLogFiles::open(log_save_path).unwrap();
+        assert_mapped_log_eq(&files.read().unwrap(), &log);
+        assert!(validate_bxes(log_save_path).unwrap().is_valid());
+
+        let lazy_log = read_bxes_lazy(log_save_path).unwrap();
+        assert_eq!(lazy_log.string_encoding, string_encoding);
+
+        let strings = lazy_log
+            .values()
+            .iter()
+            .filter_map(|value| match value.as_ref() {
+                BxesValue::String(string) => Some(string.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(strings.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        std::fs::metadata(log_save_path).unwrap().len()
+    };
+
+    let plain_strings_size = read_write(BxesStringEncoding::Plain);
+    let front_coded_strings_size = read_write(BxesStringEncoding::FrontCoded);
+
+    assert!(front_coded_strings_size < plain_strings_size);
+}
+
+#[test]
+pub fn test_write_with_frequency_index_order() {
+    let create_event = |name: &str, attribute_value: &str| BxesEvent {
+        name: Arc::new(BxesValue::String(name.into())),
+        timestamp: 0,
+        lifecycle: Lifecycle::default(),
+        attributes: Some(vec![(
+            Arc::new(BxesValue::String("key".into())),
+            Arc::new(BxesValue::String(attribute_value.into())),
+        )]),
+    };
+
+    let mut events = vec![create_event("rare", "rare_value")];
+    for _ in 0..10 {
+        events.push(create_event("frequent", "frequent_value"));
+    }
+
+    let mut builder = BxesEventLogBuilder::new();
+    builder.add_trace(vec![], events);
+    let log = builder.build();
+
+    let options = BxesWriteOptions {
+        index_order: BxesIndexOrder::Frequency,
+        ..Default::default()
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+    write_bxes_with_options(log_save_path, &log, &options).unwrap();
+
+    let read_log = read_bxes(log_save_path).unwrap();
+    assert!(read_log.variants.eq(&log.variants));
+
+    let lazy_log = read_bxes_lazy(log_save_path).unwrap();
+    let value = |index: u32| lazy_log.values()[index as usize].clone();
+
+    assert_eq!(*value(0), BxesValue::String("key".into()));
+    assert_eq!(*value(1), BxesValue::String("frequent".into()));
+    assert_eq!(*value(2), BxesValue::String("frequent_value".into()));
+
+    let (key, value_index) = lazy_log.kv_pairs()[0];
+    assert_eq!(*value(key), BxesValue::String("key".into()));
+    assert_eq!(*value(value_index), BxesValue::String("frequent_value".into()));
+}
+
+#[test]
+pub fn test_write_with_frequency_index_order_and_sorted_strings() {
+    let log = generate_random_log();
+    let options = BxesWriteOptions {
+        compression: BxesCompression::None,
+        index_order: BxesIndexOrder::Frequency,
+        sort_strings: true,
+        string_encoding: BxesStringEncoding::FrontCoded,
+        ..Default::default()
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+    write_bxes_with_options(log_save_path, &log, &options).unwrap();
+
+    let read_log = read_bxes(log_save_path).unwrap();
+    assert!(read_log.eq(&log));
+    assert!(read_log.variants.eq(&log.variants));
+
+    let files = BxesMappedLogFiles::open(log_save_path).unwrap();
+    assert_mapped_log_eq(&files.read().unwrap(), &log);
+    assert!(validate_bxes(log_save_path).unwrap().is_valid());
+}
+
+#[test]
+pub fn test_read_write_bytes() {
+    let log = generate_random_log();
+    let bytes = write_bxes_to_bytes(&log).unwrap();
+
+    let read_log = read_bxes_from_bytes(&bytes).unwrap();
+    assert!(read_log.eq(&log));
+    assert!(read_log.variants.eq(&log.variants));
+
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    std::fs::write(&log_save_path, &bytes).unwrap();
+
+    let read_log = read_bxes(log_save_path.to_str().unwrap()).unwrap();
+    assert!(read_log.variants.eq(&log.variants));
+
+    let read_log = read_bxes_from(File::open(&log_save_path).unwrap()).unwrap();
+    assert!(read_log.variants.eq(&log.variants));
+}
+
+#[test]
+pub fn test_single_file_compression() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    for compression in [
+        BxesCompression::None,
+        BxesCompression::Deflate { level: 1 },
+        BxesCompression::Zstd { level: 3 },
+        BxesCompression::Lz4,
+    ] {
+        let options = BxesWriteOptions {
+            compression,
+            ..Default::default()
+        };
+
+        write_bxes_with_options(log_save_path, &log, &options).unwrap();
+        let read_log = read_bxes(log_save_path).unwrap();
+        assert!(read_log.variants.eq(&log.variants));
+
+        let reader = BxesRandomAccessReader::open(log_save_path).unwrap();
+        assert_eq!(reader.variants_count(), log.variants.len());
+
+        let bytes = write_bxes_to_with_options(Cursor::new(vec![]), &log, &options)
+            .unwrap()
+            .into_inner();
+
+        let read_log = read_bxes_from_bytes(&bytes).unwrap();
+        assert!(read_log.variants.eq(&log.variants));
+    }
+}
+
+#[test]
+pub fn test_single_file_mmap_reader() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    write_bxes(log_save_path, &log).unwrap();
+    assert!(BxesMappedLogFiles::open(log_save_path).is_err());
+
+    let options = BxesWriteOptions {
+        compression: BxesCompression::None,
+        ..Default::default()
+    };
+
+    write_bxes_with_options(log_save_path, &log, &options).unwrap();
+
+    let files = BxesMappedLogFiles::open(log_save_path).unwrap();
+    assert_mapped_log_eq(&files.read().unwrap(), &log);
+}
+
+#[test]
+pub fn test_mmap_reader_corrupted_lengths() {
+    let log = create_log(vec![vec![create_event("a")]]);
+    let raw_log = write_raw_log(&log, BxesChecksum::None).bytes;
+
+    let read_mapped = |raw_log: &[u8]| {
+        let temp_dir = TempDir::new().unwrap();
+        let log_save_path = temp_dir.path().join("log.bxes");
+        std::fs::write(&log_save_path, raw_log).unwrap();
+
+        let files = BxesMappedLogFiles::open(log_save_path.to_str().unwrap()).unwrap();
+        files.read().map(|_| ()).map_err(|err| err.to_string())
+    };
+
+    assert!(read_mapped(&raw_log).is_ok());
+
+    // the number of values follows the header
+    let mut corrupted_log = raw_log[..HEADER_SIZE].to_vec();
+    leb128::write::unsigned(&mut corrupted_log, u32::MAX as u64).unwrap();
+    corrupted_log.extend_from_slice(&raw_log[HEADER_SIZE + SMALL_LEB128_SIZE..]);
+    assert!(read_mapped(&corrupted_log).is_err());
+
+    // the string is written as its type id, its length and its bytes
+    let string_offset = raw_log
+        .windows(3)
+        .position(|bytes| bytes == [TypeIds::String as u8, 1, b'a'])
+        .unwrap();
+
+    let mut corrupted_log = raw_log[..string_offset + 1].to_vec();
+    leb128::write::unsigned(&mut corrupted_log, u64::MAX).unwrap();
+    corrupted_log.extend_from_slice(&raw_log[string_offset + 2..]);
+    assert!(read_mapped(&corrupted_log).is_err());
+}
+
+#[test]
+pub fn test_read_archive_with_several_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+
+    let mut zip_writer = ZipWriter::new(File::create(&log_save_path).unwrap());
+    for name in ["first.bxes", "second.bxes"] {
+        zip_writer.start_file(name, FileOptions::default()).unwrap();
+        zip_writer.write_all(&[0; 8]).unwrap();
+    }
+
+    zip_writer.finish().unwrap();
+
+    assert!(matches!(
+        read_bxes(log_save_path.to_str().unwrap()),
+        Err(BxesReadError::InvalidArchive(_))
+    ));
+
+    let bytes = std::fs::read(&log_save_path).unwrap();
+    assert!(matches!(
+        read_bxes_from_bytes(&bytes),
+        Err(BxesReadError::InvalidArchive(_))
+    ));
+}
+
+#[test]
+pub fn test_read_truncated_archive_from_bytes() {
+    let bytes = write_bxes_to_bytes(&generate_random_log()).unwrap();
+
+    let err = read_bxes_from_bytes(&bytes[..bytes.len() / 2]).unwrap_err();
+    assert!(matches!(err, BxesReadError::FailedToReadArchive(_)));
+    assert!(err.source().is_some());
+}
+
+#[test]
+pub fn test_read_error_location() {
+    let log = create_log(vec![vec![create_event("a"), create_event("a")]]);
+    let raw_log = write_raw_log(&log, BxesChecksum::None);
+
+    let second_event = event_offsets(raw_log.variants_offsets[0], 1);
+    let second_event_offset = second_event.start;
+    let lifecycle_offset = second_event.lifecycle;
+
+    let mut raw_log = raw_log.bytes;
+    raw_log[lifecycle_offset] = 0;
+
+    let err = read_bxes_from_bytes(&raw_log).unwrap_err();
+    assert_eq!(
+        err.location(),
+        Some(&BxesErrorLocation {
+            section: Some(BxesSection::Variants),
+            offset: Some(second_event_offset),
+            variant_index: Some(0),
+            event_index: Some(1),
+        })
+    );
+
+    let source = err.source().unwrap().to_string();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Error in variants at offset {}, variant 0, event 1",
+            second_event_offset
+        )
+    );
+    assert!(source.contains(&lifecycle_offset.to_string()));
+
+    assert!(matches!(
+        err,
+        BxesReadError::Located { source, .. } if matches!(
+            *source,
+            BxesReadError::FailedToParseTypeId { type_id: 0, offset } if offset == lifecycle_offset
+        )
+    ));
+}
+
+#[test]
+pub fn test_read_values_out_of_range() {
+    let string = |value: &str| Arc::new(BxesValue::String(value.into()));
+    let artifact = BxesArtifact {
+        items: vec![BxesArtifactItem {
+            model: string("model"),
+            instance: string("instance"),
+            transition: string("transition"),
+        }],
+    };
+
+    let mut event = create_event("a");
+    event.attributes = Some(vec![(
+        string("artifact"),
+        Arc::new(BxesValue::Artifact(artifact)),
+    )]);
+
+    let raw_log = write_raw_log(&create_log(vec![vec![event]]), BxesChecksum::None);
+    let lifecycle_value_offset = event_offsets(raw_log.variants_offsets[0], 0).lifecycle_value;
+    let raw_log = raw_log.bytes;
+
+    let source = |err: BxesReadError| match err {
+        BxesReadError::Located { source, .. } => *source,
+        err => err,
+    };
+
+    // the artifact is written as its type id, items count and indices of model, instance and transition
+    let artifact_offset = raw_log
+        .windows(2)
+        .position(|bytes| bytes == [TypeIds::Artifact as u8, 1])
+        .unwrap();
+
+    let mut corrupted_log = raw_log.clone();
+    corrupted_log[artifact_offset + 2] = 100;
+
+    assert!(matches!(
+        source(read_bxes_from_bytes(&corrupted_log).unwrap_err()),
+        BxesReadError::FailedToIndexValue(100)
+    ));
+
+    let mut corrupted_log = raw_log.clone();
+    corrupted_log[lifecycle_value_offset] = u8::MAX;
+
+    assert!(matches!(
+        source(read_bxes_from_bytes(&corrupted_log).unwrap_err()),
+        BxesReadError::LifecycleOfEventOutOfRange { value: u8::MAX, offset }
+            if offset == lifecycle_value_offset
+    ));
+}
+
+#[test]
+pub fn test_parallel_read_error_location() {
+    let log = create_log(vec![
+        vec![create_event("a")],
+        vec![create_event("a"), create_event("a")],
+    ]);
+
+    let raw_log = write_raw_log(&log, BxesChecksum::None);
+    let lifecycle_offset = event_offsets(raw_log.variants_offsets[1], 0).lifecycle;
+
+    let mut raw_log = raw_log.bytes;
+    raw_log[lifecycle_offset] = 0;
+
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    std::fs::write(&log_save_path, raw_log).unwrap();
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    let err = read_bxes(log_save_path).unwrap_err();
+    let parallel_err = read_bxes_parallel(log_save_path).unwrap_err();
+
+    assert_eq!(err.location().unwrap().variant_index, Some(1));
+    assert_eq!(format!("{:?}", parallel_err), format!("{:?}", err));
+}
+
+#[test]
+pub fn test_read_log_with_checksums() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    let options = BxesWriteOptions {
+        compression: BxesCompression::None,
+        checksum: BxesChecksum::Crc32c,
+        ..Default::default()
+    };
+
+    write_bxes_with_options(log_save_path, &log, &options).unwrap();
+
+    assert!(read_bxes(log_save_path).unwrap().eq(&log));
+    assert!(read_bxes_parallel(log_save_path)
+        .unwrap()
+        .variants
+        .eq(&log.variants));
+
+    let lazy_log = read_bxes_lazy(log_save_path).unwrap();
+    assert_eq!(lazy_log.checksum, BxesChecksum::Crc32c);
+    assert!(lazy_log.items().all(|item| item.is_ok()));
+
+    let files = BxesMappedLogFiles::open(log_save_path).unwrap();
+    assert_mapped_log_eq(&files.read().unwrap(), &log);
+
+    assert_eq!(validate_bxes(log_save_path).unwrap().issues, vec![]);
+}
+
+#[test]
+pub fn test_read_corrupted_log_with_checksums() {
+    let log = create_log(vec![vec![create_event("a"), create_event("a")]]);
+    let raw_log = write_raw_log(&log, BxesChecksum::Crc32c);
+
+    let index_offset = raw_log.index_offset;
+    let variant_offset = raw_log.variants_offsets[0];
+    let raw_log = raw_log.bytes;
+
+    assert!(read_bxes_from_bytes(&raw_log).is_ok());
+
+    // the header precedes the values
+    let values_offset = HEADER_SIZE;
+    let mut corrupted_log = raw_log.clone();
+    let name_offset = raw_log.iter().position(|byte| *byte == b'a').unwrap();
+    corrupted_log[name_offset] = b'b';
+
+    assert!(matches!(
+        read_bxes_from_bytes(&corrupted_log),
+        Err(BxesReadError::ChecksumMismatch { section: BxesSection::Values, offset })
+            if offset == values_offset
+    ));
+
+    let issues = validate_bxes_raw(&corrupted_log).issues;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].section, BxesSection::Values);
+    assert_eq!(issues[0].offset, values_offset);
+    assert_eq!(issues[0].kind, BxesValidationIssueKind::ChecksumMismatch);
+
+    let assert_variant_checksum_mismatch = |corrupted_log: &Vec<u8>| {
+        assert!(matches!(
+            read_bxes_from_bytes(corrupted_log).unwrap_err(),
+            BxesReadError::Located { source, .. } if matches!(
+                *source,
+                BxesReadError::ChecksumMismatch { section: BxesSection::Variants, offset }
+                    if offset == variant_offset
+            )
+        ));
+
+        let issues = validate_bxes_raw(corrupted_log).issues;
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].section, BxesSection::Variants);
+        assert_eq!(issues[0].offset, variant_offset);
+        assert_eq!(issues[0].kind, BxesValidationIssueKind::ChecksumMismatch);
+    };
+
+    let mut corrupted_log = raw_log.clone();
+    corrupted_log[event_offsets(variant_offset, 1).timestamp] = 2;
+    assert_variant_checksum_mismatch(&corrupted_log);
+
+    // the traces count is the first byte of the variant
+    let mut corrupted_log = raw_log.clone();
+    corrupted_log[variant_offset] = 2;
+    assert_variant_checksum_mismatch(&corrupted_log);
+
+    // the events count of the variant follows its offset in the variants index
+    let mut corrupted_log = raw_log.clone();
+    corrupted_log[index_offset + size_of::<u64>()] = 3;
+
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    std::fs::write(&log_save_path, corrupted_log).unwrap();
+
+    assert!(matches!(
+        BxesRandomAccessReader::open(log_save_path.to_str().unwrap()),
+        Err(BxesReadError::ChecksumMismatch { section: BxesSection::VariantsIndex, offset })
+            if offset == index_offset
+    ));
+}
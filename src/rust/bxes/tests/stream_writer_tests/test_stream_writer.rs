@@ -0,0 +1,265 @@
+use std::{fs, path::Path};
+
+use bxes::{
+    constants::{
+        COMPACT_LENGTHS_VERSION, KEY_VALUES_FILE_NAME, METADATA_FILE_NAME, VALUES_FILE_NAME,
+        VARIANTS_FILE_NAME,
+    },
+    models::{BxesChecksum, BxesEventLog, BxesTraceVariantStart},
+    read::{
+        bxes_validator::validate_bxes, errors::BxesReadError,
+        random_access_bxes_reader::BxesRandomAccessReader, single_file_bxes_reader::read_bxes,
+    },
+    writer::{
+        errors::BxesWriteError,
+        multiple_file_bxes_writer::{
+            write_bxes_multiple_files, write_bxes_multiple_files_with_options,
+        },
+        single_file_bxes_writer::write_bxes_with_options,
+        stream::{
+            bxes_stream_writer::{BxesStreamEvent, BxesStreamWriter},
+            multiple_file_bxes_stream_writer::MultipleFilesBxesStreamWriter,
+            single_file_bxes_stream_writer::SingleFileBxesStreamWriter,
+        },
+        write_options::{BxesIndexOrder, BxesWriteOptions},
+    },
+};
+use tempfile::TempDir;
+
+use crate::test_core::random_log::generate_random_log;
+
+#[test]
+pub fn test_multiple_files_stream_writer() {
+    let log = generate_random_log();
+
+    let expected_dir = TempDir::new().unwrap();
+    let expected_dir_path = expected_dir.path().to_str().unwrap();
+    write_bxes_multiple_files(&log, expected_dir_path).unwrap();
+
+    let actual_dir = TempDir::new().unwrap();
+    let actual_dir_path = actual_dir.path().to_str().unwrap();
+    let mut writer = MultipleFilesBxesStreamWriter::new(actual_dir_path, log.version).unwrap();
+    write_log_events(&log, &mut writer);
+    writer.finish().unwrap();
+
+    for file_name in [
+        VALUES_FILE_NAME,
+        KEY_VALUES_FILE_NAME,
+        METADATA_FILE_NAME,
+        VARIANTS_FILE_NAME,
+    ] {
+        let expected = fs::read(Path::new(expected_dir_path).join(file_name)).unwrap();
+        let actual = fs::read(Path::new(actual_dir_path).join(file_name)).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+pub fn test_single_file_stream_writer() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    let mut writer = SingleFileBxesStreamWriter::new(log_save_path, log.version).unwrap();
+    write_log_events(&log, &mut writer);
+    writer.finish().unwrap();
+
+    let read_log = read_bxes(log_save_path).unwrap();
+    assert!(read_log.eq(&log))
+}
+
+#[test]
+pub fn test_stream_writers_with_checksums() {
+    let log = generate_random_log();
+    let options = BxesWriteOptions {
+        checksum: BxesChecksum::Crc32c,
+        version: Some(log.version),
+        ..Default::default()
+    };
+
+    let expected_dir = TempDir::new().unwrap();
+    let expected_dir_path = expected_dir.path().to_str().unwrap();
+    write_bxes_multiple_files_with_options(&log, expected_dir_path, &options).unwrap();
+
+    let actual_dir = TempDir::new().unwrap();
+    let actual_dir_path = actual_dir.path().to_str().unwrap();
+    let mut writer =
+        MultipleFilesBxesStreamWriter::with_options(actual_dir_path, &options).unwrap();
+    write_log_events(&log, &mut writer);
+    writer.finish().unwrap();
+
+    for file_name in [
+        VALUES_FILE_NAME,
+        KEY_VALUES_FILE_NAME,
+        METADATA_FILE_NAME,
+        VARIANTS_FILE_NAME,
+    ] {
+        let expected = fs::read(Path::new(expected_dir_path).join(file_name)).unwrap();
+        let actual = fs::read(Path::new(actual_dir_path).join(file_name)).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    let log_save_path = actual_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    let mut writer = SingleFileBxesStreamWriter::with_options(log_save_path, options).unwrap();
+    write_log_events(&log, &mut writer);
+    writer.finish().unwrap();
+
+    let read_log = read_bxes(log_save_path).unwrap();
+    assert!(read_log.eq(&log))
+}
+
+fn write_log_events(log: &BxesEventLog, writer: &mut impl BxesStreamWriter) {
+    for event in create_stream_events(log) {
+        writer.handle_event(event).unwrap();
+    }
+}
+
+fn create_stream_events(log: &BxesEventLog) -> Vec<BxesStreamEvent> {
+    let mut events = vec![];
+
+    if let Some(properties) = log.metadata.properties.as_ref() {
+        for property in properties {
+            events.push(BxesStreamEvent::LogMetadataProperty(property.clone()));
+        }
+    }
+
+    if let Some(extensions) = log.metadata.extensions.as_ref() {
+        for extension in extensions {
+            events.push(BxesStreamEvent::LogMetadataExtension(extension.clone()));
+        }
+    }
+
+    if let Some(globals) = log.metadata.globals.as_ref() {
+        for global in globals {
+            events.push(BxesStreamEvent::LogMetadataGlobal(global.clone()));
+        }
+    }
+
+    if let Some(classifiers) = log.metadata.classifiers.as_ref() {
+        for classifier in classifiers {
+            events.push(BxesStreamEvent::LogMetadataClassifier(classifier.clone()));
+        }
+    }
+
+    for variant in &log.variants {
+        events.push(BxesStreamEvent::TraceVariantStart(BxesTraceVariantStart {
+            traces_count: variant.traces_count,
+            metadata: variant.metadata.clone(),
+        }));
+
+        for event in &variant.events {
+            events.push(BxesStreamEvent::Event(event.clone()));
+        }
+    }
+
+    events
+}
+
+#[test]
+pub fn test_stream_writer_variants_index() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    let mut writer = SingleFileBxesStreamWriter::new(log_save_path, log.version).unwrap();
+    write_log_events(&log, &mut writer);
+    writer.finish().unwrap();
+
+    let mut reader = BxesRandomAccessReader::open(log_save_path).unwrap();
+    assert_eq!(reader.variants_count(), log.variants.len());
+
+    for index in (0..log.variants.len()).rev() {
+        assert!(reader.read_variant(index).unwrap().eq(&log.variants[index]));
+    }
+
+    assert_eq!(validate_bxes(log_save_path).unwrap().issues, vec![]);
+
+    let options = BxesWriteOptions {
+        variants_index: false,
+        version: Some(log.version),
+        ..Default::default()
+    };
+
+    let mut writer =
+        SingleFileBxesStreamWriter::with_options(log_save_path, options.clone()).unwrap();
+    write_log_events(&log, &mut writer);
+    writer.finish().unwrap();
+
+    assert!(matches!(
+        BxesRandomAccessReader::open(log_save_path),
+        Err(BxesReadError::MissingVariantsIndex)
+    ));
+
+    write_bxes_with_options(log_save_path, &log, &options).unwrap();
+    assert!(matches!(
+        BxesRandomAccessReader::open(log_save_path),
+        Err(BxesReadError::MissingVariantsIndex)
+    ));
+}
+
+#[test]
+pub fn test_stream_writers_reject_reordering_options() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    for options in [
+        BxesWriteOptions {
+            index_order: BxesIndexOrder::Frequency,
+            ..Default::default()
+        },
+        BxesWriteOptions {
+            sort_strings: true,
+            ..Default::default()
+        },
+    ] {
+        assert!(matches!(
+            MultipleFilesBxesStreamWriter::with_options(temp_dir_path, &options),
+            Err(BxesWriteError::UnsupportedStreamOption(_))
+        ));
+
+        assert!(matches!(
+            SingleFileBxesStreamWriter::with_options(log_save_path, options),
+            Err(BxesWriteError::UnsupportedStreamOption(_))
+        ));
+    }
+}
+
+#[test]
+pub fn test_stream_writer_options_version() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_save_path = temp_dir.path().join("log.bxes");
+    let log_save_path = log_save_path.to_str().unwrap();
+
+    let options = BxesWriteOptions {
+        version: Some(COMPACT_LENGTHS_VERSION),
+        ..Default::default()
+    };
+
+    let mut writer = SingleFileBxesStreamWriter::with_options(log_save_path, options).unwrap();
+    write_log_events(&log, &mut writer);
+    writer.finish().unwrap();
+
+    assert_eq!(
+        read_bxes(log_save_path).unwrap().version,
+        COMPACT_LENGTHS_VERSION
+    );
+
+    let options = BxesWriteOptions {
+        version: Some(0),
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        SingleFileBxesStreamWriter::with_options(log_save_path, options),
+        Err(BxesWriteError::UnsupportedVersion(0))
+    ));
+}
@@ -0,0 +1,29 @@
+use bxes::{models::BxesEventLog, read::mmap_bxes_reader::BxesMappedEventLog};
+
+pub fn assert_mapped_log_eq(mapped_log: &BxesMappedEventLog, log: &BxesEventLog) {
+    assert_eq!(mapped_log.version, log.version);
+    assert_eq!(mapped_log.metadata().unwrap(), log.metadata);
+    assert_eq!(mapped_log.variants_count() as usize, log.variants.len());
+
+    for (mapped_variant, variant) in mapped_log.variants().zip(&log.variants) {
+        let mapped_variant = mapped_variant.unwrap();
+        assert_eq!(mapped_variant.traces_count, variant.traces_count);
+        assert_eq!(mapped_variant.events.len(), variant.events.len());
+
+        for (mapped_event, event) in mapped_variant.events.iter().zip(&variant.events) {
+            let name = mapped_log.value(mapped_event.name_index).unwrap();
+            assert_eq!(name.to_value(), *event.name);
+            assert_eq!(mapped_event.timestamp, event.timestamp);
+            assert_eq!(mapped_event.lifecycle, event.lifecycle);
+
+            let attributes = event.attributes.clone().unwrap_or_default();
+            assert_eq!(mapped_event.attributes.len(), attributes.len());
+
+            for (kv_index, (key, value)) in mapped_event.attributes.iter().zip(&attributes) {
+                let (mapped_key, mapped_value) = mapped_log.kv_pair(*kv_index).unwrap();
+                assert_eq!(mapped_key.to_value(), **key);
+                assert_eq!(mapped_value.to_value(), **value);
+            }
+        }
+    }
+}
@@ -1 +1,3 @@
+pub mod mapped_log;
 pub mod random_log;
+pub mod raw_log;
@@ -1,6 +1,7 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use bxes::{
+    constants::BXES_VERSION,
     models::{
         BrafLifecycle, BxesArtifact, BxesArtifactItem, BxesClassifier, BxesDriver, BxesDrivers,
         BxesEvent, BxesEventLog, BxesEventLogMetadata, BxesExtension, BxesGlobal, BxesGlobalKind,
@@ -19,7 +20,7 @@ use uuid::Uuid;
 pub fn generate_random_log() -> BxesEventLog {
     let mut rng = rand::thread_rng();
     BxesEventLog {
-        version: rng.gen(),
+        version: BXES_VERSION,
         metadata: generate_random_metadata(&mut rng),
         variants: generate_random_variants(&mut rng),
     }
@@ -56,7 +57,7 @@ fn generate_random_classifiers(rng: &mut ThreadRng) -> Vec<BxesClassifier> {
     })
 }
 
-fn generate_random_values(rng: &mut ThreadRng) -> Vec<Rc<Box<BxesValue>>> {
+fn generate_random_values(rng: &mut ThreadRng) -> Vec<Arc<BxesValue>> {
     generate_random_list(rng, |rng| generate_random_bxes_value(rng))
 }
 
@@ -112,19 +113,20 @@ fn generate_random_event(rng: &mut ThreadRng) -> BxesEvent {
     BxesEvent {
         name: generate_random_string_bxes_value(rng),
         timestamp: rng.gen(),
+        lifecycle: generate_random_lifecycle(rng),
         attributes: generate_random_attributes_option(rng),
     }
 }
 
 fn generate_random_attributes(
     rng: &mut ThreadRng,
-) -> Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)> {
+) -> Vec<(Arc<BxesValue>, Arc<BxesValue>)> {
     generate_random_list(rng, |rng| generate_random_attribute(rng))
 }
 
 fn generate_random_attributes_option(
     rng: &mut ThreadRng,
-) -> Option<Vec<(Rc<Box<BxesValue>>, Rc<Box<BxesValue>>)>> {
+) -> Option<Vec<(Arc<BxesValue>, Arc<BxesValue>)>> {
     if rng.gen_bool(0.8) {
         Some(generate_random_attributes(rng))
     } else {
@@ -132,15 +134,15 @@ fn generate_random_attributes_option(
     }
 }
 
-fn generate_random_attribute(rng: &mut ThreadRng) -> (Rc<Box<BxesValue>>, Rc<Box<BxesValue>>) {
+fn generate_random_attribute(rng: &mut ThreadRng) -> (Arc<BxesValue>, Arc<BxesValue>) {
     (
         generate_random_string_bxes_value(rng),
         generate_random_bxes_value(rng),
     )
 }
 
-fn generate_random_string_bxes_value(rng: &mut ThreadRng) -> Rc<Box<BxesValue>> {
-    Rc::new(Box::new(BxesValue::String(Rc::new(Box::new(generate_random_string(rng))))))
+fn generate_random_string_bxes_value(rng: &mut ThreadRng) -> Arc<BxesValue> {
+    Arc::new(BxesValue::String(Arc::from(generate_random_string(rng))))
 }
 
 fn generate_random_string(rng: &mut ThreadRng) -> String {
@@ -151,8 +153,8 @@ fn generate_random_string(rng: &mut ThreadRng) -> String {
         .collect()
 }
 
-fn generate_random_bxes_value(rng: &mut ThreadRng) -> Rc<Box<BxesValue>> {
-    Rc::new(Box::new(
+fn generate_random_bxes_value(rng: &mut ThreadRng) -> Arc<BxesValue> {
+    Arc::new(
         match TypeIds::from_u8(rng.gen_range(0..TypeIds::VARIANT_COUNT) as u8).unwrap() {
             TypeIds::I32 => BxesValue::Int32(rng.gen()),
             TypeIds::I64 => BxesValue::Int64(rng.gen()),
@@ -161,7 +163,7 @@ fn generate_random_bxes_value(rng: &mut ThreadRng) -> Rc<Box<BxesValue>> {
             TypeIds::F32 => BxesValue::Float32(rng.gen()),
             TypeIds::F64 => BxesValue::Float64(rng.gen()),
             TypeIds::Bool => BxesValue::Bool(rng.gen()),
-            TypeIds::String => BxesValue::String(Rc::new(Box::new(generate_random_string(rng)))),
+            TypeIds::String => BxesValue::String(Arc::from(generate_random_string(rng))),
             TypeIds::Timestamp => BxesValue::Timestamp(rng.gen()),
             TypeIds::BrafLifecycle => BxesValue::BrafLifecycle(generate_random_braf_lifecycle()),
             TypeIds::StandardLifecycle => {
@@ -177,7 +179,7 @@ fn generate_random_bxes_value(rng: &mut ThreadRng) -> Rc<Box<BxesValue>> {
             TypeIds::Drivers => generate_random_drivers(rng),
             _ => panic!("Got unknown type id"),
         },
-    ))
+    )
 }
 
 fn generate_random_drivers(rng: &mut ThreadRng) -> BxesValue {
@@ -194,10 +196,8 @@ fn generate_random_drivers(rng: &mut ThreadRng) -> BxesValue {
 fn generate_random_driver(rng: &mut ThreadRng) -> BxesDriver {
     BxesDriver {
         amount: BxesValue::Float64(rng.gen()),
-        name: Rc::new(Box::new(BxesValue::String(Rc::new(Box::new(generate_random_string(rng)))))),
-        driver_type: Rc::new(
-            Box::new(BxesValue::String(Rc::new(Box::new(generate_random_string(rng)))))
-        ),
+        name: Arc::new(BxesValue::String(Arc::from(generate_random_string(rng)))),
+        driver_type: Arc::new(BxesValue::String(Arc::from(generate_random_string(rng)))),
     }
 }
 
@@ -214,13 +214,9 @@ fn generate_random_artifact(rng: &mut ThreadRng) -> BxesValue {
 
 fn generate_random_artifact_item(rng: &mut ThreadRng) -> BxesArtifactItem {
     BxesArtifactItem {
-        model: Rc::new(Box::new(BxesValue::String(Rc::new(Box::new(generate_random_string(rng)))))),
-        instance: Rc::new(
-            Box::new(BxesValue::String(Rc::new(Box::new(generate_random_string(rng)))))
-        ),
-        transition: Rc::new(
-            Box::new(BxesValue::String(Rc::new(Box::new(generate_random_string(rng)))))
-        ),
+        model: Arc::new(BxesValue::String(Arc::from(generate_random_string(rng)))),
+        instance: Arc::new(BxesValue::String(Arc::from(generate_random_string(rng)))),
+        transition: Arc::new(BxesValue::String(Arc::from(generate_random_string(rng)))),
     }
 }
 
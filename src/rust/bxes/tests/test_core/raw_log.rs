@@ -0,0 +1,118 @@
+use std::{io::Cursor, sync::Arc};
+
+use bxes::{
+    builder::bxes_event_log_builder::BxesEventLogBuilder,
+    constants::{VARIANTS_INDEX_ENTRY_SIZE, VARIANTS_INDEX_TRAILER_SIZE},
+    models::{BxesChecksum, BxesEvent, BxesEventLog, BxesValue, Lifecycle},
+    writer::{
+        single_file_bxes_writer::write_bxes_to_with_options,
+        write_options::{BxesCompression, BxesWriteOptions},
+    },
+};
+
+/// The version, the encoding of strings and the kind of checksums precede the values.
+pub const HEADER_SIZE: usize = std::mem::size_of::<u32>() + 2 * std::mem::size_of::<u8>();
+
+/// Counts, indices and timestamp deltas of logs created by `create_log` take one byte.
+pub const SMALL_LEB128_SIZE: usize = 1;
+
+/// The timestamp of the first event of a variant is written as is.
+pub const TIMESTAMP_SIZE: usize = std::mem::size_of::<i64>();
+
+/// The lifecycle of an event is written as its type id and value.
+pub const LIFECYCLE_SIZE: usize = 2;
+
+/// The traces count, the metadata count and the events count precede events of a variant without metadata.
+pub const VARIANT_EVENTS_OFFSET: usize = 3 * SMALL_LEB128_SIZE;
+
+/// Uncompressed single-file log along with offsets read from its variants index.
+pub struct RawLog {
+    pub bytes: Vec<u8>,
+    pub index_offset: usize,
+    pub variants_offsets: Vec<usize>,
+}
+
+/// Offsets of fields of an event in the raw log, see `event_offsets`.
+pub struct EventOffsets {
+    pub start: usize,
+    pub timestamp: usize,
+    pub lifecycle: usize,
+    pub lifecycle_value: usize,
+}
+
+pub fn create_event(name: &str) -> BxesEvent {
+    BxesEvent {
+        name: Arc::new(BxesValue::String(name.into())),
+        timestamp: 0,
+        lifecycle: Lifecycle::default(),
+        attributes: None,
+    }
+}
+
+pub fn create_log(traces: Vec<Vec<BxesEvent>>) -> BxesEventLog {
+    let mut builder = BxesEventLogBuilder::new();
+    for events in traces {
+        builder.add_trace(vec![], events);
+    }
+
+    builder.build()
+}
+
+/// Writes the log without compression and reads offsets of its variants from the variants index.
+pub fn write_raw_log(log: &BxesEventLog, checksum: BxesChecksum) -> RawLog {
+    let options = BxesWriteOptions {
+        compression: BxesCompression::None,
+        checksum,
+        ..Default::default()
+    };
+
+    let bytes = write_bxes_to_with_options(Cursor::new(vec![]), log, &options)
+        .unwrap()
+        .into_inner();
+
+    let read_u64 =
+        |offset: usize| u64::from_le_bytes(bytes[offset..][..8].try_into().unwrap()) as usize;
+
+    // the trailer is preceded by the number of variants
+    let trailer_offset = bytes.len() - VARIANTS_INDEX_TRAILER_SIZE - checksum.size();
+    let index_offset = read_u64(trailer_offset);
+    let variants_count = u32::from_le_bytes(bytes[trailer_offset - 4..][..4].try_into().unwrap());
+
+    let variants_offsets = (0..variants_count as usize)
+        .map(|index| read_u64(index_offset + index * VARIANTS_INDEX_ENTRY_SIZE))
+        .collect();
+
+    RawLog {
+        bytes,
+        index_offset,
+        variants_offsets,
+    }
+}
+
+/// Returns offsets of fields of the event of the variant at `variant_offset` in a log created by `create_log`,
+/// events which precede the event must have no attributes and the same timestamp as the first event.
+pub fn event_offsets(variant_offset: usize, event_index: usize) -> EventOffsets {
+    let first_event_size = SMALL_LEB128_SIZE + TIMESTAMP_SIZE + LIFECYCLE_SIZE + SMALL_LEB128_SIZE;
+    let event_size = 3 * SMALL_LEB128_SIZE + LIFECYCLE_SIZE;
+
+    let start = variant_offset
+        + VARIANT_EVENTS_OFFSET
+        + match event_index {
+            0 => 0,
+            _ => first_event_size + (event_index - 1) * event_size,
+        };
+
+    let timestamp = start + SMALL_LEB128_SIZE;
+    let lifecycle = timestamp
+        + match event_index {
+            0 => TIMESTAMP_SIZE,
+            _ => SMALL_LEB128_SIZE,
+        };
+
+    EventOffsets {
+        start,
+        timestamp,
+        lifecycle,
+        lifecycle_value: lifecycle + 1,
+    }
+}
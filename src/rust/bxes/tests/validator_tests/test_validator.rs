@@ -0,0 +1,237 @@
+use std::{fs, path::Path, sync::Arc};
+
+use bxes::{
+    constants::KEY_VALUES_FILE_NAME,
+    models::{BxesArtifact, BxesArtifactItem, BxesValue},
+    read::{
+        bxes_validator::{
+            validate_bxes, validate_bxes_multiple_files, validate_bxes_raw, BxesValidationIssue,
+            BxesValidationIssueKind,
+        },
+        errors::{BxesReadError, BxesSection},
+        single_file_bxes_reader::{read_bxes, read_bxes_lazy},
+    },
+    type_ids::TypeIds,
+    writer::{
+        multiple_file_bxes_writer::write_bxes_multiple_files,
+        single_file_bxes_writer::{write_bxes, write_bxes_with_options},
+        write_options::{BxesCompression, BxesWriteOptions},
+    },
+};
+use tempfile::TempDir;
+
+use crate::test_core::{
+    random_log::generate_random_log,
+    raw_log::{create_event, create_log},
+};
+
+#[test]
+pub fn test_validate_valid_logs() {
+    let log = generate_random_log();
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("log.bxes");
+    let log_path = log_path.to_str().unwrap();
+    let temp_dir_path = temp_dir.path().to_str().unwrap();
+
+    write_bxes(log_path, &log).unwrap();
+    write_bxes_multiple_files(&log, temp_dir_path).unwrap();
+
+    for report in [
+        validate_bxes(log_path).unwrap(),
+        validate_bxes_multiple_files(temp_dir_path).unwrap(),
+    ] {
+        assert_eq!(report.issues, vec![]);
+        assert_eq!(report.variants_count, log.variants.len() as u32);
+    }
+
+    let kv_pairs_path = Path::new(temp_dir_path).join(KEY_VALUES_FILE_NAME);
+    let mut bytes = fs::read(&kv_pairs_path).unwrap();
+    bytes[..4].copy_from_slice(&1u32.to_le_bytes());
+    fs::write(&kv_pairs_path, bytes).unwrap();
+
+    let report = validate_bxes_multiple_files(temp_dir_path).unwrap();
+    assert_eq!(
+        report.issues,
+        vec![create_issue(
+            BxesSection::KeyValues,
+            0,
+            BxesValidationIssueKind::VersionsMismatch {
+                expected: log.version,
+                actual: 1
+            }
+        )]
+    );
+}
+
+#[test]
+pub fn test_validate_corrupted_log() {
+    let raw_log = create_corrupted_raw_log();
+
+    let report = validate_bxes_raw(&raw_log);
+    assert_eq!(
+        report.issues,
+        vec![
+            create_issue(
+                BxesSection::Values,
+                23,
+                BxesValidationIssueKind::DuplicatedValue {
+                    index: 2,
+                    first_index: 0
+                }
+            ),
+            create_issue(
+                BxesSection::KeyValues,
+                37,
+                BxesValidationIssueKind::NonStringAttributeKey(0)
+            ),
+            create_issue(
+                BxesSection::Metadata,
+                43,
+                BxesValidationIssueKind::DanglingKeyValueIndex(3)
+            ),
+            create_issue(
+                BxesSection::Metadata,
+                55,
+                BxesValidationIssueKind::InvalidEnumValue(7)
+            ),
+            create_issue(
+                BxesSection::Variants,
+                80,
+                BxesValidationIssueKind::DanglingValueIndex(5)
+            ),
+            create_issue(
+                BxesSection::Variants,
+                90,
+                BxesValidationIssueKind::InvalidEnumValue(99)
+            ),
+            create_issue(
+                BxesSection::Values,
+                23,
+                BxesValidationIssueKind::UnusedValue(2)
+            ),
+        ]
+    );
+
+    let report = validate_bxes_raw(&raw_log[..85]);
+    assert_eq!(
+        report.issues.last(),
+        Some(&create_issue(
+            BxesSection::Variants,
+            81,
+            BxesValidationIssueKind::Truncated
+        ))
+    );
+}
+
+#[test]
+pub fn test_validate_forward_nested_value_index() {
+    let string = |value: &str| Arc::new(BxesValue::String(value.into()));
+    let artifact = BxesArtifact {
+        items: vec![BxesArtifactItem {
+            model: string("model"),
+            instance: string("instance"),
+            transition: string("transition"),
+        }],
+    };
+
+    let mut event = create_event("a");
+    event.attributes = Some(vec![(
+        string("artifact"),
+        Arc::new(BxesValue::Artifact(artifact)),
+    )]);
+
+    let log = create_log(vec![vec![event]]);
+
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("log.bxes");
+    let log_path = log_path.to_str().unwrap();
+    let options = BxesWriteOptions {
+        compression: BxesCompression::None,
+        ..Default::default()
+    };
+
+    write_bxes_with_options(log_path, &log, &options).unwrap();
+
+    let artifact_index = read_bxes_lazy(log_path)
+        .unwrap()
+        .values()
+        .iter()
+        .position(|value| matches!(value.as_ref(), BxesValue::Artifact(_)))
+        .unwrap();
+
+    // the artifact is written as its type id, items count and indices of model, instance and transition,
+    // the model index is replaced with the index of the artifact itself
+    let mut raw_log = fs::read(log_path).unwrap();
+    let model_offset = raw_log
+        .windows(2)
+        .position(|bytes| bytes == [TypeIds::Artifact as u8, 1])
+        .unwrap()
+        + 2;
+
+    raw_log[model_offset] = artifact_index as u8;
+    fs::write(log_path, &raw_log).unwrap();
+
+    assert!(matches!(
+        read_bxes(log_path).unwrap_err(),
+        BxesReadError::Located { source, .. } if matches!(
+            *source,
+            BxesReadError::FailedToIndexValue(index) if index == artifact_index
+        )
+    ));
+
+    assert!(validate_bxes_raw(&raw_log).issues.contains(&create_issue(
+        BxesSection::Values,
+        model_offset,
+        BxesValidationIssueKind::DanglingValueIndex(artifact_index as u32)
+    )));
+}
+
+fn create_corrupted_raw_log() -> Vec<u8> {
+    let mut raw_log = vec![];
+    let write_u32 =
+        |raw_log: &mut Vec<u8>, value: u32| raw_log.extend_from_slice(&value.to_le_bytes());
+
+    write_u32(&mut raw_log, 2);
+
+    write_u32(&mut raw_log, 3);
+    for value in [
+        &[6u8, 1, 0, 0, 0, 0, 0, 0, 0, b'a'][..],
+        &[0, 5, 0, 0, 0],
+        &[6, 1, 0, 0, 0, 0, 0, 0, 0, b'a'],
+    ] {
+        raw_log.extend_from_slice(value);
+    }
+
+    write_u32(&mut raw_log, 1);
+    raw_log.extend_from_slice(&[1, 0]);
+
+    write_u32(&mut raw_log, 1);
+    write_u32(&mut raw_log, 3);
+    write_u32(&mut raw_log, 0);
+    write_u32(&mut raw_log, 1);
+    raw_log.push(7);
+    write_u32(&mut raw_log, 0);
+    write_u32(&mut raw_log, 0);
+
+    write_u32(&mut raw_log, 1);
+    write_u32(&mut raw_log, 1);
+    write_u32(&mut raw_log, 0);
+    write_u32(&mut raw_log, 1);
+    raw_log.push(5);
+    raw_log.extend_from_slice(&0i64.to_le_bytes());
+    raw_log.extend_from_slice(&[10, 99, 0]);
+
+    raw_log
+}
+
+fn create_issue(
+    section: BxesSection,
+    offset: usize,
+    kind: BxesValidationIssueKind,
+) -> BxesValidationIssue {
+    BxesValidationIssue {
+        section,
+        offset,
+        kind,
+    }
+}
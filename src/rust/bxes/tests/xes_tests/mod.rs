@@ -0,0 +1,2 @@
+pub mod test_bxes_to_xes;
+pub mod test_xes_to_bxes;
@@ -0,0 +1,87 @@
+use bxes::{
+    models::{BrafLifecycle, BxesEventLog, Lifecycle, StandardLifecycle},
+    read::single_file_bxes_reader::{read_bxes, read_bxes_from_bytes},
+    writer::single_file_bxes_writer::{write_bxes, write_bxes_to_bytes},
+    xes::{
+        bxes_to_xes_converter::{convert_bxes_to_xes, write_xes_to},
+        xes_to_bxes_converter::{read_xes, read_xes_from},
+    },
+};
+use tempfile::TempDir;
+
+use super::test_xes_to_bxes::TEST_LOG;
+
+#[test]
+pub fn test_write_xes() {
+    let log = read_xes_from(TEST_LOG.as_bytes()).unwrap();
+    let xes = String::from_utf8(write_xes_to(&log, vec![]).unwrap()).unwrap();
+
+    assert_eq!(xes.matches("<trace>").count(), 3);
+    assert!(xes.contains(r#"<date key="time:timestamp" value="2023-11-30T12:00:01+00:00"/>"#));
+    assert!(xes.contains(r#"<string key="lifecycle:transition" value="Closed.Cancelled"/>"#));
+    assert!(xes.contains(r#"<list key="artifactlifecycle:moves">"#));
+    assert!(xes.contains(r#"<string key="cost:driver" value="driver">"#));
+
+    let read_log = read_xes_from(xes.as_bytes()).unwrap();
+    assert!(read_log.eq(&log));
+}
+
+#[test]
+pub fn test_convert_bxes_to_xes() {
+    let log = read_xes_from(TEST_LOG.as_bytes()).unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let bxes_path = temp_dir.path().join("log.bxes");
+    let xes_path = temp_dir.path().join("log.xes");
+    let bxes_path = bxes_path.to_str().unwrap();
+    let xes_path = xes_path.to_str().unwrap();
+
+    write_bxes(bxes_path, &log).unwrap();
+    convert_bxes_to_xes(bxes_path, xes_path).unwrap();
+
+    let xes_log = read_xes(xes_path).unwrap();
+    assert!(xes_log.eq(&read_bxes(bxes_path).unwrap()));
+}
+
+#[test]
+pub fn test_lifecycle_round_trip() {
+    let log = read_xes_from(TEST_LOG.as_bytes()).unwrap();
+    let bxes_log = read_bxes_from_bytes(&write_bxes_to_bytes(&log).unwrap()).unwrap();
+    let xes = write_xes_to(&bxes_log, vec![]).unwrap();
+    let read_log = read_xes_from(xes.as_slice()).unwrap();
+
+    let lifecycles = |log: &BxesEventLog| {
+        log.variants
+            .iter()
+            .flat_map(|variant| variant.events.iter().map(|event| event.lifecycle.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        lifecycles(&read_log)[..2],
+        [
+            Lifecycle::Standard(StandardLifecycle::Start),
+            Lifecycle::Braf(BrafLifecycle::ClosedCancelled)
+        ]
+    );
+    assert_eq!(lifecycles(&read_log), lifecycles(&log));
+    assert_eq!(lifecycles(&bxes_log), lifecycles(&log));
+    assert!(read_log.variants.eq(&log.variants));
+}
+
+#[test]
+pub fn test_unrecognised_lifecycle_round_trip() {
+    let log = r#"<log xes.version="1.0">
+        <trace>
+            <event>
+                <string key="concept:name" value="A"/>
+                <string key="lifecycle:transition" value="custom"/>
+            </event>
+        </trace>
+    </log>"#;
+
+    let log = read_xes_from(log.as_bytes()).unwrap();
+    let xes = String::from_utf8(write_xes_to(&log, vec![]).unwrap()).unwrap();
+
+    assert!(xes.contains(r#"<string key="lifecycle:transition" value="unspecified"/>"#));
+}
@@ -0,0 +1,190 @@
+use std::{error::Error, sync::Arc};
+
+use bxes::{
+    models::{BrafLifecycle, BxesGlobalKind, BxesValue, Lifecycle, StandardLifecycle},
+    read::single_file_bxes_reader::read_bxes,
+    xes::{
+        errors::{XesReadError, XesToBxesConversionError},
+        xes_to_bxes_converter::{convert_xes_to_bxes, read_xes, read_xes_from},
+    },
+};
+use tempfile::TempDir;
+
+pub const TEST_LOG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<log xes.version="1.0">
+    <extension name="Concept" prefix="concept" uri="http://www.xes-standard.org/concept.xesext"/>
+    <global scope="event">
+        <string key="concept:name" value="__INVALID__"/>
+    </global>
+    <classifier name="Activity" keys="concept:name lifecycle:transition"/>
+    <string key="source" value="test"/>
+    <trace>
+        <string key="concept:name" value="1"/>
+        <event>
+            <string key="concept:name" value="A"/>
+            <date key="time:timestamp" value="2023-11-30T12:00:00.000+00:00"/>
+            <string key="lifecycle:transition" value="start"/>
+            <int key="cost" value="12"/>
+        </event>
+        <event>
+            <string key="concept:name" value="B"/>
+            <date key="time:timestamp" value="2023-11-30T12:00:01.000+00:00"/>
+            <string key="lifecycle:transition" value="Closed.Cancelled"/>
+            <list key="artifactlifecycle:moves">
+                <values>
+                    <string key="artifactlifecycle:model" value="model">
+                        <string key="artifactlifecycle:instance" value="instance"/>
+                        <string key="artifactlifecycle:transition" value="transition"/>
+                    </string>
+                </values>
+            </list>
+            <list key="cost:drivers">
+                <values>
+                    <string key="cost:driver" value="driver">
+                        <float key="cost:amount" value="1.5"/>
+                        <string key="cost:type" value="type"/>
+                    </string>
+                </values>
+            </list>
+        </event>
+    </trace>
+    <trace>
+        <string key="concept:name" value="2"/>
+        <event>
+            <string key="concept:name" value="A"/>
+            <date key="time:timestamp" value="2023-11-30T12:00:00.000+00:00"/>
+            <string key="lifecycle:transition" value="start"/>
+            <int key="cost" value="12"/>
+        </event>
+        <event>
+            <string key="concept:name" value="B"/>
+            <date key="time:timestamp" value="2023-11-30T12:00:01.000+00:00"/>
+            <string key="lifecycle:transition" value="Closed.Cancelled"/>
+            <list key="artifactlifecycle:moves">
+                <values>
+                    <string key="artifactlifecycle:model" value="model">
+                        <string key="artifactlifecycle:instance" value="instance"/>
+                        <string key="artifactlifecycle:transition" value="transition"/>
+                    </string>
+                </values>
+            </list>
+            <list key="cost:drivers">
+                <values>
+                    <string key="cost:driver" value="driver">
+                        <float key="cost:amount" value="1.5"/>
+                        <string key="cost:type" value="type"/>
+                    </string>
+                </values>
+            </list>
+        </event>
+    </trace>
+    <trace>
+        <event>
+            <date key="time:timestamp" value="2023-11-30T12:00:02.000+00:00"/>
+            <boolean key="flag" value="True"/>
+        </event>
+    </trace>
+</log>"#;
+
+#[test]
+pub fn test_read_xes() {
+    let log = read_xes_from(TEST_LOG.as_bytes()).unwrap();
+
+    assert_eq!(log.variants.len(), 2);
+    assert_eq!(log.variants[0].traces_count, 2);
+    assert_eq!(log.variants[1].traces_count, 1);
+
+    let metadata = &log.metadata;
+    assert_eq!(metadata.extensions.as_ref().unwrap().len(), 1);
+    assert_eq!(metadata.classifiers.as_ref().unwrap()[0].keys.len(), 2);
+    assert_eq!(metadata.properties.as_ref().unwrap().len(), 1);
+
+    let globals = metadata.globals.as_ref().unwrap();
+    assert_eq!(globals[0].entity_kind, BxesGlobalKind::Event);
+
+    let first_event = &log.variants[0].events[0];
+    assert_eq!(first_event.name.as_ref(), &string_value("A"));
+    assert_eq!(first_event.timestamp, 1_701_345_600_000_000_000);
+
+    assert_eq!(
+        first_event.lifecycle,
+        Lifecycle::Standard(StandardLifecycle::Start)
+    );
+
+    let attributes = first_event.attributes.as_ref().unwrap();
+    assert_eq!(
+        attributes[0].1.as_ref(),
+        &BxesValue::StandardLifecycle(StandardLifecycle::Start)
+    );
+    assert_eq!(attributes[1].1.as_ref(), &BxesValue::Int64(12));
+
+    let second_event = &log.variants[0].events[1];
+    assert_eq!(
+        second_event.lifecycle,
+        Lifecycle::Braf(BrafLifecycle::ClosedCancelled)
+    );
+
+    let second_event_attributes = second_event.attributes.as_ref().unwrap();
+    assert_eq!(
+        second_event_attributes[0].1.as_ref(),
+        &BxesValue::BrafLifecycle(BrafLifecycle::ClosedCancelled)
+    );
+
+    match second_event_attributes[1].1.as_ref() {
+        BxesValue::Artifact(artifact) => assert_eq!(artifact.items.len(), 1),
+        _ => panic!("Expected artifact value"),
+    }
+
+    match second_event_attributes[2].1.as_ref() {
+        BxesValue::Drivers(drivers) => {
+            assert_eq!(drivers.drivers[0].amount, BxesValue::Float64(1.5))
+        }
+        _ => panic!("Expected drivers value"),
+    }
+
+    let default_name_event = &log.variants[1].events[0];
+    assert_eq!(
+        default_name_event.name.as_ref(),
+        &string_value("__INVALID__")
+    );
+}
+
+#[test]
+pub fn test_convert_xes_to_bxes() {
+    let temp_dir = TempDir::new().unwrap();
+    let xes_path = temp_dir.path().join("log.xes");
+    let bxes_path = temp_dir.path().join("log.bxes");
+    std::fs::write(&xes_path, TEST_LOG).unwrap();
+
+    let xes_path = xes_path.to_str().unwrap();
+    let bxes_path = bxes_path.to_str().unwrap();
+    convert_xes_to_bxes(xes_path, bxes_path).unwrap();
+
+    let xes_log = read_xes(xes_path).unwrap();
+    let bxes_log = read_bxes(bxes_path).unwrap();
+
+    assert!(bxes_log.eq(&xes_log));
+}
+
+#[test]
+pub fn test_xes_read_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let xes_path = temp_dir.path().join("missing.xes");
+    let bxes_path = temp_dir.path().join("log.bxes");
+
+    let err =
+        convert_xes_to_bxes(xes_path.to_str().unwrap(), bxes_path.to_str().unwrap()).unwrap_err();
+
+    assert!(matches!(
+        err,
+        XesToBxesConversionError::XesRead(XesReadError::FailedToOpenFile(_))
+    ));
+    assert!(err.source().unwrap().source().is_some());
+
+    let err = read_xes_from(r#"<log><trace></log>"#.as_bytes()).unwrap_err();
+    assert!(matches!(err, XesReadError::XmlError(_)));
+}
+
+fn string_value(value: &str) -> BxesValue {
+    BxesValue::String(Arc::from(value))
+}